@@ -0,0 +1,95 @@
+// An interactive chat in the terminal, using the streaming chat completion endpoint
+// Usage: OPENAI_API_KEY=(your key) cargo run --example chat_repl -- [--model gpt-3.5-turbo] [--system "You are a pirate"]
+use openai_rust::chat::{ChatArguments, Message};
+use openai_rust::futures_util::StreamExt;
+use std::io::{BufRead, Write};
+
+const USAGE: &str = "usage: chat_repl [--model <model>] [--system <prompt>]";
+
+struct Options {
+    model: String,
+    system: Option<String>,
+}
+
+fn parse_args() -> Options {
+    let mut options = Options {
+        model: "gpt-3.5-turbo".to_owned(),
+        system: None,
+    };
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-m" | "--model" => options.model = args.next().expect(USAGE),
+            "-s" | "--system" => options.system = Some(args.next().expect(USAGE)),
+            _ => {
+                eprintln!("{}", USAGE);
+                std::process::exit(1);
+            }
+        }
+    }
+    options
+}
+
+fn initial_messages(options: &Options) -> Vec<Message> {
+    match &options.system {
+        Some(system) => vec![Message::system(system)],
+        None => vec![],
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    let options = parse_args();
+    let client = openai_rust::Client::new(&std::env::var("OPENAI_API_KEY").unwrap());
+    let mut messages = initial_messages(&options);
+
+    println!("Chatting with {}. Type /reset to start over and /exit to quit.", options.model);
+    let stdin = std::io::stdin();
+    loop {
+        print!("> ");
+        std::io::stdout().flush().unwrap();
+
+        let mut line = String::new();
+        if stdin.lock().read_line(&mut line).unwrap() == 0 {
+            break;
+        }
+        match line.trim() {
+            "" => continue,
+            "/exit" => break,
+            "/reset" => {
+                messages = initial_messages(&options);
+                continue;
+            }
+            input => messages.push(Message::user(input)),
+        }
+
+        let args = ChatArguments::new(&options.model, messages.clone());
+        let mut stream = match client.create_chat_stream(args).await {
+            Ok(stream) => stream,
+            Err(e) => {
+                eprintln!("error: {}", e);
+                messages.pop();
+                continue;
+            }
+        };
+
+        let mut reply = String::new();
+        while let Some(chunk) = stream.next().await {
+            match chunk {
+                Ok(chunk) => {
+                    let content = chunk.to_string();
+                    print!("{}", content);
+                    std::io::stdout().flush().unwrap();
+                    reply.push_str(&content);
+                }
+                Err(e) => {
+                    eprintln!("\nerror: {}", e);
+                    break;
+                }
+            }
+        }
+        println!();
+
+        messages.push(Message::assistant(reply));
+    }
+}
@@ -0,0 +1,240 @@
+//! See <https://platform.openai.com/docs/api-reference/assistants>.
+//! Use with [Client::create_assistant](crate::Client::create_assistant), [Client::list_assistants](crate::Client::list_assistants),
+//! [Client::retrieve_assistant](crate::Client::retrieve_assistant), [Client::modify_assistant](crate::Client::modify_assistant)
+//! and [Client::delete_assistant](crate::Client::delete_assistant).
+
+use crate::tools::FunctionDefinition;
+use serde::{Deserialize, Serialize};
+
+/// Request arguments for creating an assistant.
+///
+/// See <https://platform.openai.com/docs/api-reference/assistants/createAssistant>.
+///
+/// ```
+/// use openai_rust::assistants::*;
+/// let mut args = AssistantArguments::new("gpt-4o");
+/// args.instructions = Some("You are a helpful product support assistant.".to_owned());
+/// args.tools = Some(vec![Tool::file_search(FileSearch {
+///     max_num_results: Some(5),
+///     ranking_options: Some(RankingOptions {
+///         ranker: None,
+///         score_threshold: 0.5,
+///     }),
+/// })]);
+/// args.tool_resources = Some(ToolResources {
+///     file_search: Some(FileSearchResources {
+///         vector_store_ids: vec!["vs_abc123".to_owned()],
+///     }),
+///     ..Default::default()
+/// });
+/// ```
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct AssistantArguments {
+    /// ID of the model to use.
+    pub model: String,
+
+    /// The name of the assistant. The maximum length is 256 characters.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    /// The description of the assistant. The maximum length is 512 characters.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// The system instructions that the assistant uses. The maximum length is 256,000 characters.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instructions: Option<String>,
+
+    /// A list of tools enabled on the assistant. There can be a maximum of 128 tools per assistant.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tools: Option<Vec<Tool>>,
+
+    /// Resources used by the tools of the assistant, such as the vector stores searched by [Tool::FileSearch].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_resources: Option<ToolResources>,
+
+    /// What sampling temperature to use, between 0 and 2. Higher values like 0.8 will make the output more random, while lower values like 0.2 will make it more focused and deterministic.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f32>,
+
+    /// An alternative to sampling with temperature, called nucleus sampling, where the model considers the results of the tokens with top_p probability mass. So 0.1 means only the tokens comprising the top 10% probability mass are considered.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f32>,
+
+    /// Additional parameters sent in the body, for parameters not yet supported by this crate or specific to a provider.
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    pub extra: Option<serde_json::Map<String, serde_json::Value>>,
+}
+
+impl AssistantArguments {
+    pub fn new(model: impl AsRef<str>) -> AssistantArguments {
+        AssistantArguments {
+            model: model.as_ref().to_owned(),
+            name: None,
+            description: None,
+            instructions: None,
+            tools: None,
+            tool_resources: None,
+            temperature: None,
+            top_p: None,
+            extra: None,
+        }
+    }
+}
+
+/// Request arguments for modifying an assistant. Only the given fields are changed.
+///
+/// See <https://platform.openai.com/docs/api-reference/assistants/modifyAssistant>.
+///
+/// ```
+/// use openai_rust::assistants::ModifyAssistantArguments;
+/// let mut args = ModifyAssistantArguments::new();
+/// args.instructions = Some("You are a helpful product support assistant. Answer in English.".to_owned());
+/// assert_eq!(serde_json::to_value(&args).unwrap().as_object().unwrap().len(), 1);
+/// ```
+#[derive(Serialize, Debug, Clone, Default, PartialEq)]
+#[non_exhaustive]
+pub struct ModifyAssistantArguments {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instructions: Option<String>,
+
+    /// Replaces all tools of the assistant.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tools: Option<Vec<Tool>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_resources: Option<ToolResources>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f32>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f32>,
+
+    /// Additional parameters sent in the body, for parameters not yet supported by this crate or specific to a provider.
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    pub extra: Option<serde_json::Map<String, serde_json::Value>>,
+}
+
+impl ModifyAssistantArguments {
+    pub fn new() -> ModifyAssistantArguments {
+        ModifyAssistantArguments::default()
+    }
+}
+
+/// The response of [Client::delete_assistant](crate::Client::delete_assistant).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct DeletedAssistant {
+    pub id: String,
+    pub deleted: bool,
+}
+
+/// An assistant that can call the model and use tools.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct Assistant {
+    pub id: String,
+    pub created_at: u64,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub model: String,
+    pub instructions: Option<String>,
+    #[serde(default)]
+    pub tools: Vec<Tool>,
+    pub tool_resources: Option<ToolResources>,
+    pub temperature: Option<f32>,
+    pub top_p: Option<f32>,
+}
+
+/// A tool enabled on an assistant or run.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Tool {
+    /// Lets the assistant write and run Python code in a sandbox.
+    CodeInterpreter,
+    /// Lets the assistant search the vector stores in [ToolResources::file_search].
+    FileSearch {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        file_search: Option<FileSearch>,
+    },
+    /// Lets the assistant call a function, after which the run waits for its output.
+    /// See [Client::submit_tool_outputs](crate::Client::submit_tool_outputs).
+    Function { function: FunctionDefinition },
+}
+
+impl Tool {
+    /// A function tool.
+    pub fn function(function: FunctionDefinition) -> Tool {
+        Tool::Function { function }
+    }
+
+
+    /// A file search tool with the given options.
+    pub fn file_search(options: FileSearch) -> Tool {
+        Tool::FileSearch {
+            file_search: Some(options),
+        }
+    }
+}
+
+/// Options for the [Tool::FileSearch] tool.
+///
+/// See <https://platform.openai.com/docs/assistants/tools/file-search/customizing-file-search-settings>.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct FileSearch {
+    /// The maximum number of results the file search tool should output, between 1 and 50.
+    /// Defaults to 20 for gpt-4 models and 5 for gpt-3.5-turbo.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_num_results: Option<u32>,
+
+    /// How the results are ranked. Defaults to the `auto` ranker with a score threshold of 0.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ranking_options: Option<RankingOptions>,
+}
+
+/// Ranking options for the [Tool::FileSearch] tool.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RankingOptions {
+    /// The ranker to use, `auto` or `default_2024_08_21`. Defaults to `auto`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ranker: Option<String>,
+
+    /// The score threshold for results, between 0 and 1. Results with a lower score are left out.
+    pub score_threshold: f32,
+}
+
+/// Resources made available to the tools of an assistant.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct ToolResources {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code_interpreter: Option<CodeInterpreterResources>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file_search: Option<FileSearchResources>,
+}
+
+/// Resources for the [Tool::CodeInterpreter] tool.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct CodeInterpreterResources {
+    /// The IDs of files made available to the code interpreter. There can be a maximum of 20 files.
+    #[serde(default)]
+    pub file_ids: Vec<String>,
+}
+
+/// Resources for the [Tool::FileSearch] tool.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct FileSearchResources {
+    /// The IDs of the vector stores to search. There can be a maximum of 1 vector store.
+    #[serde(default)]
+    pub vector_store_ids: Vec<String>,
+}
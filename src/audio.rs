@@ -0,0 +1,390 @@
+//! See <https://platform.openai.com/docs/api-reference/audio>.
+//! Use with [Client::create_speech](crate::Client::create_speech), [Client::create_speech_chunks](crate::Client::create_speech_chunks),
+//! [Client::create_speech_stream](crate::Client::create_speech_stream)
+//! and [Client::create_transcription](crate::Client::create_transcription).
+
+use crate::files::Upload;
+use serde::{Deserialize, Serialize};
+
+/// Request arguments for generating audio from text.
+///
+/// See <https://platform.openai.com/docs/api-reference/audio/createSpeech>.
+///
+/// ```
+/// use openai_rust::audio::{AudioFormat, SpeechArguments, Voice};
+/// let mut args = SpeechArguments::new("tts-1", "The quick brown fox jumped over the lazy dog.", Voice::Nova);
+/// args.response_format = Some(AudioFormat::Opus);
+///
+/// let mut args = SpeechArguments::new("gpt-4o-mini-tts", "Today is a wonderful day!", Voice::Coral);
+/// args.instructions = Some("Speak in a cheerful and positive tone.".to_owned());
+/// ```
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct SpeechArguments {
+    /// One of the TTS models, like `tts-1`, `tts-1-hd` or `gpt-4o-mini-tts`.
+    pub model: String,
+
+    /// The text to generate audio for. The maximum length is 4096 characters.
+    pub input: String,
+
+    /// The voice to use when generating the audio.
+    pub voice: Voice,
+
+    /// The format of the audio. Defaults to [AudioFormat::Mp3].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response_format: Option<AudioFormat>,
+
+    /// The speed of the generated audio, from 0.25 to 4.0. Defaults to 1.0.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub speed: Option<f32>,
+
+    /// How the voice speaks, like "Speak in a cheerful tone" or "Whisper".
+    /// Only supported by `gpt-4o-mini-tts`, not by `tts-1` and `tts-1-hd`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instructions: Option<String>,
+
+    /// Additional parameters sent in the body, for parameters not yet supported by this crate or specific to a provider.
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    pub extra: Option<serde_json::Map<String, serde_json::Value>>,
+}
+
+impl SpeechArguments {
+    pub fn new(model: impl AsRef<str>, input: impl AsRef<str>, voice: Voice) -> SpeechArguments {
+        SpeechArguments {
+            model: model.as_ref().to_owned(),
+            input: input.as_ref().to_owned(),
+            voice,
+            response_format: None,
+            speed: None,
+            instructions: None,
+            extra: None,
+        }
+    }
+}
+
+/// The voices available for speech.
+/// Previews can be found in the [Text to speech guide](https://platform.openai.com/docs/guides/text-to-speech#voice-options).
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Voice {
+    Alloy,
+    Ash,
+    Ballad,
+    Coral,
+    Echo,
+    Fable,
+    Onyx,
+    Nova,
+    Sage,
+    Shimmer,
+    Verse,
+}
+
+/// The format of generated speech.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum AudioFormat {
+    Mp3,
+    /// For internet streaming and communication, low latency.
+    Opus,
+    Aac,
+    /// Lossless compression.
+    Flac,
+    /// Uncompressed, suitable for low-latency applications to avoid decoding overhead.
+    Wav,
+    /// Raw samples in 24kHz (16-bit signed, low-endian), without the header.
+    Pcm,
+}
+
+/// Request arguments for transcribing audio into the input language.
+///
+/// See <https://platform.openai.com/docs/api-reference/audio/createTranscription>.
+///
+/// ```
+/// use openai_rust::audio::*;
+/// use openai_rust::files::Upload;
+/// # let audio = vec![];
+/// let mut args = TranscriptionArguments::new("whisper-1", Upload::bytes(audio, "interview.mp3"));
+/// args.language = Some("nl".to_owned());
+/// args.prompt = Some("An interview about Rust, Tokio and OpenAI.".to_owned());
+/// args.response_format = Some(TranscriptionFormat::VerboseJson);
+/// args.timestamp_granularities = Some(vec![TimestampGranularity::Word, TimestampGranularity::Segment]);
+/// ```
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct TranscriptionArguments {
+    /// ID of the model to use, like `whisper-1` or `gpt-4o-transcribe`.
+    pub model: String,
+
+    /// The audio file, in one of these formats: flac, mp3, mp4, mpeg, mpga, m4a, ogg, wav, or webm.
+    /// The format is derived from the extension of its name.
+    pub file: Upload,
+
+    /// The language of the input audio in [ISO-639-1](https://en.wikipedia.org/wiki/List_of_ISO_639-1_codes) format, like `en`.
+    /// Supplying the language improves accuracy and latency.
+    pub language: Option<String>,
+
+    /// Text to guide the style of the model or continue a previous audio segment,
+    /// which can be used to hint the spelling of uncommon words. It should match the language of the audio.
+    pub prompt: Option<String>,
+
+    /// The format of the transcript. Defaults to [TranscriptionFormat::Json].
+    pub response_format: Option<TranscriptionFormat>,
+
+    /// The sampling temperature, between 0 and 1. Higher values like 0.8 will make the output more random, while lower values like 0.2 will make it more focused and deterministic.
+    /// If set to 0, the model will use log probability to automatically increase the temperature until certain thresholds are hit.
+    pub temperature: Option<f32>,
+
+    /// The timestamps to include in the transcript. Requires [TranscriptionFormat::VerboseJson].
+    pub timestamp_granularities: Option<Vec<TimestampGranularity>>,
+
+    /// Additional parameters sent as form fields, for parameters not yet supported by this crate or specific to a provider.
+    /// Strings are sent as is, other values as JSON.
+    pub extra: Option<serde_json::Map<String, serde_json::Value>>,
+}
+
+impl TranscriptionArguments {
+    pub fn new(model: impl AsRef<str>, file: Upload) -> TranscriptionArguments {
+        TranscriptionArguments {
+            model: model.as_ref().to_owned(),
+            file,
+            language: None,
+            prompt: None,
+            response_format: None,
+            temperature: None,
+            timestamp_granularities: None,
+            extra: None,
+        }
+    }
+
+    pub(crate) fn into_form(self) -> reqwest::multipart::Form {
+        let mut form = reqwest::multipart::Form::new()
+            .text("model", self.model)
+            .part("file", self.file.into_part());
+        if let Some(language) = self.language {
+            form = form.text("language", language);
+        }
+        if let Some(prompt) = self.prompt {
+            form = form.text("prompt", prompt);
+        }
+        if let Some(format) = self.response_format {
+            form = form.text("response_format", format.as_str());
+        }
+        if let Some(temperature) = self.temperature {
+            form = form.text("temperature", temperature.to_string());
+        }
+        for granularity in self.timestamp_granularities.unwrap_or_default() {
+            form = form.text("timestamp_granularities[]", granularity.as_str());
+        }
+        crate::files::extra_fields(form, self.extra)
+    }
+}
+
+/// The format of a transcript.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TranscriptionFormat {
+    Json,
+    /// Plain text.
+    Text,
+    /// SubRip subtitles.
+    Srt,
+    /// JSON with the language, duration, and timestamps of the transcript.
+    VerboseJson,
+    /// WebVTT subtitles.
+    Vtt,
+}
+
+impl TranscriptionFormat {
+    fn as_str(&self) -> &'static str {
+        match self {
+            TranscriptionFormat::Json => "json",
+            TranscriptionFormat::Text => "text",
+            TranscriptionFormat::Srt => "srt",
+            TranscriptionFormat::VerboseJson => "verbose_json",
+            TranscriptionFormat::Vtt => "vtt",
+        }
+    }
+
+    /// Whether the transcript is returned as JSON rather than text.
+    pub(crate) fn is_json(&self) -> bool {
+        matches!(self, TranscriptionFormat::Json | TranscriptionFormat::VerboseJson)
+    }
+}
+
+/// The detail of the timestamps in a transcript.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TimestampGranularity {
+    Word,
+    Segment,
+}
+
+impl TimestampGranularity {
+    fn as_str(&self) -> &'static str {
+        match self {
+            TimestampGranularity::Word => "word",
+            TimestampGranularity::Segment => "segment",
+        }
+    }
+}
+
+/// A transcript of audio.
+///
+/// With [TranscriptionFormat::Text], [TranscriptionFormat::Srt] or [TranscriptionFormat::Vtt]
+/// the transcript is in [text](Transcription::text), and the other fields are empty.
+///
+/// It implements [Display](std::fmt::Display) as a shortcut to the text.
+/// ```
+/// # use serde_json;
+/// # let json = r#"{
+/// #   "task": "transcribe",
+/// #   "language": "english",
+/// #   "duration": 8.470000267028809,
+/// #   "text": "The beach was a popular spot on a hot summer day.",
+/// #   "words": [
+/// #     {"word": "The", "start": 0.0, "end": 0.23999999463558197},
+/// #     {"word": "beach", "start": 0.23999999463558197, "end": 0.6200000047683716}
+/// #   ],
+/// #   "segments": [
+/// #     {"id": 0, "seek": 0, "start": 0.0, "end": 3.319999933242798, "text": " The beach was a popular spot on a hot summer day.", "tokens": [50364, 440], "temperature": 0.0, "avg_logprob": -0.2860786020755768, "compression_ratio": 1.2363636493682861, "no_speech_prob": 0.00985979475080967}
+/// #   ]
+/// # }"#;
+/// # let transcript = serde_json::from_str::<openai_rust::audio::Transcription>(json).unwrap();
+/// println!("{}", transcript);
+/// for word in &transcript.words {
+///     println!("{:.2}-{:.2}: {}", word.start, word.end, word.word);
+/// }
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[non_exhaustive]
+pub struct Transcription {
+    pub text: String,
+    /// Only with [TranscriptionFormat::VerboseJson].
+    #[serde(default)]
+    pub language: Option<String>,
+    /// The duration of the audio in seconds, only with [TranscriptionFormat::VerboseJson].
+    #[serde(default)]
+    pub duration: Option<f64>,
+    /// Only with [TimestampGranularity::Word].
+    #[serde(default)]
+    pub words: Vec<Word>,
+    /// Only with [TimestampGranularity::Segment], or [TranscriptionFormat::VerboseJson] without granularities.
+    #[serde(default)]
+    pub segments: Vec<Segment>,
+}
+
+impl Transcription {
+    /// The segments as SubRip subtitles.
+    ///
+    /// Requires [TranscriptionFormat::VerboseJson]. To get subtitles without the segments,
+    /// request [TranscriptionFormat::Srt] instead.
+    ///
+    /// ```
+    /// # let json = r#"{"text": "", "segments": [
+    /// #   {"id": 0, "seek": 0, "start": 0.0, "end": 3.32, "text": " The beach was a popular spot on a hot summer day."},
+    /// #   {"id": 1, "seek": 0, "start": 3.32, "end": 3661.5, "text": " People were swimming."}
+    /// # ]}"#;
+    /// # let transcript = serde_json::from_str::<openai_rust::audio::Transcription>(json).unwrap();
+    /// assert_eq!(transcript.to_srt(), "\
+    /// 1
+    /// 00:00:00,000 --> 00:00:03,320
+    /// The beach was a popular spot on a hot summer day.
+    ///
+    /// 2
+    /// 00:00:03,320 --> 01:01:01,500
+    /// People were swimming.
+    /// ");
+    /// ```
+    pub fn to_srt(&self) -> String {
+        let cues: Vec<String> = self
+            .segments
+            .iter()
+            .enumerate()
+            .map(|(i, segment)| {
+                format!(
+                    "{}\n{} --> {}\n{}\n",
+                    i + 1,
+                    timestamp(segment.start, ','),
+                    timestamp(segment.end, ','),
+                    segment.text.trim()
+                )
+            })
+            .collect();
+        cues.join("\n")
+    }
+
+    /// The segments as WebVTT subtitles.
+    ///
+    /// Requires [TranscriptionFormat::VerboseJson]. To get subtitles without the segments,
+    /// request [TranscriptionFormat::Vtt] instead.
+    ///
+    /// ```
+    /// # let json = r#"{"text": "", "segments": [
+    /// #   {"id": 0, "seek": 0, "start": 0.0, "end": 3.32, "text": " The beach was a popular spot on a hot summer day."}
+    /// # ]}"#;
+    /// # let transcript = serde_json::from_str::<openai_rust::audio::Transcription>(json).unwrap();
+    /// assert_eq!(transcript.to_vtt(), "\
+    /// WEBVTT
+    ///
+    /// 00:00:00.000 --> 00:00:03.320
+    /// The beach was a popular spot on a hot summer day.
+    /// ");
+    /// ```
+    pub fn to_vtt(&self) -> String {
+        let mut vtt = "WEBVTT\n".to_owned();
+        for segment in &self.segments {
+            vtt += &format!(
+                "\n{} --> {}\n{}\n",
+                timestamp(segment.start, '.'),
+                timestamp(segment.end, '.'),
+                segment.text.trim()
+            );
+        }
+        vtt
+    }
+}
+
+/// Seconds as `hh:mm:ss` followed by the milliseconds.
+fn timestamp(seconds: f64, separator: char) -> String {
+    let millis = (seconds.max(0.0) * 1000.0).round() as u64;
+    format!(
+        "{:02}:{:02}:{:02}{}{:03}",
+        millis / 3_600_000,
+        millis / 60_000 % 60,
+        millis / 1000 % 60,
+        separator,
+        millis % 1000
+    )
+}
+
+impl std::fmt::Display for Transcription {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.text)
+    }
+}
+
+/// A word of a [Transcription], with its start and end in seconds.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct Word {
+    pub word: String,
+    pub start: f64,
+    pub end: f64,
+}
+
+/// A segment of a [Transcription], with its start and end in seconds.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct Segment {
+    pub id: u32,
+    pub start: f64,
+    pub end: f64,
+    pub text: String,
+    /// The average log probability of the segment. Segments below -1 are likely to be wrong.
+    #[serde(default)]
+    pub avg_logprob: f64,
+    /// The probability that the segment contains no speech.
+    #[serde(default)]
+    pub no_speech_prob: f64,
+}
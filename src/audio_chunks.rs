@@ -0,0 +1,265 @@
+//! Transcribe audio larger than the [MAX_FILE_SIZE] accepted by the API, by splitting it into chunks.
+//! Use with [Client::create_chunked_transcription](crate::Client::create_chunked_transcription).
+//!
+//! A [Splitter] cuts the audio into chunks and tells at which second each chunk starts.
+//! The chunks are transcribed concurrently, and [stitch] joins the transcripts,
+//! moving the timestamps of the words and segments to where they are in the full audio.
+//!
+//! [WavSplitter] splits PCM WAV files, optionally at the quietest moment before the size limit,
+//! so words are not cut in half. Compressed formats can be split with a [Splitter] using a tool like ffmpeg.
+//!
+//! ```
+//! use std::time::Duration;
+//! use openai_rust::audio_chunks::{Splitter, WavSplitter};
+//! # let wav = |seconds: u32| {
+//! #     let data = vec![0; 16_000 * 2 * seconds as usize];
+//! #     let mut wav = b"RIFF".to_vec();
+//! #     wav.extend((36 + data.len() as u32).to_le_bytes());
+//! #     wav.extend(b"WAVEfmt ");
+//! #     wav.extend(16u32.to_le_bytes());
+//! #     wav.extend([1, 0, 1, 0]);
+//! #     wav.extend(16_000u32.to_le_bytes());
+//! #     wav.extend(32_000u32.to_le_bytes());
+//! #     wav.extend([2, 0, 16, 0]);
+//! #     wav.extend(b"data");
+//! #     wav.extend((data.len() as u32).to_le_bytes());
+//! #     wav.extend(data);
+//! #     wav
+//! # };
+//! let audio = wav(50); // 50 seconds of 16 kHz mono audio
+//! let splitter = WavSplitter::new(1_000_000).at_silence(Duration::from_secs(5));
+//! let chunks = splitter.split(audio, "interview.wav").unwrap();
+//! assert_eq!(chunks.len(), 2);
+//! assert_eq!(chunks[1].filename, "interview-2.wav");
+//! assert!(chunks[1].offset > 25.0 && chunks[1].offset <= 31.25);
+//! ```
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+
+use crate::audio::{Segment, Transcription, Word};
+
+/// The maximum size of an audio file accepted by the API, 25 MB.
+pub const MAX_FILE_SIZE: usize = 25 * 1024 * 1024;
+
+/// A part of a longer audio file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Chunk {
+    pub audio: Vec<u8>,
+    /// The file name, with the extension of the format of the audio.
+    pub filename: String,
+    /// Where the chunk starts in the full audio, in seconds.
+    pub offset: f64,
+}
+
+/// Cuts audio into chunks of at most [MAX_FILE_SIZE].
+pub trait Splitter {
+    /// Split audio with the given file name into chunks, in order.
+    fn split(&self, audio: Vec<u8>, filename: &str) -> Result<Vec<Chunk>>;
+}
+
+impl<F: Fn(Vec<u8>, &str) -> Result<Vec<Chunk>>> Splitter for F {
+    fn split(&self, audio: Vec<u8>, filename: &str) -> Result<Vec<Chunk>> {
+        self(audio, filename)
+    }
+}
+
+/// Splits PCM WAV files into WAV files of at most a number of bytes.
+///
+/// Audio that is already small enough is returned as a single chunk, in any format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WavSplitter {
+    max_bytes: usize,
+    search: Option<Duration>,
+}
+
+/// The size of the header written before each chunk, without the `fmt ` data.
+const HEADER_SIZE: usize = 12 + 8 + 8;
+
+/// The length of the windows compared when looking for silence.
+const SILENCE_WINDOW: Duration = Duration::from_millis(20);
+
+impl WavSplitter {
+    /// Chunks of at most `max_bytes`, including the header.
+    pub fn new(max_bytes: usize) -> WavSplitter {
+        WavSplitter { max_bytes, search: None }
+    }
+
+    /// Cut at the quietest moment within `search` before the size limit, instead of at the limit itself.
+    ///
+    /// Only 16-bit audio is searched for silence, other audio is cut at the limit.
+    pub fn at_silence(mut self, search: Duration) -> WavSplitter {
+        self.search = Some(search);
+        self
+    }
+
+    /// The frame to cut at, at or before `limit`.
+    fn cut(&self, wav: &Wav, start: usize, limit: usize) -> usize {
+        let Some(search) = self.search.filter(|_| wav.bits_per_sample == 16) else {
+            return limit;
+        };
+        let window = ((wav.sample_rate as f64 * SILENCE_WINDOW.as_secs_f64()) as usize).max(1);
+        let search = (wav.sample_rate as f64 * search.as_secs_f64()) as usize;
+        let from = limit.saturating_sub(search).max(start + window);
+        let mut quietest = (u64::MAX, limit);
+        let mut frame = from;
+        while frame + window <= limit {
+            let energy: u64 = (frame..frame + window).map(|frame| wav.energy(frame)).sum();
+            if energy < quietest.0 {
+                quietest = (energy, frame + window / 2);
+            }
+            frame += window;
+        }
+        quietest.1
+    }
+}
+
+impl Splitter for WavSplitter {
+    fn split(&self, audio: Vec<u8>, filename: &str) -> Result<Vec<Chunk>> {
+        if audio.len() <= self.max_bytes {
+            return Ok(vec![Chunk {
+                audio,
+                filename: filename.to_owned(),
+                offset: 0.0,
+            }]);
+        }
+        let wav = Wav::parse(&audio)?;
+        let header = HEADER_SIZE + wav.fmt.len();
+        let frames_per_chunk = self.max_bytes.saturating_sub(header) / wav.block_align;
+        if frames_per_chunk == 0 {
+            return Err(anyhow!("{} bytes is too small for a chunk of {}", self.max_bytes, filename));
+        }
+        let (stem, extension) = filename.rsplit_once('.').unwrap_or((filename, "wav"));
+
+        let mut chunks = vec![];
+        let mut start = 0;
+        while start < wav.frames() {
+            let limit = start + frames_per_chunk;
+            let end = match limit < wav.frames() {
+                true => self.cut(&wav, start, limit),
+                false => wav.frames(),
+            };
+            chunks.push(Chunk {
+                audio: wav.write(start, end),
+                filename: format!("{}-{}.{}", stem, chunks.len() + 1, extension),
+                offset: start as f64 / wav.sample_rate as f64,
+            });
+            start = end;
+        }
+        Ok(chunks)
+    }
+}
+
+/// The parts of a WAV file needed to split it.
+struct Wav<'a> {
+    /// The data of the `fmt ` chunk, copied into every chunk.
+    fmt: &'a [u8],
+    data: &'a [u8],
+    sample_rate: u32,
+    block_align: usize,
+    bits_per_sample: u16,
+}
+
+impl<'a> Wav<'a> {
+    fn parse(wav: &'a [u8]) -> Result<Wav<'a>> {
+        if wav.len() < 12 || &wav[0..4] != b"RIFF" || &wav[8..12] != b"WAVE" {
+            return Err(anyhow!("only WAV files can be split"));
+        }
+        let (mut fmt, mut data) = (None, None);
+        let mut rest = &wav[12..];
+        while rest.len() >= 8 {
+            let size = u32::from_le_bytes(rest[4..8].try_into().unwrap()) as usize;
+            let body = &rest[8..rest.len().min(8 + size)];
+            match &rest[0..4] {
+                b"fmt " => fmt = Some(body),
+                b"data" => data = Some(body),
+                _ => {}
+            }
+            rest = &rest[rest.len().min(8 + size + size % 2)..];
+        }
+        let fmt = fmt.filter(|fmt| fmt.len() >= 16).ok_or_else(|| anyhow!("the WAV file has no format"))?;
+        let data = data.ok_or_else(|| anyhow!("the WAV file has no data"))?;
+        let block_align = u16::from_le_bytes([fmt[12], fmt[13]]) as usize;
+        if block_align == 0 {
+            return Err(anyhow!("the WAV file has an invalid format"));
+        }
+        Ok(Wav {
+            fmt,
+            data,
+            sample_rate: u32::from_le_bytes(fmt[4..8].try_into().unwrap()),
+            block_align,
+            bits_per_sample: u16::from_le_bytes([fmt[14], fmt[15]]),
+        })
+    }
+
+    fn frames(&self) -> usize {
+        self.data.len() / self.block_align
+    }
+
+    /// The loudness of a frame of 16-bit audio, summed over the channels.
+    fn energy(&self, frame: usize) -> u64 {
+        self.data[frame * self.block_align..(frame + 1) * self.block_align]
+            .chunks_exact(2)
+            .map(|sample| i16::from_le_bytes([sample[0], sample[1]]).unsigned_abs() as u64)
+            .sum()
+    }
+
+    /// A WAV file with the frames from `start` until `end`.
+    fn write(&self, start: usize, end: usize) -> Vec<u8> {
+        let data = &self.data[start * self.block_align..end * self.block_align];
+        let mut wav = Vec::with_capacity(HEADER_SIZE + self.fmt.len() + data.len());
+        wav.extend_from_slice(b"RIFF");
+        wav.extend_from_slice(&((HEADER_SIZE - 8 + self.fmt.len() + data.len()) as u32).to_le_bytes());
+        wav.extend_from_slice(b"WAVEfmt ");
+        wav.extend_from_slice(&(self.fmt.len() as u32).to_le_bytes());
+        wav.extend_from_slice(self.fmt);
+        wav.extend_from_slice(b"data");
+        wav.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        wav.extend_from_slice(data);
+        wav
+    }
+}
+
+/// Join the transcripts of chunks starting at the given offsets in seconds, in order.
+///
+/// The timestamps of words and segments are moved by the offset of their chunk, and segments are numbered again.
+///
+/// ```
+/// use openai_rust::audio_chunks::stitch;
+/// # let first = serde_json::from_str(r#"{"text": "Hello.", "duration": 30.0, "segments": [{"id": 0, "start": 0.0, "end": 1.0, "text": " Hello."}]}"#).unwrap();
+/// # let second = serde_json::from_str(r#"{"text": "Goodbye.", "duration": 10.0, "segments": [{"id": 0, "start": 2.0, "end": 3.0, "text": " Goodbye."}]}"#).unwrap();
+/// let transcript = stitch(vec![(0.0, first), (30.0, second)]);
+/// assert_eq!(transcript.text, "Hello. Goodbye.");
+/// assert_eq!(transcript.duration, Some(40.0));
+/// assert_eq!((transcript.segments[1].id, transcript.segments[1].start), (1, 32.0));
+/// ```
+pub fn stitch(parts: Vec<(f64, Transcription)>) -> Transcription {
+    let mut stitched = Transcription::default();
+    let mut texts = vec![];
+    for (offset, part) in parts {
+        if stitched.language.is_none() {
+            stitched.language = part.language;
+        }
+        if let Some(duration) = part.duration {
+            stitched.duration = Some(offset + duration);
+        }
+        stitched.words.extend(part.words.into_iter().map(|word| Word {
+            start: word.start + offset,
+            end: word.end + offset,
+            ..word
+        }));
+        for segment in part.segments {
+            stitched.segments.push(Segment {
+                id: stitched.segments.len() as u32,
+                start: segment.start + offset,
+                end: segment.end + offset,
+                ..segment
+            });
+        }
+        if !part.text.trim().is_empty() {
+            texts.push(part.text.trim().to_owned());
+        }
+    }
+    stitched.text = texts.join(" ");
+    stitched
+}
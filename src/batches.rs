@@ -0,0 +1,335 @@
+//! See <https://platform.openai.com/docs/api-reference/batch>.
+//! Use with [Client::create_batch](crate::Client::create_batch), [Client::list_batches](crate::Client::list_batches),
+//! [Client::cancel_batch](crate::Client::cancel_batch) and [Client::wait_for_batch](crate::Client::wait_for_batch).
+//!
+//! The results of a batch are written to an output file, and requests that failed to an error file.
+//! Both are JSONL files with one result per line, which can be parsed with [parse_output].
+//!
+//! ```
+//! # let jsonl = r#"{"id": "batch_req_123", "custom_id": "request-2", "response": {"status_code": 200, "request_id": "req_123", "body": {"id": "chatcmpl-123", "object": "chat.completion", "created": 1711652795, "model": "gpt-3.5-turbo-0125", "choices": [{"index": 0, "message": {"role": "assistant", "content": "Hello."}, "logprobs": null, "finish_reason": "stop"}], "usage": {"prompt_tokens": 22, "completion_tokens": 2, "total_tokens": 24}, "system_fingerprint": "fp_123"}}, "error": null}
+//! # {"id": "batch_req_456", "custom_id": "request-1", "response": null, "error": {"code": "invalid_request", "message": "The request was invalid."}}
+//! # "#;
+//! use openai_rust::batches::parse_output;
+//! use openai_rust::chat::ChatCompletion;
+//! for output in parse_output::<ChatCompletion>(jsonl).unwrap() {
+//!     match output.result {
+//!         Ok(completion) => println!("{}: {}", output.custom_id, completion),
+//!         Err(err) => println!("{} failed: {}", output.custom_id, err),
+//!     }
+//! }
+//! ```
+use anyhow::{anyhow, Result};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Request arguments for creating a batch, from an uploaded file made with [input_jsonl].
+///
+/// See <https://platform.openai.com/docs/api-reference/batch/create>.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct BatchArguments {
+    pub input_file_id: String,
+    /// The endpoint used by all requests of the batch, like `/v1/chat/completions`.
+    pub endpoint: String,
+    /// The time frame within which the batch should be processed. Only `24h` is supported.
+    pub completion_window: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<std::collections::HashMap<String, String>>,
+}
+
+impl BatchArguments {
+    pub fn new(input_file_id: &str, endpoint: &str) -> BatchArguments {
+        BatchArguments {
+            input_file_id: input_file_id.to_owned(),
+            endpoint: endpoint.to_owned(),
+            completion_window: "24h".to_owned(),
+            metadata: None,
+        }
+    }
+}
+
+/// A batch of requests, processed asynchronously within 24 hours.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct Batch {
+    pub id: String,
+    /// The endpoint used by the requests, like `/v1/chat/completions`.
+    pub endpoint: String,
+    pub input_file_id: String,
+    /// One of `validating`, `failed`, `in_progress`, `finalizing`, `completed`, `expired`, `cancelling`, or `cancelled`.
+    pub status: String,
+    /// The file with the results of the successful requests.
+    pub output_file_id: Option<String>,
+    /// The file with the results of the failed requests.
+    pub error_file_id: Option<String>,
+    pub created_at: u64,
+    pub completed_at: Option<u64>,
+    #[serde(default)]
+    pub request_counts: RequestCounts,
+    /// Why the batch failed validation.
+    #[serde(default)]
+    pub errors: Option<BatchErrors>,
+}
+
+impl Batch {
+    /// Whether the batch will not change anymore.
+    pub fn is_finished(&self) -> bool {
+        matches!(self.status.as_str(), "completed" | "failed" | "expired" | "cancelled")
+    }
+}
+
+/// The progress of a [Batch].
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+#[serde(default)]
+pub struct RequestCounts {
+    pub total: u32,
+    pub completed: u32,
+    pub failed: u32,
+}
+
+/// The errors of a [Batch] that failed validation.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[non_exhaustive]
+pub struct BatchErrors {
+    #[serde(default)]
+    pub data: Vec<BatchError>,
+}
+
+type ProgressCallback = Box<dyn Fn(&RequestCounts) + Send + Sync>;
+
+/// How [Client::wait_for_batch](crate::Client::wait_for_batch) polls a batch.
+///
+/// The batch is polled after [interval](WaitOptions::interval), which is multiplied by
+/// [backoff](WaitOptions::backoff) after every poll until it reaches [max_interval](WaitOptions::max_interval).
+///
+/// ```
+/// use std::time::Duration;
+/// use openai_rust::batches::WaitOptions;
+/// let options = WaitOptions::new()
+///     .interval(Duration::from_secs(10))
+///     .timeout(Duration::from_secs(60 * 60))
+///     .on_progress(|counts| println!("{}/{} done", counts.completed + counts.failed, counts.total));
+/// ```
+pub struct WaitOptions {
+    interval: Duration,
+    max_interval: Duration,
+    backoff: f64,
+    timeout: Option<Duration>,
+    on_progress: Option<ProgressCallback>,
+}
+
+impl Default for WaitOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WaitOptions {
+    /// Poll after 5 seconds, backing off by a factor of 1.5 up to 5 minutes, without a timeout.
+    pub fn new() -> WaitOptions {
+        WaitOptions {
+            interval: Duration::from_secs(5),
+            max_interval: Duration::from_secs(5 * 60),
+            backoff: 1.5,
+            timeout: None,
+            on_progress: None,
+        }
+    }
+
+    /// The time to wait before the first poll. Defaults to 5 seconds.
+    pub fn interval(mut self, interval: Duration) -> WaitOptions {
+        self.interval = interval;
+        self
+    }
+
+    /// The maximum time between polls. Defaults to 5 minutes.
+    pub fn max_interval(mut self, max_interval: Duration) -> WaitOptions {
+        self.max_interval = max_interval;
+        self
+    }
+
+    /// The factor the interval grows by after every poll. Defaults to 1.5.
+    pub fn backoff(mut self, backoff: f64) -> WaitOptions {
+        self.backoff = backoff;
+        self
+    }
+
+    /// Give up after waiting this long. The batch itself keeps running.
+    pub fn timeout(mut self, timeout: Duration) -> WaitOptions {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Called with the progress of the batch after every poll.
+    pub fn on_progress(mut self, on_progress: impl Fn(&RequestCounts) + Send + Sync + 'static) -> WaitOptions {
+        self.on_progress = Some(Box::new(on_progress));
+        self
+    }
+
+    pub(crate) fn progress(&self, counts: &RequestCounts) {
+        if let Some(on_progress) = &self.on_progress {
+            on_progress(counts);
+        }
+    }
+
+    /// The interval after the given one.
+    pub(crate) fn next_interval(&self, interval: Duration) -> Duration {
+        interval.mul_f64(self.backoff).min(self.max_interval)
+    }
+
+    pub(crate) fn first_interval(&self) -> Duration {
+        self.interval
+    }
+
+    pub(crate) fn deadline(&self) -> Option<tokio::time::Instant> {
+        self.timeout.map(|timeout| tokio::time::Instant::now() + timeout)
+    }
+}
+
+/// The result of a single request of a batch.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct BatchOutput<T> {
+    pub id: String,
+    /// The ID given to the request in the input file, used to match results to requests.
+    pub custom_id: String,
+    /// The ID of the request, only present if the request was sent to the API.
+    pub request_id: Option<String>,
+    /// The parsed response body, or the reason the request failed.
+    pub result: Result<T, BatchError>,
+}
+
+/// Why a request of a batch failed.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct BatchError {
+    #[serde(default)]
+    pub code: Option<String>,
+    pub message: String,
+    /// The HTTP status code of the response, if the request was sent to the API.
+    #[serde(skip)]
+    pub status_code: Option<u16>,
+}
+
+impl std::fmt::Display for BatchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(status) = self.status_code {
+            write!(f, "{}: ", status)?;
+        }
+        if let Some(code) = &self.code {
+            write!(f, "{}: ", code)?;
+        }
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for BatchError {}
+
+#[derive(Serialize)]
+struct InputLine<'a, T> {
+    custom_id: &'a str,
+    method: &'static str,
+    url: &'a str,
+    body: &'a T,
+}
+
+/// The input file of a batch, with one `POST` request to `endpoint` per line.
+///
+/// The custom ID of each request is used to match it to its [BatchOutput], and must be unique.
+///
+/// ```
+/// use openai_rust::batches::input_jsonl;
+/// use openai_rust::embeddings::EmbeddingsArguments;
+/// let args = EmbeddingsArguments::new("text-embedding-3-small", "Hello".to_owned());
+/// let jsonl = input_jsonl("/v1/embeddings", [("request-1", &args)]).unwrap();
+/// assert!(jsonl.starts_with(r#"{"custom_id":"request-1","method":"POST","url":"/v1/embeddings","body":{"#));
+/// assert!(jsonl.ends_with("}\n"));
+/// ```
+pub fn input_jsonl<'a, T: Serialize + 'a>(
+    endpoint: &str,
+    requests: impl IntoIterator<Item = (&'a str, &'a T)>,
+) -> serde_json::Result<String> {
+    let mut jsonl = String::new();
+    for (custom_id, body) in requests {
+        let line = InputLine {
+            custom_id,
+            method: "POST",
+            url: endpoint,
+            body,
+        };
+        jsonl += &serde_json::to_string(&line)?;
+        jsonl.push('\n');
+    }
+    Ok(jsonl)
+}
+
+#[derive(Deserialize)]
+struct Line {
+    id: String,
+    custom_id: String,
+    #[serde(default)]
+    response: Option<Response>,
+    #[serde(default)]
+    error: Option<BatchError>,
+}
+
+#[derive(Deserialize)]
+struct Response {
+    status_code: u16,
+    #[serde(default)]
+    request_id: Option<String>,
+    body: serde_json::Value,
+}
+
+#[derive(Deserialize)]
+struct ErrorBody {
+    error: BatchError,
+}
+
+/// Parse the output file or error file of a batch.
+///
+/// Successful response bodies are deserialized into `T`, which should be the response type
+/// of the endpoint the batch was created for, like [ChatCompletion](crate::chat::ChatCompletion)
+/// for `/v1/chat/completions` or [EmbeddingsResponse](crate::embeddings::EmbeddingsResponse) for `/v1/embeddings`.
+///
+/// Fails if a line is not valid JSON or a successful body doesn't match `T`.
+pub fn parse_output<T: DeserializeOwned>(jsonl: &str) -> Result<Vec<BatchOutput<T>>> {
+    jsonl
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(n, line)| parse_line(line).map_err(|err| anyhow!("line {} of batch output: {}", n + 1, err)))
+        .collect()
+}
+
+fn parse_line<T: DeserializeOwned>(line: &str) -> Result<BatchOutput<T>> {
+    let line: Line = serde_json::from_str(line)?;
+    let (request_id, result) = match (line.response, line.error) {
+        (Some(res), _) if (200..300).contains(&res.status_code) => {
+            (res.request_id, Ok(serde_json::from_value(res.body)?))
+        }
+        (Some(res), error) => {
+            let mut error = serde_json::from_value::<ErrorBody>(res.body)
+                .map(|body| body.error)
+                .ok()
+                .or(error)
+                .unwrap_or_else(|| BatchError {
+                    code: None,
+                    message: "request failed".to_owned(),
+                    status_code: None,
+                });
+            error.status_code = Some(res.status_code);
+            (res.request_id, Err(error))
+        }
+        (None, Some(error)) => (None, Err(error)),
+        (None, None) => return Err(anyhow!("result of {} has neither a response nor an error", line.custom_id)),
+    };
+    Ok(BatchOutput {
+        id: line.id,
+        custom_id: line.custom_id,
+        request_id,
+        result,
+    })
+}
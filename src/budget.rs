@@ -0,0 +1,254 @@
+//! A spending cap for the [Client](crate::Client).
+//! Attach with [Client::with_budget](crate::Client::with_budget).
+//!
+//! The budget tracks the tokens reported in the `usage` of every response over a rolling window.
+//! Once the limit is reached, new requests are either rejected with a [BudgetExceeded] error
+//! or deferred until enough of the window has passed.
+//!
+//! ```
+//! use openai_rust::budget::{Budget, Price};
+//! let budget = Budget::dollars_per_day(2.0)
+//!     .price("gpt-3.5-turbo", Price::new(0.5, 1.5));
+//!
+//! // 1M prompt tokens at $0.5 and 1M completion tokens at $1.5
+//! budget.record("gpt-3.5-turbo", 1_000_000, 1_000_000);
+//! assert_eq!(budget.spent(), 2.0);
+//! assert!(budget.check().is_err());
+//! ```
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// What a [Budget] is measured in.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Limit {
+    /// The total amount of prompt and completion tokens.
+    Tokens(u64),
+    /// The estimated cost in dollars, calculated using the configured [Price]s.
+    Dollars(f64),
+}
+
+/// What to do with a request once the [Budget] is exhausted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OnExceeded {
+    /// Fail the request with a [BudgetExceeded] error.
+    Reject,
+    /// Wait until the window has moved far enough to make room for the request.
+    Defer,
+}
+
+/// The price of a model in dollars per million tokens.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Price {
+    pub prompt: f64,
+    pub completion: f64,
+}
+
+impl Price {
+    pub fn new(prompt: f64, completion: f64) -> Price {
+        Price { prompt, completion }
+    }
+}
+
+/// A limit on the amount of tokens or dollars that can be spent over a rolling window.
+#[derive(Debug)]
+pub struct Budget {
+    limit: Limit,
+    window: Duration,
+    on_exceeded: OnExceeded,
+    prices: HashMap<String, Price>,
+    default_price: Option<Price>,
+    spent: Mutex<VecDeque<(Instant, f64)>>,
+}
+
+impl Budget {
+    /// Create a budget of `limit` over every `window`.
+    pub fn new(limit: Limit, window: Duration) -> Budget {
+        Budget {
+            limit,
+            window,
+            on_exceeded: OnExceeded::Reject,
+            prices: HashMap::new(),
+            default_price: None,
+            spent: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    pub fn tokens_per_hour(tokens: u64) -> Budget {
+        Budget::new(Limit::Tokens(tokens), Duration::from_secs(60 * 60))
+    }
+
+    pub fn tokens_per_day(tokens: u64) -> Budget {
+        Budget::new(Limit::Tokens(tokens), Duration::from_secs(24 * 60 * 60))
+    }
+
+    pub fn dollars_per_hour(dollars: f64) -> Budget {
+        Budget::new(Limit::Dollars(dollars), Duration::from_secs(60 * 60))
+    }
+
+    pub fn dollars_per_day(dollars: f64) -> Budget {
+        Budget::new(Limit::Dollars(dollars), Duration::from_secs(24 * 60 * 60))
+    }
+
+    /// Defer requests until there is room in the budget, instead of rejecting them.
+    ///
+    /// Requests are still rejected by a budget of 0, which never has room.
+    pub fn defer(mut self) -> Budget {
+        self.on_exceeded = OnExceeded::Defer;
+        self
+    }
+
+    /// Set the price of a model. This is only used by dollar budgets.
+    ///
+    /// Prices are matched on the longest prefix of the model name,
+    /// so a price for `gpt-4o` also applies to `gpt-4o-2024-08-06`.
+    pub fn price(mut self, model: impl AsRef<str>, price: Price) -> Budget {
+        self.prices.insert(model.as_ref().to_owned(), price);
+        self
+    }
+
+    /// The price used for models without a price of their own.
+    ///
+    /// Without a default price, usage of unknown models is not counted towards a dollar budget.
+    pub fn default_price(mut self, price: Price) -> Budget {
+        self.default_price = Some(price);
+        self
+    }
+
+    pub fn limit(&self) -> Limit {
+        self.limit
+    }
+
+    pub fn window(&self) -> Duration {
+        self.window
+    }
+
+    /// The amount spent within the current window, in tokens or dollars depending on the [Limit].
+    pub fn spent(&self) -> f64 {
+        let mut spent = self.spent.lock().unwrap();
+        self.expire(&mut spent);
+        spent.iter().map(|(_, cost)| cost).sum()
+    }
+
+    /// The amount that can still be spent within the current window.
+    pub fn remaining(&self) -> f64 {
+        (self.max() - self.spent()).max(0.0)
+    }
+
+    /// Count the usage of a request towards the budget.
+    ///
+    /// The [Client](crate::Client) does this automatically for every response that reports usage.
+    pub fn record(&self, model: &str, prompt_tokens: u32, completion_tokens: u32) {
+        let cost = match self.limit {
+            Limit::Tokens(_) => prompt_tokens as f64 + completion_tokens as f64,
+            Limit::Dollars(_) => match self.price_of(model) {
+                Some(price) => {
+                    (prompt_tokens as f64 * price.prompt + completion_tokens as f64 * price.completion)
+                        / 1_000_000.0
+                }
+                None => return,
+            },
+        };
+        let mut spent = self.spent.lock().unwrap();
+        self.expire(&mut spent);
+        spent.push_back((Instant::now(), cost));
+    }
+
+    /// Check if a new request fits in the budget.
+    pub fn check(&self) -> Result<(), BudgetExceeded> {
+        let mut spent = self.spent.lock().unwrap();
+        self.expire(&mut spent);
+        let total: f64 = spent.iter().map(|(_, cost)| cost).sum();
+        if total < self.max() {
+            return Ok(());
+        }
+
+        // The budget frees up once enough of the oldest entries leave the window
+        let mut freed = 0.0;
+        let mut reset_in = Duration::ZERO;
+        for (at, cost) in spent.iter() {
+            freed += cost;
+            reset_in = at
+                .checked_add(self.window)
+                .map_or(Duration::MAX, |end| end.saturating_duration_since(Instant::now()));
+            if total - freed < self.max() {
+                break;
+            }
+        }
+
+        Err(BudgetExceeded {
+            limit: self.limit,
+            spent: total,
+            reset_in,
+        })
+    }
+
+    /// Wait for room in the budget, or fail right away when rejecting.
+    /// A budget of 0 never has room, so it fails right away when deferring too.
+    #[cfg(any(
+        feature = "chat",
+        feature = "completions",
+        feature = "embeddings",
+        feature = "responses",
+    ))]
+    pub(crate) async fn acquire(&self) -> Result<(), BudgetExceeded> {
+        loop {
+            match self.check() {
+                Ok(()) => return Ok(()),
+                Err(e) if self.on_exceeded == OnExceeded::Defer && self.max() > 0.0 => {
+                    tokio::time::sleep(e.reset_in.max(Duration::from_millis(10))).await;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    fn max(&self) -> f64 {
+        match self.limit {
+            Limit::Tokens(tokens) => tokens as f64,
+            Limit::Dollars(dollars) => dollars,
+        }
+    }
+
+    fn price_of(&self, model: &str) -> Option<Price> {
+        self.prices
+            .iter()
+            .filter(|(prefix, _)| model.starts_with(prefix.as_str()))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(_, price)| *price)
+            .or(self.default_price)
+    }
+
+    fn expire(&self, spent: &mut VecDeque<(Instant, f64)>) {
+        while let Some((at, _)) = spent.front() {
+            if at.elapsed() >= self.window {
+                spent.pop_front();
+            } else {
+                break;
+            }
+        }
+    }
+}
+
+/// The error returned when a request is rejected by a [Budget].
+#[derive(Debug, Clone)]
+pub struct BudgetExceeded {
+    /// The limit of the budget.
+    pub limit: Limit,
+    /// The amount spent within the current window.
+    pub spent: f64,
+    /// How long until a new request would fit in the budget.
+    pub reset_in: Duration,
+}
+
+impl std::fmt::Display for BudgetExceeded {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.limit {
+            Limit::Tokens(tokens) => write!(f, "token budget exceeded: {} of {} tokens spent", self.spent, tokens)?,
+            Limit::Dollars(dollars) => write!(f, "dollar budget exceeded: ${:.2} of ${:.2} spent", self.spent, dollars)?,
+        }
+        write!(f, ", resets in {}s", self.reset_in.as_secs())
+    }
+}
+
+impl std::error::Error for BudgetExceeded {}
@@ -1,24 +1,68 @@
 //! See <https://platform.openai.com/docs/api-reference/chat>.
 //! Use with [Client::create_chat](crate::Client::create_chat) or [Client::create_chat_stream](crate::Client::create_chat_stream).
 
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 
+use crate::logit_bias::LogitBias;
+use crate::stop::StopSequences;
+use crate::tools::FunctionDefinition;
+use crate::vision::ImageUrl;
+
 /// Request arguments for chat completion.
 ///
 /// See <https://platform.openai.com/docs/api-reference/chat/create>.
 ///
 /// ```
 /// let args = openai_rust::chat::ChatArguments::new("gpt-3.5-turbo", vec![
-///     openai_rust::chat::Message {
-///         role: "user".to_owned(),
-///         content: "Hello GPT!".to_owned(),
-///     }
+///     openai_rust::chat::Message::user("Hello GPT!")
 /// ]);
 /// ```
 ///
 /// To use streaming, use [crate::Client::create_chat_stream].
 ///
-#[derive(Serialize, Debug, Clone)]
+/// The optional parameters can be set in one expression:
+///
+/// ```
+/// # let messages = vec![];
+/// let args = openai_rust::chat::ChatArguments::new("gpt-4o", messages)
+///     .temperature(0.7)
+///     .max_tokens(500)
+///     .n(2)
+///     .user("user-1234");
+/// assert_eq!(args.max_tokens, Some(500));
+/// ```
+///
+/// Parameters not yet supported by this crate can be sent with [extra](ChatArguments::extra):
+///
+/// ```
+/// # let args = openai_rust::chat::ChatArguments::new("gpt-4o", vec![]);
+/// let args = args.extra("prediction", serde_json::json!({ "type": "content", "content": "fn main() {}" }));
+/// let body = serde_json::to_value(&args).unwrap();
+/// assert_eq!(body["prediction"]["type"], "content");
+/// ```
+///
+/// Arguments can be loaded from a preset in a config file, or replayed from a logged request body.
+/// Unknown parameters end up in [extra](ChatArguments::extra).
+///
+/// ```
+/// let preset = r#"{
+///     "model": "gpt-4o",
+///     "messages": [{"role": "system", "content": "Answer in French."}],
+///     "temperature": 0.2,
+///     "seed": 42
+/// }"#;
+/// let args: openai_rust::chat::ChatArguments = serde_json::from_str(preset).unwrap();
+/// assert_eq!(args.temperature, Some(0.2));
+/// assert_eq!(args.extra.unwrap()["seed"], 42);
+///
+/// let args = openai_rust::chat::ChatArguments::new("gpt-4o", vec![]);
+/// let json = serde_json::to_string(&args).unwrap();
+/// assert_eq!(serde_json::from_str::<openai_rust::chat::ChatArguments>(&json).unwrap(), args);
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[non_exhaustive]
 pub struct ChatArguments {
     /// ID of the model to use. See the model [endpoint compatibility table](https://platform.openai.com/docs/models/model-endpoint-compatibility) for details on which models work with the Chat API.
     pub model: String,
@@ -42,12 +86,16 @@ pub struct ChatArguments {
     pub n: Option<u32>,
 
     /// Whether to stream back partial progress.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "Option::is_none", skip_deserializing)]
     pub(crate) stream: Option<bool>,
 
+    /// Options for streaming. Only allowed with [crate::Client::create_chat_stream].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stream_options: Option<StreamOptions>,
+
     /// Up to 4 sequences where the API will stop generating further tokens.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub stop: Option<String>,
+    pub stop: Option<StopSequences>,
 
     /// The maximum number of [tokens](https://platform.openai.com/tokenizer) to generate in the chat completion.
     ///
@@ -55,6 +103,11 @@ pub struct ChatArguments {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub max_tokens: Option<u32>,
 
+    /// The maximum number of tokens to generate, including reasoning tokens.
+    /// Replaces `max_tokens`, which is not supported by reasoning models like o1.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_completion_tokens: Option<u32>,
+
     /// Number between -2.0 and 2.0. Positive values penalize new tokens based on whether they appear in the text so far,
     /// increasing the model's likelihood to talk about new topics.
     ///
@@ -67,11 +120,70 @@ pub struct ChatArguments {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub frequency_penalty: Option<f32>,
 
-    // logit_bias
+    /// The format of the output, like JSON following a schema. Defaults to [ResponseFormat::Text].
+    ///
+    /// See [Client::create_structured_chat](crate::Client::create_structured_chat) to parse the output into a type.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response_format: Option<ResponseFormat>,
+
+    /// The tools the model may call. The calls are in the [tool_calls](Message::tool_calls) of the reply.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tools: Option<Vec<Tool>>,
+
+    /// Whether and which tool the model calls. Defaults to [ToolChoice::Auto] when there are tools.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_choice: Option<ToolChoice>,
+
+    /// Whether the model may call several tools at once. Defaults to `true`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parallel_tool_calls: Option<bool>,
+
+    /// The functions the model may call, the deprecated predecessor of [tools](ChatArguments::tools).
+    /// The call is in the [function_call](Message::function_call) of the reply.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub functions: Option<Vec<FunctionDefinition>>,
+
+    /// Whether to return the log probabilities of the output tokens, in the [logprobs](Choice::logprobs) of each choice.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub logprobs: Option<bool>,
+
+    /// The number of most likely alternatives to return for each token, between 0 and 20. Requires [logprobs](ChatArguments::logprobs).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_logprobs: Option<u8>,
+
+    /// Make tokens more or less likely to appear in the output, see [logit_bias](crate::logit_bias).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub logit_bias: Option<LogitBias>,
+
     /// A unique identifier representing your end-user, which can help OpenAI to monitor and detect abuse.
     /// [Learn more](https://platform.openai.com/docs/guides/safety-best-practices/end-user-ids).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub user: Option<String>,
+
+    /// Whether to store the completion, for use with [Client::list_chat_completions](crate::Client::list_chat_completions)
+    /// and model distillation or evals. Defaults to `false`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub store: Option<bool>,
+
+    /// Up to 16 key-value pairs to filter stored completions on.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<HashMap<String, String>>,
+
+    /// OpenRouter only: preferences for the providers the request is routed to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provider: Option<crate::openrouter::ProviderPreferences>,
+
+    /// OpenRouter only: set to `"fallback"` to try the `models` in order when a model is unavailable.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub route: Option<String>,
+
+    /// OpenRouter only: the models to fall back on when using `route`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub models: Option<Vec<String>>,
+
+    /// Additional parameters sent in the body, for parameters not yet supported by this crate or specific to a provider.
+    #[serde(flatten, skip_serializing_if = "Option::is_none", deserialize_with = "crate::de::empty_none")]
+    pub extra: Option<serde_json::Map<String, serde_json::Value>>,
 }
 
 impl ChatArguments {
@@ -83,13 +195,397 @@ impl ChatArguments {
             top_p: None,
             n: None,
             stream: None,
+            stream_options: None,
             stop: None,
             max_tokens: None,
+            max_completion_tokens: None,
             presence_penalty: None,
             frequency_penalty: None,
+            response_format: None,
+            tools: None,
+            tool_choice: None,
+            parallel_tool_calls: None,
+            functions: None,
+            logprobs: None,
+            top_logprobs: None,
+            logit_bias: None,
             user: None,
+            store: None,
+            metadata: None,
+            provider: None,
+            route: None,
+            models: None,
+            extra: None,
         }
     }
+
+    pub fn temperature(mut self, temperature: f32) -> ChatArguments {
+        self.temperature = Some(temperature);
+        self
+    }
+
+    pub fn top_p(mut self, top_p: f32) -> ChatArguments {
+        self.top_p = Some(top_p);
+        self
+    }
+
+    pub fn n(mut self, n: u32) -> ChatArguments {
+        self.n = Some(n);
+        self
+    }
+
+    pub fn stream_options(mut self, stream_options: StreamOptions) -> ChatArguments {
+        self.stream_options = Some(stream_options);
+        self
+    }
+
+    /// A single stop sequence like `"\n"`, or up to 4 like `["\n\n", "User:"]`.
+    pub fn stop(mut self, stop: impl Into<StopSequences>) -> ChatArguments {
+        self.stop = Some(stop.into());
+        self
+    }
+
+    pub fn max_tokens(mut self, max_tokens: u32) -> ChatArguments {
+        self.max_tokens = Some(max_tokens);
+        self
+    }
+
+    pub fn max_completion_tokens(mut self, max_completion_tokens: u32) -> ChatArguments {
+        self.max_completion_tokens = Some(max_completion_tokens);
+        self
+    }
+
+    pub fn presence_penalty(mut self, presence_penalty: f32) -> ChatArguments {
+        self.presence_penalty = Some(presence_penalty);
+        self
+    }
+
+    pub fn frequency_penalty(mut self, frequency_penalty: f32) -> ChatArguments {
+        self.frequency_penalty = Some(frequency_penalty);
+        self
+    }
+
+    pub fn response_format(mut self, response_format: ResponseFormat) -> ChatArguments {
+        self.response_format = Some(response_format);
+        self
+    }
+
+    pub fn tools(mut self, tools: Vec<Tool>) -> ChatArguments {
+        self.tools = Some(tools);
+        self
+    }
+
+    pub fn tool_choice(mut self, tool_choice: ToolChoice) -> ChatArguments {
+        self.tool_choice = Some(tool_choice);
+        self
+    }
+
+    pub fn parallel_tool_calls(mut self, parallel_tool_calls: bool) -> ChatArguments {
+        self.parallel_tool_calls = Some(parallel_tool_calls);
+        self
+    }
+
+    /// Return the log probabilities of the output tokens, with the given number of alternatives for each token.
+    pub fn logprobs(mut self, top_logprobs: u8) -> ChatArguments {
+        self.logprobs = Some(true);
+        self.top_logprobs = (top_logprobs > 0).then_some(top_logprobs);
+        self
+    }
+
+    pub fn logit_bias(mut self, logit_bias: LogitBias) -> ChatArguments {
+        self.logit_bias = Some(logit_bias);
+        self
+    }
+
+    pub fn user(mut self, user: impl AsRef<str>) -> ChatArguments {
+        self.user = Some(user.as_ref().to_owned());
+        self
+    }
+
+    pub fn store(mut self, store: bool) -> ChatArguments {
+        self.store = Some(store);
+        self
+    }
+
+    /// Add a key-value pair to the [metadata](ChatArguments::metadata) of a stored completion.
+    pub fn metadata(mut self, key: impl AsRef<str>, value: impl AsRef<str>) -> ChatArguments {
+        self.metadata
+            .get_or_insert_with(Default::default)
+            .insert(key.as_ref().to_owned(), value.as_ref().to_owned());
+        self
+    }
+
+    /// Add a parameter not yet supported by this crate to [extra](ChatArguments::extra).
+    pub fn extra(mut self, key: impl AsRef<str>, value: impl Into<serde_json::Value>) -> ChatArguments {
+        self.extra.get_or_insert_with(Default::default).insert(key.as_ref().to_owned(), value.into());
+        self
+    }
+}
+
+/// The format of the output of a chat completion.
+///
+/// ```
+/// use openai_rust::chat::{JsonSchema, ResponseFormat};
+/// let format = ResponseFormat::JsonSchema {
+///     json_schema: JsonSchema::new("weather", serde_json::json!({
+///         "type": "object",
+///         "properties": { "temperature": { "type": "number" } },
+///         "required": ["temperature"],
+///         "additionalProperties": false
+///     }))
+///     .strict(true),
+/// };
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ResponseFormat {
+    /// Plain text.
+    Text,
+    /// Any valid JSON. The messages have to ask for JSON, or the model may generate whitespace until it runs out of tokens.
+    JsonObject,
+    /// JSON following a schema, also known as [Structured Outputs](https://platform.openai.com/docs/guides/structured-outputs).
+    JsonSchema { json_schema: JsonSchema },
+}
+
+/// The schema of [ResponseFormat::JsonSchema].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct JsonSchema {
+    /// The name of the format, containing only a-z, A-Z, 0-9, underscores and dashes.
+    pub name: String,
+    /// What the format is for, used by the model to decide how to respond.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub schema: serde_json::Value,
+    /// Whether the output always follows the schema exactly.
+    /// This requires the schema to meet the constraints checked by [validate_strict_schema](crate::tools::validate_strict_schema).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub strict: Option<bool>,
+}
+
+impl JsonSchema {
+    pub fn new(name: impl AsRef<str>, schema: serde_json::Value) -> JsonSchema {
+        JsonSchema {
+            name: name.as_ref().to_owned(),
+            description: None,
+            schema,
+            strict: None,
+        }
+    }
+
+    pub fn description(mut self, description: impl AsRef<str>) -> JsonSchema {
+        self.description = Some(description.as_ref().to_owned());
+        self
+    }
+
+    pub fn strict(mut self, strict: bool) -> JsonSchema {
+        self.strict = Some(strict);
+        self
+    }
+}
+
+/// A tool the model can call, for the [tools](ChatArguments::tools) of a request.
+///
+/// ```
+/// # tokio_test::block_on(async {
+/// # let client = openai_rust::Client::new("");
+/// use openai_rust::chat::{ChatArguments, Message, Tool, ToolChoice};
+/// use openai_rust::tools::FunctionDefinition;
+/// let mut args = ChatArguments::new("gpt-4o", vec![Message::user("What's the weather like in Paris?")]);
+/// args.tools = Some(vec![Tool::function(FunctionDefinition::new(
+///     "get_weather",
+///     "Get the current weather in a location",
+///     serde_json::json!({
+///         "type": "object",
+///         "properties": { "location": { "type": "string" } },
+///         "required": ["location"]
+///     }),
+/// ))]);
+/// args.tool_choice = Some(ToolChoice::Function("get_weather".to_owned()));
+/// # let json = r#"{"choices": [{"index": 0, "finish_reason": "tool_calls", "message": {"role": "assistant", "content": null,
+/// #     "tool_calls": [{"id": "call_abc123", "type": "function", "function": {"name": "get_weather", "arguments": "{\"location\": \"Paris\"}"}}]}}]}"#;
+/// # let res = serde_json::from_str::<openai_rust::chat::ChatCompletion>(json).unwrap();
+/// # /*
+/// let res = client.create_chat(args.clone()).await.unwrap();
+/// # */
+/// let reply = res.choices[0].message.clone();
+/// args.messages.push(reply.clone());
+/// for call in &reply.tool_calls {
+///     assert_eq!(call.function.name, "get_weather");
+///     args.messages.push(Message::tool_result(&call.id, r#"{"temperature": 18}"#));
+/// }
+/// # })
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Tool {
+    Function { function: FunctionDefinition },
+}
+
+impl Tool {
+    /// A function tool.
+    pub fn function(function: FunctionDefinition) -> Tool {
+        Tool::Function { function }
+    }
+}
+
+/// Whether and which tool the model calls, see [tool_choice](ChatArguments::tool_choice).
+///
+/// ```
+/// use openai_rust::chat::ToolChoice;
+/// let choice = serde_json::to_value(ToolChoice::Function("get_weather".to_owned())).unwrap();
+/// assert_eq!(choice, serde_json::json!({"type": "function", "function": {"name": "get_weather"}}));
+/// assert_eq!(serde_json::from_value::<ToolChoice>(choice).unwrap(), ToolChoice::Function("get_weather".to_owned()));
+/// assert_eq!(serde_json::to_value(ToolChoice::Required).unwrap(), "required");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ToolChoice {
+    /// Reply with a message instead of calling a tool.
+    None,
+    /// Reply with a message or call tools.
+    Auto,
+    /// Call one or more tools.
+    Required,
+    /// Call the function with this name.
+    Function(String),
+}
+
+impl Serialize for ToolChoice {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            ToolChoice::None => serializer.serialize_str("none"),
+            ToolChoice::Auto => serializer.serialize_str("auto"),
+            ToolChoice::Required => serializer.serialize_str("required"),
+            ToolChoice::Function(name) => {
+                serde_json::json!({ "type": "function", "function": { "name": name } }).serialize(serializer)
+            }
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for ToolChoice {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<ToolChoice, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            Mode(String),
+            Function { function: FunctionName },
+        }
+        #[derive(Deserialize)]
+        struct FunctionName {
+            name: String,
+        }
+        match Repr::deserialize(deserializer)? {
+            Repr::Mode(mode) => match mode.as_str() {
+                "none" => Ok(ToolChoice::None),
+                "auto" => Ok(ToolChoice::Auto),
+                "required" => Ok(ToolChoice::Required),
+                mode => Err(serde::de::Error::unknown_variant(mode, &["none", "auto", "required"])),
+            },
+            Repr::Function { function } => Ok(ToolChoice::Function(function.name)),
+        }
+    }
+}
+
+/// A call of a [Tool] by the model, in the [tool_calls](Message::tool_calls) of a reply.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ToolCall {
+    /// The ID of the call, to send the result with [Message::tool_result].
+    pub id: String,
+    /// The type of the tool, `function`.
+    #[serde(rename = "type", default = "ToolCall::function_kind")]
+    pub kind: String,
+    pub function: FunctionCall,
+}
+
+impl ToolCall {
+    fn function_kind() -> String {
+        "function".to_owned()
+    }
+
+    /// Parse the arguments of the function.
+    pub fn arguments<T: serde::de::DeserializeOwned>(&self) -> serde_json::Result<T> {
+        serde_json::from_str(&self.function.arguments)
+    }
+}
+
+/// The function called by a [ToolCall].
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct FunctionCall {
+    pub name: String,
+    /// The arguments as a JSON string. The model may generate invalid JSON,
+    /// or arguments that do not match the schema unless the function is [strict](FunctionDefinition::strict).
+    #[serde(default, deserialize_with = "crate::de::null_default")]
+    pub arguments: String,
+}
+
+/// Filters for [Client::list_chat_completions](crate::Client::list_chat_completions).
+///
+/// ```
+/// let query = openai_rust::chat::ListChatCompletions::new()
+///     .model("gpt-4o")
+///     .metadata("user_id", "user_123");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ListChatCompletions {
+    pub model: Option<String>,
+    /// Only completions with all of these metadata pairs.
+    pub metadata: HashMap<String, String>,
+}
+
+impl ListChatCompletions {
+    pub fn new() -> ListChatCompletions {
+        ListChatCompletions::default()
+    }
+
+    pub fn model(mut self, model: impl AsRef<str>) -> ListChatCompletions {
+        self.model = Some(model.as_ref().to_owned());
+        self
+    }
+
+    pub fn metadata(mut self, key: impl AsRef<str>, value: impl AsRef<str>) -> ListChatCompletions {
+        self.metadata.insert(key.as_ref().to_owned(), value.as_ref().to_owned());
+        self
+    }
+
+    /// The query parameters of the list endpoint.
+    pub(crate) fn query(&self) -> Vec<(String, String)> {
+        let mut query = vec![];
+        if let Some(model) = &self.model {
+            query.push(("model".to_owned(), model.clone()));
+        }
+        for (key, value) in &self.metadata {
+            query.push((format!("metadata[{}]", key), value.clone()));
+        }
+        query
+    }
+}
+
+/// A message of a stored chat completion, returned by [Client::list_chat_completion_messages](crate::Client::list_chat_completion_messages).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct StoredMessage {
+    pub id: String,
+    pub role: Role,
+    #[serde(default)]
+    pub content: Option<String>,
+    #[serde(default)]
+    pub name: Option<String>,
+}
+
+/// The response of [Client::delete_chat_completion](crate::Client::delete_chat_completion).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct DeletedChatCompletion {
+    pub id: String,
+    pub deleted: bool,
+}
+
+/// Options for streaming chat completions.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct StreamOptions {
+    /// Send the usage of the request in a last chunk without choices,
+    /// which is also available from [stream::ChatCompletionChunkStream::end].
+    pub include_usage: bool,
 }
 
 /// This is the response of a chat.
@@ -120,30 +616,252 @@ impl ChatArguments {
 /// // or
 /// let msg = res.to_string();
 /// ```
-#[derive(Deserialize, Debug, Clone)]
+///
+/// Responses without choices, like those stopped by a content filter, display as an empty string.
+/// Use [ChatCompletion::first_content] to tell them apart.
+///
+/// It can be serialized again, to cache or store it.
+/// ```
+/// # let json = r#"{"id":"chatcmpl-123","created":1677652288,"choices":[{"index":0,"message":{"role":"assistant","content":"Hi"},"finish_reason":"stop"}]}"#;
+/// # let res = serde_json::from_str::<openai_rust::chat::ChatCompletion>(json).unwrap();
+/// let cached = serde_json::to_string(&res).unwrap();
+/// let res = serde_json::from_str::<openai_rust::chat::ChatCompletion>(&cached).unwrap();
+/// assert_eq!(res.to_string(), "Hi");
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[non_exhaustive]
 pub struct ChatCompletion {
+    #[serde(default, deserialize_with = "crate::de::null_default")]
     pub id: String,
-    pub created: u32,
+    #[serde(default, deserialize_with = "crate::de::null_default")]
+    pub created: u64,
     pub choices: Vec<Choice>,
-    pub usage: Usage,
+    pub usage: Option<Usage>,
+    /// The metadata of a stored completion.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<HashMap<String, String>>,
+}
+
+impl ChatCompletion {
+    /// When this was created, see [created](Self::created).
+    #[cfg(feature = "chrono")]
+    pub fn created_at(&self) -> chrono::DateTime<chrono::Utc> {
+        crate::timestamp(self.created)
+    }
+
+    /// The first choice, if there is one.
+    pub fn first_choice(&self) -> Option<&Choice> {
+        self.choices.first()
+    }
+
+    /// The content of the first choice, if there is one.
+    pub fn first_content(&self) -> Option<&str> {
+        self.first_choice().and_then(|c| c.message.content.as_text())
+    }
+
+    /// Whether the first choice was cut off by the maximum amount of tokens.
+    pub fn is_truncated(&self) -> bool {
+        self.first_choice().is_some_and(|c| c.finish_reason == "length")
+    }
+
+    /// Append the first choice of a continuation to the first choice, and add its usage.
+    pub(crate) fn append(&mut self, continuation: ChatCompletion) {
+        if let (Some(choice), Some(next)) = (self.choices.first_mut(), continuation.choices.into_iter().next()) {
+            choice.message.content = format!("{}{}", choice.message.content, next.message.content).into();
+            choice.finish_reason = next.finish_reason;
+        }
+        match (&mut self.usage, continuation.usage) {
+            (Some(usage), Some(next)) => {
+                usage.prompt_tokens += next.prompt_tokens;
+                usage.completion_tokens += next.completion_tokens;
+                usage.total_tokens += next.total_tokens;
+            }
+            (None, Some(next)) => self.usage = Some(next),
+            (_, None) => {}
+        }
+    }
 }
 
+/// The message asking the model to continue a reply that was cut off, see [Client::with_auto_continue](crate::Client::with_auto_continue).
+pub const CONTINUE_PROMPT: &str = "Continue exactly where you stopped, without repeating anything.";
+
 impl std::fmt::Display for ChatCompletion {
+    /// Writes the content of the first choice, or nothing if there are no choices.
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", &self.choices[0].message.content)?;
+        write!(f, "{}", self.first_content().unwrap_or_default())?;
         Ok(())
     }
 }
 
+/// How often [parallel_map] retries a request after a rate limit, a server error or a connection error.
+pub const PARALLEL_MAP_RETRIES: u32 = 3;
+
+/// Send many chat requests, at most `concurrency` at a time, and return the results in the same order.
+///
+/// A request that hits a rate limit or a server error is retried up to [PARALLEL_MAP_RETRIES] times,
+/// waiting as long as the API asks, or 1, 2 and 4 seconds. A failed request does not stop the others.
+/// A client with a [RetryPolicy](crate::retry::RetryPolicy) retries by that policy instead.
+/// Configure a [RateLimiter](crate::rate_limit::RateLimiter) on the client to stay under the limits of your account.
+///
+/// ```no_run
+/// # tokio_test::block_on(async {
+/// # let api_key = "";
+/// use openai_rust::chat::{parallel_map, ChatArguments, Message};
+/// let client = openai_rust::Client::new(api_key);
+/// let reviews = ["Great product!", "Broke after a week."];
+/// let args = reviews
+///     .iter()
+///     .map(|review| {
+///         let prompt = format!("Is this review positive or negative?\n\n{}", review);
+///         ChatArguments::new("gpt-4o-mini", vec![Message::user(prompt)])
+///     })
+///     .collect();
+/// for (review, result) in reviews.iter().zip(parallel_map(&client, args, 8).await) {
+///     match result {
+///         Ok(completion) => println!("{}: {}", review, completion),
+///         Err(e) => println!("{}: failed with {}", review, e),
+///     }
+/// }
+/// # })
+/// ```
+pub async fn parallel_map(
+    client: &crate::Client,
+    args: Vec<ChatArguments>,
+    concurrency: usize,
+) -> Vec<crate::error::Result<ChatCompletion>> {
+    use futures_util::StreamExt;
+    futures_util::stream::iter(args)
+        .map(|args| create_with_retries(client, args))
+        .buffered(concurrency.max(1))
+        .collect()
+        .await
+}
+
+/// How [bulk_map] sends its requests.
+#[cfg(feature = "batches")]
+pub struct BulkOptions {
+    concurrency: usize,
+    batch_threshold: Option<usize>,
+    wait: crate::batches::WaitOptions,
+}
+
+#[cfg(feature = "batches")]
+impl BulkOptions {
+    /// Send at most `concurrency` requests at a time, without using the Batch API.
+    pub fn new(concurrency: usize) -> BulkOptions {
+        BulkOptions {
+            concurrency,
+            batch_threshold: None,
+            wait: crate::batches::WaitOptions::new(),
+        }
+    }
+
+    /// Send jobs of at least this many requests as a batch.
+    pub fn batch_threshold(mut self, batch_threshold: usize) -> BulkOptions {
+        self.batch_threshold = Some(batch_threshold);
+        self
+    }
+
+    /// How to wait for the batch to finish.
+    pub fn wait(mut self, wait: crate::batches::WaitOptions) -> BulkOptions {
+        self.wait = wait;
+        self
+    }
+}
+
+/// Like [parallel_map], but large jobs are sent through the [Batch API](crate::batches),
+/// which costs half as much but can take up to 24 hours.
+///
+/// Jobs smaller than the [batch threshold](BulkOptions::batch_threshold) are sent with [parallel_map].
+/// Otherwise the requests are uploaded as a file and the results are read once the batch has finished,
+/// in the same order as the requests. Requests that did not finish before the batch expired are errors.
+///
+/// ```no_run
+/// # tokio_test::block_on(async {
+/// # let api_key = "";
+/// # let args = vec![];
+/// use openai_rust::chat::{bulk_map, BulkOptions};
+/// let client = openai_rust::Client::new(api_key);
+/// let options = BulkOptions::new(8).batch_threshold(1000);
+/// for result in bulk_map(&client, args, options).await {
+///     println!("{}", result.unwrap());
+/// }
+/// # })
+/// ```
+#[cfg(feature = "batches")]
+pub async fn bulk_map(
+    client: &crate::Client,
+    args: Vec<ChatArguments>,
+    options: BulkOptions,
+) -> Vec<crate::error::Result<ChatCompletion>> {
+    match options.batch_threshold {
+        Some(threshold) if !args.is_empty() && args.len() >= threshold => {
+            let count = args.len();
+            match run_batch(client, &args, options.wait).await {
+                Ok(mut results) => (0..count)
+                    .map(|i| {
+                        results
+                            .remove(&format!("request-{}", i))
+                            .unwrap_or_else(|| Err(anyhow::anyhow!("request {} did not finish before the batch ended", i).into()))
+                    })
+                    .collect(),
+                Err(e) => (0..count).map(|_| Err(anyhow::anyhow!("{:#}", e).into())).collect(),
+            }
+        }
+        _ => parallel_map(client, args, options.concurrency).await,
+    }
+}
+
+/// Send the requests as a batch and wait for the results, by custom ID.
+#[cfg(feature = "batches")]
+async fn run_batch(
+    client: &crate::Client,
+    args: &[ChatArguments],
+    wait: crate::batches::WaitOptions,
+) -> anyhow::Result<HashMap<String, crate::error::Result<ChatCompletion>>> {
+    use crate::batches::{input_jsonl, BatchArguments};
+    const ENDPOINT: &str = "/v1/chat/completions";
+    let ids: Vec<String> = (0..args.len()).map(|i| format!("request-{}", i)).collect();
+    let jsonl = input_jsonl(ENDPOINT, ids.iter().map(String::as_str).zip(args))?;
+    let file = client
+        .upload_file(crate::files::Upload::bytes(jsonl, "chat_completions.jsonl"), "batch")
+        .await?;
+    let batch = client.create_batch(BatchArguments::new(&file.id, ENDPOINT)).await?;
+    let outputs = client.wait_for_batch::<ChatCompletion>(&batch.id, wait).await?;
+    Ok(outputs
+        .into_iter()
+        .map(|output| (output.custom_id, output.result.map_err(|e| anyhow::Error::from(e).into())))
+        .collect())
+}
+
+async fn create_with_retries(client: &crate::Client, args: ChatArguments) -> crate::error::Result<ChatCompletion> {
+    if client.retry.is_some() {
+        return client.create_chat(args).await;
+    }
+    let mut delay = std::time::Duration::from_secs(1);
+    let mut retries = 0;
+    loop {
+        match client.create_chat(args.clone()).await {
+            Err(e) if retries < PARALLEL_MAP_RETRIES && e.is_retryable() => {
+                retries += 1;
+                tokio::time::sleep(e.retry_after().unwrap_or(delay)).await;
+                delay *= 2;
+            }
+            res => return res,
+        }
+    }
+}
+
 /// Structs and deserialization method for the responses
 /// when using streaming chat responses.
 pub mod stream {
     use bytes::Bytes;
     use futures_util::Stream;
-    use serde::Deserialize;
+    use serde::{Deserialize, Serialize};
     use std::pin::Pin;
     use std::task::Poll;
-    use std::str;
+
+    use crate::sse::EventStream;
 
     /// This is the partial chat result received when streaming.
     ///
@@ -170,140 +888,337 @@ pub mod stream {
     /// // or
     /// let msg = res.to_string();
     /// ```
-    #[derive(Deserialize, Debug, Clone)]
+    #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+    #[non_exhaustive]
     pub struct ChatCompletionChunk {
+        #[serde(default, deserialize_with = "crate::de::null_default")]
         pub id: String,
-        pub created: u32,
+        #[serde(default, deserialize_with = "crate::de::null_default")]
+        pub created: u64,
+        #[serde(default, deserialize_with = "crate::de::null_default")]
         pub model: String,
         pub choices: Vec<Choice>,
         pub system_fingerprint: Option<String>,
+        /// Only on the last chunk, when requested with [include_usage](super::StreamOptions::include_usage).
+        #[serde(default)]
+        pub usage: Option<super::Usage>,
+    }
+
+    impl ChatCompletionChunk {
+        /// When this was created, see [created](Self::created).
+        #[cfg(feature = "chrono")]
+        pub fn created_at(&self) -> chrono::DateTime<chrono::Utc> {
+            crate::timestamp(self.created)
+        }
+
+        /// The first choice, if there is one.
+        /// Chunks with only usage have no choices.
+        ///
+        /// ```
+        /// # use openai_rust::chat::stream::ChatCompletionChunk;
+        /// let json = r#"{"id":"chatcmpl-123","created":1694268190,"model":"gpt-4o","choices":[],"usage":{"prompt_tokens":9,"completion_tokens":12,"total_tokens":21}}"#;
+        /// let chunk = serde_json::from_str::<ChatCompletionChunk>(json).unwrap();
+        /// assert!(chunk.first_choice().is_none());
+        /// assert_eq!(chunk.to_string(), "");
+        /// ```
+        pub fn first_choice(&self) -> Option<&Choice> {
+            self.choices.first()
+        }
+
+        /// The content of the first choice, if there is any.
+        pub fn first_content(&self) -> Option<&str> {
+            self.first_choice()?.delta.content.as_deref()
+        }
     }
 
     impl std::fmt::Display for ChatCompletionChunk {
+        /// Writes the content of the first choice, or nothing if there is none.
         fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-            write!(
-                f,
-                "{}",
-                self.choices[0].delta.content.as_ref().unwrap_or(&"".into())
-            )?;
+            write!(f, "{}", self.first_content().unwrap_or_default())?;
             Ok(())
         }
     }
 
     /// Choices for [super::ChatCompletion].
-    #[derive(Deserialize, Debug, Clone)]
+    #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+    #[non_exhaustive]
     pub struct Choice {
         pub delta: ChoiceDelta,
+        #[serde(default, deserialize_with = "crate::de::null_default")]
         pub index: u32,
+        #[serde(default)]
         pub finish_reason: Option<String>,
+        /// The log probabilities of the tokens of the delta, when requested with [logprobs](super::ChatArguments::logprobs).
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub logprobs: Option<super::Logprobs>,
     }
 
     /// Additional data from [Choice].
-    #[derive(Deserialize, Debug, Clone)]
+    #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+    #[non_exhaustive]
     pub struct ChoiceDelta {
         pub content: Option<String>,
+        /// Parts of the tool calls of the assistant, combined by [ToolCallAccumulator].
+        #[serde(default, skip_serializing_if = "Vec::is_empty", deserialize_with = "crate::de::null_default")]
+        pub tool_calls: Vec<ToolCallDelta>,
+    }
+
+    /// A part of a [ToolCall](super::ToolCall). The first part of a call has its ID and the name of the function,
+    /// the following parts have pieces of its arguments.
+    #[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, Hash)]
+    #[non_exhaustive]
+    pub struct ToolCallDelta {
+        /// The position of the call in the tool calls of the message.
+        pub index: usize,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub id: Option<String>,
+        #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+        pub kind: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub function: Option<FunctionCallDelta>,
+    }
+
+    /// A part of a [FunctionCall](super::FunctionCall).
+    #[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, Hash)]
+    #[non_exhaustive]
+    pub struct FunctionCallDelta {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub name: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub arguments: Option<String>,
+    }
+
+    /// Combines the [ToolCallDelta]s of the first choice of streamed chunks into complete [ToolCall](super::ToolCall)s.
+    ///
+    /// [ChatCompletionChunkStream] does this itself, see [StreamEnd::tool_calls].
+    ///
+    /// ```
+    /// use openai_rust::chat::stream::{ChatCompletionChunk, ToolCallAccumulator};
+    /// let chunks = [
+    ///     r#"{"choices":[{"index":0,"delta":{"tool_calls":[{"index":0,"id":"call_abc","type":"function","function":{"name":"get_weather","arguments":""}}]}}]}"#,
+    ///     r#"{"choices":[{"index":0,"delta":{"tool_calls":[{"index":0,"function":{"arguments":"{\"location\""}}]}}]}"#,
+    ///     r#"{"choices":[{"index":0,"delta":{"tool_calls":[{"index":0,"function":{"arguments":": \"Paris\"}"}}]}}]}"#,
+    /// ];
+    /// let mut accumulator = ToolCallAccumulator::new();
+    /// for chunk in chunks {
+    ///     accumulator.push(&serde_json::from_str::<ChatCompletionChunk>(chunk).unwrap());
+    /// }
+    /// let calls = accumulator.finish();
+    /// assert_eq!(calls[0].id, "call_abc");
+    /// assert_eq!(calls[0].function.arguments, r#"{"location": "Paris"}"#);
+    /// ```
+    #[derive(Debug, Clone, Default, PartialEq)]
+    pub struct ToolCallAccumulator {
+        calls: Vec<super::ToolCall>,
+    }
+
+    impl ToolCallAccumulator {
+        pub fn new() -> ToolCallAccumulator {
+            ToolCallAccumulator::default()
+        }
+
+        /// Add the tool call parts of the first choice of a chunk.
+        pub fn push(&mut self, chunk: &ChatCompletionChunk) {
+            let Some(choice) = chunk.choices.iter().find(|choice| choice.index == 0) else {
+                return;
+            };
+            for delta in &choice.delta.tool_calls {
+                while self.calls.len() <= delta.index {
+                    self.calls.push(super::ToolCall {
+                        id: String::new(),
+                        kind: "function".to_owned(),
+                        function: Default::default(),
+                    });
+                }
+                let call = &mut self.calls[delta.index];
+                if let Some(id) = &delta.id {
+                    call.id.clone_from(id);
+                }
+                if let Some(kind) = &delta.kind {
+                    call.kind.clone_from(kind);
+                }
+                if let Some(function) = &delta.function {
+                    if let Some(name) = &function.name {
+                        call.function.name += name;
+                    }
+                    if let Some(arguments) = &function.arguments {
+                        call.function.arguments += arguments;
+                    }
+                }
+            }
+        }
+
+        /// The tool calls received so far.
+        pub fn finish(self) -> Vec<super::ToolCall> {
+            self.calls
+        }
+    }
+
+    /// How a stream ended, returned by [ChatCompletionChunkStream::end].
+    #[derive(Debug, Clone, Default, PartialEq)]
+    #[non_exhaustive]
+    pub struct StreamEnd {
+        /// The last finish reason of the streamed choices, like `stop` or `length`.
+        pub finish_reason: Option<String>,
+        /// The usage of the request, when requested with [include_usage](super::StreamOptions::include_usage).
+        pub usage: Option<super::Usage>,
+        /// The tool calls of the first choice, combined from their parts.
+        pub tool_calls: Vec<super::ToolCall>,
     }
 
+    type UsageCallback = Box<dyn FnOnce(&super::Usage) + Send>;
+
+    /// A stream of [ChatCompletionChunk]s, returned by [Client::create_chat_stream](crate::Client::create_chat_stream).
+    ///
+    /// The stream ends after the `[DONE]` marker of the API.
+    /// When the connection is closed before that, [ChatCompletionChunkStream::end] returns `None`.
     pub struct ChatCompletionChunkStream {
-        byte_stream: Pin<Box<dyn Stream<Item = reqwest::Result<Bytes>>>>,
-        // internal buffer of incomplete completionchunks
-        buf: String,
+        events: EventStream,
+        // set once the [DONE] marker was received
+        end: Option<StreamEnd>,
+        finish_reason: Option<String>,
+        usage: Option<super::Usage>,
+        tool_calls: ToolCallAccumulator,
+        // called with the usage once it is received
+        on_usage: Option<UsageCallback>,
     }
 
     impl ChatCompletionChunkStream {
 
-        pub(crate) fn new(stream: Pin<Box<dyn Stream<Item = reqwest::Result<Bytes>>>>) -> Self {
+        pub(crate) fn new(stream: Pin<Box<dyn Stream<Item = reqwest::Result<Bytes>> + Send>>) -> Self {
             Self {
-                byte_stream: stream,
-                buf: String::new(),
+                events: EventStream::new(stream),
+                end: None,
+                finish_reason: None,
+                usage: None,
+                tool_calls: ToolCallAccumulator::new(),
+                on_usage: None,
             }
         }
 
-        /// If possible, returns a the first deserialized chunk
-        /// from the buffer.
-        fn deserialize_buf(self: Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Option<anyhow::Result<ChatCompletionChunk>> {
-            // let's take the first chunk
-            let bufclone = self.buf.clone();
-            let mut chunks = bufclone.split("\n\n").peekable();
-            let first = chunks.next();
-            let second = chunks.peek();
-
-            match first {
-                Some(first) => {
-                    match first.strip_prefix("data: ") {
-                        Some(chunk) => {
-                            if !chunk.ends_with("}") {
-                                // This guard happens on partial chunks or the
-                                // [DONE] marker
-                                None
-                            } else {
-                                // If there's a second chunk, wake
-                                if let Some(second) = second {
-                                    if second.ends_with("}") {
-                                        cx.waker().wake_by_ref();
-                                    }
-                                }
-
-                                // Save the remainder
-                                self.get_mut().buf = chunks.collect::<Vec<_>>().join("\n\n");
-                                //self.get_mut().buf = chunks.remainder().unwrap_or("").to_owned();
-
-                                Some(
-                                    serde_json::from_str::<ChatCompletionChunk>(&chunk)
-                                    .map_err(|e| anyhow::anyhow!(e))
-                                )
-                            }
-                        },
-                        None => None,
+        /// Call `on_usage` when the usage of the request is received, to count it towards the budget.
+        pub(crate) fn on_usage(mut self, on_usage: impl FnOnce(&super::Usage) + Send + 'static) -> Self {
+            self.on_usage = Some(Box::new(on_usage));
+            self
+        }
+
+        /// Whether the API signaled the end of the stream.
+        pub fn is_done(&self) -> bool {
+            self.end.is_some()
+        }
+
+        /// Why generation stopped, once the stream has ended.
+        ///
+        /// ```no_run
+        /// # tokio_test::block_on(async {
+        /// # let client = openai_rust::Client::new("");
+        /// # let args = openai_rust::chat::ChatArguments::new("gpt-4o", vec![]);
+        /// use openai_rust::futures_util::StreamExt;
+        /// let mut stream = client.create_chat_stream(args).await.unwrap();
+        /// while let Some(chunk) = stream.next().await {
+        ///     print!("{}", chunk.unwrap());
+        /// }
+        /// match stream.end() {
+        ///     Some(end) => println!("\nstopped because of {:?}", end.finish_reason),
+        ///     None => println!("\nconnection lost"),
+        /// }
+        /// # })
+        /// ```
+        pub fn end(&self) -> Option<&StreamEnd> {
+            self.end.as_ref()
+        }
+
+        /// Turn the chunks back into server-sent events, like they were sent by the API,
+        /// to proxy a stream to a browser.
+        ///
+        /// Every chunk is sent as a `data:` line. The `[DONE]` marker is only sent when the API sent it,
+        /// so the receiver can tell a complete answer from a lost connection.
+        /// The stream ends after the first error.
+        ///
+        /// ```no_run
+        /// # tokio_test::block_on(async {
+        /// # let client = openai_rust::Client::new("");
+        /// # let args = openai_rust::chat::ChatArguments::new("gpt-4o", vec![]);
+        /// use openai_rust::futures_util::StreamExt;
+        /// let stream = client.create_chat_stream(args).await.unwrap();
+        /// // Like axum::body::Body::from_stream(stream.into_sse())
+        /// let mut events = stream.into_sse();
+        /// while let Some(event) = events.next().await {
+        ///     print!("{}", std::str::from_utf8(&event.unwrap()).unwrap());
+        /// }
+        /// # })
+        /// ```
+        pub fn into_sse(self) -> impl Stream<Item = crate::error::Result<Bytes>> + Send + Unpin {
+            use futures_util::StreamExt;
+            Box::pin(futures_util::stream::unfold(Some(self), |stream| async move {
+                let mut stream = stream?;
+                match stream.next().await {
+                    Some(Ok(chunk)) => {
+                        let event = match serde_json::to_string(&chunk) {
+                            Ok(json) => Ok(Bytes::from(format!("data: {}\n\n", json))),
+                            Err(e) => Err(e.into()),
+                        };
+                        let next = event.is_ok().then_some(stream);
+                        Some((event, next))
                     }
-                },
-                None => None,
+                    Some(Err(e)) => Some((Err(e), None)),
+                    None if stream.is_done() => Some((Ok(Bytes::from_static(b"data: [DONE]\n\n")), None)),
+                    None => None,
+                }
+            }))
+        }
+
+        /// Remember the finish reason, usage and tool calls of a chunk for [ChatCompletionChunkStream::end].
+        fn observe(&mut self, chunk: &ChatCompletionChunk) {
+            if let Some(reason) = chunk.choices.iter().rev().find_map(|c| c.finish_reason.clone()) {
+                self.finish_reason = Some(reason);
+            }
+            if let Some(usage) = &chunk.usage {
+                if let Some(on_usage) = self.on_usage.take() {
+                    on_usage(usage);
+                }
+                self.usage = Some(usage.clone());
             }
+            self.tool_calls.push(chunk);
         }
     }
 
     impl Stream for ChatCompletionChunkStream {
-        type Item = anyhow::Result<ChatCompletionChunk>;
+        type Item = crate::error::Result<ChatCompletionChunk>;
 
         fn poll_next(mut self: Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<Option<Self::Item>> {
-
-            // Possibly fetch a chunk from the buffer
-            match self.as_mut().deserialize_buf(cx) {
-                Some(chunk) => return Poll::Ready(Some(chunk)),
-                None => {},
+            // Nothing comes after the [DONE] marker
+            if self.end.is_some() {
+                return Poll::Ready(None);
+            }
+            let event = match Pin::new(&mut self.events).poll_next(cx) {
+                Poll::Ready(Some(Ok(event))) => event,
+                Poll::Ready(Some(Err(e))) => return Poll::Ready(Some(Err(e.into()))),
+                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Pending => return Poll::Pending,
             };
-
-            match self.byte_stream.as_mut().poll_next(cx) {
-                Poll::Ready(bytes_option) => match bytes_option {
-                    Some(bytes_result) => match bytes_result {
-                        Ok(bytes) => {
-                            // Finally actually get some bytes
-                            let data = str::from_utf8(&bytes)?.to_owned();
-                            self.buf = self.buf.clone() + &data;
-                            match self.deserialize_buf(cx) {
-                                Some(chunk) => Poll::Ready(Some(chunk)),
-                                // Partial
-                                None => {
-                                    // On a partial, I think the best we can do is just to wake the
-                                    // task again. If we don't this task will get stuck.
-                                    cx.waker().wake_by_ref();
-                                    Poll::Pending
-                                },
-                            }
-                        },
-                        Err(e) => Poll::Ready(Some(Err(e.into()))),
-                    },
-                    // Stream terminated
-                    None => Poll::Ready(None),
-                },
-                Poll::Pending => Poll::Pending,
+            if event.data.trim() == "[DONE]" {
+                let this = self.get_mut();
+                this.end = Some(StreamEnd {
+                    finish_reason: this.finish_reason.take(),
+                    usage: this.usage.take(),
+                    tool_calls: std::mem::take(&mut this.tool_calls).finish(),
+                });
+                return Poll::Ready(None);
             }
+            let chunk = serde_json::from_str::<ChatCompletionChunk>(&event.data).map_err(crate::error::Error::from);
+            if let Ok(chunk) = &chunk {
+                self.observe(chunk);
+            }
+            Poll::Ready(Some(chunk))
         }
     }
 }
 
 /// Infomration about the tokens used by [ChatCompletion].
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+#[serde(default)]
 pub struct Usage {
     pub prompt_tokens: u32,
     pub completion_tokens: u32,
@@ -311,23 +1226,420 @@ pub struct Usage {
 }
 
 /// Completion choices from [ChatCompletion].
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[non_exhaustive]
 pub struct Choice {
+    #[serde(default, deserialize_with = "crate::de::null_default")]
     pub index: u32,
     pub message: Message,
+    #[serde(default, deserialize_with = "crate::de::null_default")]
     pub finish_reason: String,
+    /// The log probabilities of the tokens of the message, when requested with [logprobs](ChatArguments::logprobs).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub logprobs: Option<Logprobs>,
+}
+
+/// The log probabilities of the tokens of a [Choice].
+///
+/// ```
+/// # let json = r#"{"content": [{"token": "Yes", "logprob": -0.0019, "bytes": [89, 101, 115],
+/// #   "top_logprobs": [{"token": "Yes", "logprob": -0.0019, "bytes": [89, 101, 115]}, {"token": "No", "logprob": -6.27, "bytes": [78, 111]}]}],
+/// #   "refusal": null}"#;
+/// let logprobs: openai_rust::chat::Logprobs = serde_json::from_str(json).unwrap();
+/// let first = &logprobs.content[0];
+/// assert!(first.probability() > 0.99);
+/// assert_eq!(first.top_logprobs[1].token, "No");
+/// assert_eq!(first.bytes.as_deref(), Some("Yes".as_bytes()));
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[non_exhaustive]
+pub struct Logprobs {
+    /// The tokens of the content of the message.
+    #[serde(default, deserialize_with = "crate::de::null_default")]
+    pub content: Vec<LogprobsContent>,
+    /// The tokens of the refusal of the message.
+    #[serde(default, deserialize_with = "crate::de::null_default")]
+    pub refusal: Vec<LogprobsContent>,
+}
+
+/// A token and its log probability, with the most likely alternatives.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct LogprobsContent {
+    pub token: String,
+    pub logprob: f32,
+    /// The UTF-8 bytes of the token. Characters can be split across tokens, so the bytes of consecutive tokens
+    /// have to be combined to decode them.
+    #[serde(default)]
+    pub bytes: Option<Vec<u8>>,
+    /// The most likely tokens at this position, as many as [top_logprobs](ChatArguments::top_logprobs).
+    #[serde(default, deserialize_with = "crate::de::null_default")]
+    pub top_logprobs: Vec<TopLogprob>,
+}
+
+impl LogprobsContent {
+    /// The probability of the token, between 0 and 1.
+    pub fn probability(&self) -> f32 {
+        self.logprob.exp()
+    }
+}
+
+/// An alternative to a token in [LogprobsContent::top_logprobs].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct TopLogprob {
+    pub token: String,
+    pub logprob: f32,
+    #[serde(default)]
+    pub bytes: Option<Vec<u8>>,
+}
+
+impl TopLogprob {
+    /// The probability of the token, between 0 and 1.
+    pub fn probability(&self) -> f32 {
+        self.logprob.exp()
+    }
 }
 
 /// A message.
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, Hash)]
 pub struct Message {
-    pub role: String,
-    pub content: String,
+    pub role: Role,
+    /// The text of the message, or parts like text and images for vision models.
+    #[serde(default, deserialize_with = "crate::de::null_default")]
+    pub content: MessageContent,
+    /// Why the model refused to answer, instead of answering with [ResponseFormat::JsonSchema].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub refusal: Option<String>,
+    /// The sources cited by the assistant, like the results of a web search.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub annotations: Vec<Annotation>,
+    /// The tools called by the assistant.
+    #[serde(default, skip_serializing_if = "Vec::is_empty", deserialize_with = "crate::de::null_default")]
+    pub tool_calls: Vec<ToolCall>,
+    /// The call answered by a message with the `tool` role.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_call_id: Option<String>,
+    /// The function called by the assistant, when using the deprecated [functions](ChatArguments::functions).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub function_call: Option<FunctionCall>,
+    /// The name of the participant, or of the function answered by a message with the `function` role.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+impl Message {
+    pub fn new(role: Role, content: impl Into<MessageContent>) -> Message {
+        Message {
+            role,
+            content: content.into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn system(content: impl Into<MessageContent>) -> Message {
+        Message::new(Role::System, content)
+    }
+
+    pub fn developer(content: impl Into<MessageContent>) -> Message {
+        Message::new(Role::Developer, content)
+    }
+
+    pub fn user(content: impl Into<MessageContent>) -> Message {
+        Message::new(Role::User, content)
+    }
+
+    pub fn assistant(content: impl Into<MessageContent>) -> Message {
+        Message::new(Role::Assistant, content)
+    }
+
+    /// A message with the `tool` role, with the result of a [ToolCall] of the assistant.
+    pub fn tool_result(tool_call_id: impl AsRef<str>, content: impl AsRef<str>) -> Message {
+        Message {
+            role: Role::Tool,
+            content: content.as_ref().into(),
+            tool_call_id: Some(tool_call_id.as_ref().to_owned()),
+            ..Default::default()
+        }
+    }
+}
+
+/// The [content](Message::content) of a message.
+///
+/// Messages of the user can combine text with images and audio:
+///
+/// ```
+/// use openai_rust::chat::{ContentPart, Message};
+/// use openai_rust::vision::{ImageDetail, ImageUrl};
+/// let message = Message::user(vec![
+///     ContentPart::text("What is in this image?"),
+///     ContentPart::image_url(ImageUrl::new("https://example.com/otter.png").detail(ImageDetail::Low)),
+/// ]);
+/// let json = serde_json::to_value(&message).unwrap();
+/// assert_eq!(json["content"][1]["type"], "image_url");
+/// assert_eq!(json["content"][1]["image_url"]["url"], "https://example.com/otter.png");
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(untagged)]
+pub enum MessageContent {
+    Text(String),
+    Parts(Vec<ContentPart>),
+}
+
+impl MessageContent {
+    /// The text, if the content is a single text.
+    pub fn as_text(&self) -> Option<&str> {
+        match self {
+            MessageContent::Text(text) => Some(text),
+            MessageContent::Parts(parts) => match parts.as_slice() {
+                [ContentPart::Text { text }] => Some(text),
+                _ => None,
+            },
+        }
+    }
+
+    /// The text, with the text parts joined and the other parts left out.
+    pub fn text(&self) -> std::borrow::Cow<'_, str> {
+        match self.as_text() {
+            Some(text) => text.into(),
+            None => self.to_string().into(),
+        }
+    }
+}
+
+impl Default for MessageContent {
+    fn default() -> Self {
+        MessageContent::Text(String::new())
+    }
+}
+
+impl std::fmt::Display for MessageContent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MessageContent::Text(text) => f.write_str(text),
+            MessageContent::Parts(parts) => parts.iter().try_for_each(|part| match part {
+                ContentPart::Text { text } => f.write_str(text),
+                _ => Ok(()),
+            }),
+        }
+    }
+}
+
+impl From<String> for MessageContent {
+    fn from(text: String) -> Self {
+        MessageContent::Text(text)
+    }
+}
+
+impl From<&str> for MessageContent {
+    fn from(text: &str) -> Self {
+        MessageContent::Text(text.to_owned())
+    }
+}
+
+impl From<&String> for MessageContent {
+    fn from(text: &String) -> Self {
+        MessageContent::Text(text.clone())
+    }
+}
+
+impl From<Vec<ContentPart>> for MessageContent {
+    fn from(parts: Vec<ContentPart>) -> Self {
+        MessageContent::Parts(parts)
+    }
+}
+
+impl PartialEq<str> for MessageContent {
+    fn eq(&self, other: &str) -> bool {
+        self.as_text() == Some(other)
+    }
+}
+
+impl PartialEq<&str> for MessageContent {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_text() == Some(*other)
+    }
+}
+
+/// A part of the [MessageContent] of a message.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ContentPart {
+    Text { text: String },
+    /// An image, for vision models like GPT-4o. See [vision] to embed local or private images.
+    ///
+    /// [vision]: crate::vision
+    ImageUrl { image_url: ImageUrl },
+    /// Audio, for audio models like `gpt-4o-audio-preview`.
+    InputAudio { input_audio: InputAudio },
+}
+
+impl ContentPart {
+    pub fn text(text: impl AsRef<str>) -> ContentPart {
+        ContentPart::Text {
+            text: text.as_ref().to_owned(),
+        }
+    }
+
+    pub fn image_url(image_url: ImageUrl) -> ContentPart {
+        ContentPart::ImageUrl { image_url }
+    }
+
+    pub fn input_audio(input_audio: InputAudio) -> ContentPart {
+        ContentPart::InputAudio { input_audio }
+    }
+}
+
+/// Audio in a [ContentPart].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct InputAudio {
+    /// The audio, encoded as base64.
+    pub data: String,
+    /// The format of the audio, `wav` or `mp3`.
+    pub format: String,
+}
+
+impl InputAudio {
+    /// Audio in a format like `wav` or `mp3`.
+    pub fn from_bytes(audio: &[u8], format: impl AsRef<str>) -> InputAudio {
+        use base64::Engine;
+        InputAudio {
+            data: base64::engine::general_purpose::STANDARD.encode(audio),
+            format: format.as_ref().to_owned(),
+        }
+    }
+}
+
+/// A source cited in the [content](Message::content) of a message.
+///
+/// ```
+/// # let json = r#"{
+/// #   "role": "assistant",
+/// #   "content": "The Eiffel Tower is 330 meters tall.",
+/// #   "annotations": [
+/// #     {
+/// #       "type": "url_citation",
+/// #       "url_citation": {
+/// #         "start_index": 0,
+/// #         "end_index": 36,
+/// #         "title": "Eiffel Tower - Wikipedia",
+/// #         "url": "https://en.wikipedia.org/wiki/Eiffel_Tower"
+/// #       }
+/// #     }
+/// #   ]
+/// # }"#;
+/// # let message = serde_json::from_str::<openai_rust::chat::Message>(json).unwrap();
+/// use openai_rust::chat::Annotation;
+/// for annotation in &message.annotations {
+///     if let Annotation::UrlCitation { url_citation } = annotation {
+///         println!("[{}]({})", url_citation.title, url_citation.url);
+///     }
+/// }
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Annotation {
+    /// A web page found by web search.
+    UrlCitation { url_citation: UrlCitation },
+    /// An annotation not yet supported by this crate, as its JSON.
+    #[serde(untagged)]
+    Other(serde_json::Value),
+}
+
+/// A web page cited in a message.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct UrlCitation {
+    pub url: String,
+    pub title: String,
+    /// The index of the first character of the citation in the content.
+    pub start_index: usize,
+    /// The index after the last character of the citation in the content.
+    pub end_index: usize,
 }
 
 /// Role of a [Message].
+///
+/// Compares equal to its name, so `message.role == "assistant"` works.
+/// Roles not yet supported by this crate are kept in [Role::Other].
+///
+/// ```
+/// use openai_rust::chat::{Message, Role};
+/// let message: Message = serde_json::from_str(r#"{"role": "assistant", "content": "Hi!"}"#).unwrap();
+/// assert_eq!(message.role, Role::Assistant);
+/// assert_eq!(message.role, "assistant");
+/// let message: Message = serde_json::from_str(r#"{"role": "critic", "content": "Meh."}"#).unwrap();
+/// assert_eq!(message.role, Role::Other("critic".to_owned()));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
 pub enum Role {
     System,
-    Assistant,
+    /// Instructions that take precedence over user messages, replacing `system` for reasoning models like o1.
+    Developer,
+    #[default]
     User,
+    Assistant,
+    /// The result of a [ToolCall], see [Message::tool_result].
+    Tool,
+    /// The result of a function call, when using the deprecated [functions](ChatArguments::functions).
+    Function,
+    Other(String),
+}
+
+impl Role {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Role::System => "system",
+            Role::Developer => "developer",
+            Role::User => "user",
+            Role::Assistant => "assistant",
+            Role::Tool => "tool",
+            Role::Function => "function",
+            Role::Other(role) => role,
+        }
+    }
+}
+
+impl From<&str> for Role {
+    fn from(role: &str) -> Role {
+        match role {
+            "system" => Role::System,
+            "developer" => Role::Developer,
+            "user" => Role::User,
+            "assistant" => Role::Assistant,
+            "tool" => Role::Tool,
+            "function" => Role::Function,
+            role => Role::Other(role.to_owned()),
+        }
+    }
+}
+
+impl std::fmt::Display for Role {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl PartialEq<str> for Role {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for Role {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl Serialize for Role {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for Role {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Role, D::Error> {
+        Ok(Role::from(String::deserialize(deserializer)?.as_str()))
+    }
 }
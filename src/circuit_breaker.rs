@@ -0,0 +1,169 @@
+//! A circuit breaker for the [Client](crate::Client).
+//! Attach with [Client::with_circuit_breaker](crate::Client::with_circuit_breaker).
+//!
+//! After a number of consecutive failures the circuit opens, and requests fail immediately with a
+//! [CircuitOpen] error instead of waiting on a degraded API. Once the cooldown has passed a single
+//! request is let through: if it succeeds the circuit closes again, otherwise it stays open for another cooldown.
+//!
+//! Failures are server errors (5xx) and requests that timed out or could not connect.
+//!
+//! ```
+//! use std::time::Duration;
+//! use openai_rust::circuit_breaker::CircuitBreaker;
+//! let breaker = CircuitBreaker::new(3, Duration::from_secs(30));
+//! for _ in 0..3 {
+//!     breaker.record_failure();
+//! }
+//! assert!(breaker.check().is_err());
+//! ```
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Fails requests fast after repeated failures of the API.
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    threshold: u32,
+    cooldown: Duration,
+    state: Mutex<State>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    failures: u32,
+    opened_at: Option<Instant>,
+    /// Whether a request is let through to test if the API recovered.
+    probing: bool,
+    /// The amount of requests let through after a cooldown, to tell the current one apart.
+    probes: u64,
+}
+
+impl CircuitBreaker {
+    /// Open the circuit after `threshold` consecutive failures, for `cooldown`.
+    pub fn new(threshold: u32, cooldown: Duration) -> CircuitBreaker {
+        CircuitBreaker {
+            threshold: threshold.max(1),
+            cooldown,
+            state: Mutex::new(State::default()),
+        }
+    }
+
+    /// Whether requests are currently failing fast.
+    pub fn is_open(&self) -> bool {
+        self.state.lock().unwrap().opened_at.is_some()
+    }
+
+    /// The amount of consecutive failures.
+    pub fn failures(&self) -> u32 {
+        self.state.lock().unwrap().failures
+    }
+
+    /// Check whether a request may be sent.
+    ///
+    /// After the cooldown this lets a single request through, until its outcome is recorded
+    /// or the returned [Probe] is dropped, like when the request is cancelled.
+    pub fn check(&self) -> Result<Probe<'_>, CircuitOpen> {
+        let mut state = self.state.lock().unwrap();
+        let Some(opened_at) = state.opened_at else {
+            return Ok(Probe { breaker: self, probe: None });
+        };
+        let elapsed = opened_at.elapsed();
+        if elapsed >= self.cooldown && !state.probing {
+            state.probing = true;
+            state.probes += 1;
+            return Ok(Probe {
+                breaker: self,
+                probe: Some(state.probes),
+            });
+        }
+        Err(CircuitOpen {
+            failures: state.failures,
+            retry_in: self.cooldown.saturating_sub(elapsed),
+        })
+    }
+
+    /// Record a successful request, which closes the circuit.
+    pub fn record_success(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.failures = 0;
+        state.opened_at = None;
+        state.probing = false;
+    }
+
+    /// Record a failed request, which opens the circuit once the threshold is reached.
+    pub fn record_failure(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.failures += 1;
+        if state.failures >= self.threshold {
+            state.opened_at = Some(Instant::now());
+            state.probing = false;
+        }
+    }
+
+    /// Record the outcome of a request.
+    pub(crate) fn record(&self, result: &anyhow::Result<reqwest::Response>) {
+        match is_failure(result) {
+            Some(true) => self.record_failure(),
+            Some(false) => self.record_success(),
+            // the request itself was invalid, which says nothing about the API,
+            // so let another request probe it
+            None => self.state.lock().unwrap().probing = false,
+        }
+    }
+}
+
+/// A request let through by [CircuitBreaker::check].
+///
+/// When the request is the single one let through after the cooldown, dropping this before its outcome is
+/// recorded lets another request through, so a cancelled request does not keep the circuit open forever.
+#[must_use = "dropping the probe lets another request through"]
+#[derive(Debug)]
+pub struct Probe<'a> {
+    breaker: &'a CircuitBreaker,
+    probe: Option<u64>,
+}
+
+impl Drop for Probe<'_> {
+    fn drop(&mut self) {
+        let Some(probe) = self.probe else {
+            return;
+        };
+        let mut state = self.breaker.state.lock().unwrap();
+        if state.probing && state.probes == probe {
+            state.probing = false;
+        }
+    }
+}
+
+/// Whether the outcome of a request means the API is failing,
+/// or `None` when the request could not be sent for another reason.
+pub(crate) fn is_failure(result: &anyhow::Result<reqwest::Response>) -> Option<bool> {
+    match result {
+        Ok(res) => Some(res.status().is_server_error()),
+        Err(err) => match err.downcast_ref::<reqwest::Error>() {
+            Some(err) if err.is_timeout() || err.is_connect() => Some(true),
+            _ => None,
+        },
+    }
+}
+
+/// The error returned while a [CircuitBreaker] is open.
+#[derive(Debug, Clone)]
+pub struct CircuitOpen {
+    /// The amount of consecutive failures.
+    pub failures: u32,
+    /// How long until a request is let through again.
+    pub retry_in: Duration,
+}
+
+impl std::fmt::Display for CircuitOpen {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "circuit open after {} failures, retrying in {}s",
+            self.failures,
+            self.retry_in.as_secs()
+        )
+    }
+}
+
+impl std::error::Error for CircuitOpen {}
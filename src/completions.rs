@@ -4,6 +4,9 @@ use std::collections::HashMap;
 
 use serde::{Deserialize, Serialize};
 
+use crate::logit_bias::LogitBias;
+use crate::stop::StopSequences;
+
 /// Request arguments for completions.
 ///
 /// See <https://platform.openai.com/docs/api-reference/completions/create>.
@@ -12,9 +15,12 @@ use serde::{Deserialize, Serialize};
 /// let args = openai_rust::completions::CompletionArguments::new(
 ///     "text-davinci-003",
 ///     "The quick brown fox".to_owned()
-/// );
+/// )
+/// .max_tokens(64)
+/// .temperature(0.2);
 /// ```
-#[derive(Serialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[non_exhaustive]
 pub struct CompletionArguments {
     /// ID of the model to use.
     /// You can use the [List models](crate::Client::list_models) API to see all of your available models,
@@ -66,7 +72,7 @@ pub struct CompletionArguments {
     /// Use carefully and ensure that you have reasonable settings for `max_tokens` and `stop`.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub n: Option<u32>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "Option::is_none", skip_deserializing)]
     pub(crate) stream: Option<bool>,
 
     /// Include the log probabilities on the `logprobs` most likely tokens,
@@ -86,7 +92,7 @@ pub struct CompletionArguments {
 
     /// Up to 4 sequences where the API will stop generating further tokens. The returned text will not contain the stop sequence.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub stop: Option<String>,
+    pub stop: Option<StopSequences>,
 
     /// Number between -2.0 and 2.0. Positive values penalize new tokens based on whether they appear in the text so far,
     /// increasing the model's likelihood to talk about new topics.
@@ -112,11 +118,18 @@ pub struct CompletionArguments {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub best_of: Option<u32>,
 
-    //logit_bias
+    /// Make tokens more or less likely to appear in the output, see [logit_bias](crate::logit_bias).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub logit_bias: Option<LogitBias>,
+
     /// A unique identifier representing your end-user, which can help OpenAI to monitor and detect abuse.
     /// [Learn more](https://platform.openai.com/docs/guides/safety-best-practices/end-user-ids).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub user: Option<String>,
+
+    /// Additional parameters sent in the body, for parameters not yet supported by this crate or specific to a provider.
+    #[serde(flatten, skip_serializing_if = "Option::is_none", deserialize_with = "crate::de::empty_none")]
+    pub extra: Option<serde_json::Map<String, serde_json::Value>>,
 }
 
 impl CompletionArguments {
@@ -136,9 +149,83 @@ impl CompletionArguments {
             presence_penalty: None,
             frequency_penalty: None,
             best_of: None,
+            logit_bias: None,
             user: None,
+            extra: None,
         }
     }
+
+    pub fn suffix(mut self, suffix: impl AsRef<str>) -> CompletionArguments {
+        self.suffix = Some(suffix.as_ref().to_owned());
+        self
+    }
+
+    pub fn max_tokens(mut self, max_tokens: u32) -> CompletionArguments {
+        self.max_tokens = Some(max_tokens);
+        self
+    }
+
+    pub fn temperature(mut self, temperature: f32) -> CompletionArguments {
+        self.temperature = Some(temperature);
+        self
+    }
+
+    pub fn top_p(mut self, top_p: f32) -> CompletionArguments {
+        self.top_p = Some(top_p);
+        self
+    }
+
+    pub fn n(mut self, n: u32) -> CompletionArguments {
+        self.n = Some(n);
+        self
+    }
+
+    pub fn logprobs(mut self, logprobs: u8) -> CompletionArguments {
+        self.logprobs = Some(logprobs);
+        self
+    }
+
+    pub fn echo(mut self, echo: bool) -> CompletionArguments {
+        self.echo = Some(echo);
+        self
+    }
+
+    /// A single stop sequence like `"\n"`, or up to 4 like `["\n\n", "User:"]`.
+    pub fn stop(mut self, stop: impl Into<StopSequences>) -> CompletionArguments {
+        self.stop = Some(stop.into());
+        self
+    }
+
+    pub fn presence_penalty(mut self, presence_penalty: f32) -> CompletionArguments {
+        self.presence_penalty = Some(presence_penalty);
+        self
+    }
+
+    pub fn frequency_penalty(mut self, frequency_penalty: f32) -> CompletionArguments {
+        self.frequency_penalty = Some(frequency_penalty);
+        self
+    }
+
+    pub fn best_of(mut self, best_of: u32) -> CompletionArguments {
+        self.best_of = Some(best_of);
+        self
+    }
+
+    pub fn logit_bias(mut self, logit_bias: LogitBias) -> CompletionArguments {
+        self.logit_bias = Some(logit_bias);
+        self
+    }
+
+    pub fn user(mut self, user: impl AsRef<str>) -> CompletionArguments {
+        self.user = Some(user.as_ref().to_owned());
+        self
+    }
+
+    /// Add a parameter not yet supported by this crate to [extra](CompletionArguments::extra).
+    pub fn extra(mut self, key: impl AsRef<str>, value: impl Into<serde_json::Value>) -> CompletionArguments {
+        self.extra.get_or_insert_with(Default::default).insert(key.as_ref().to_owned(), value.into());
+        self
+    }
 }
 
 /// The repsonse of a completion request.
@@ -170,34 +257,60 @@ impl CompletionArguments {
 /// // or
 /// let text = res.to_string();
 /// ```
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[non_exhaustive]
 pub struct CompletionResponse {
+    #[serde(default, deserialize_with = "crate::de::null_default")]
     pub id: String,
-    pub created: u32,
+    #[serde(default, deserialize_with = "crate::de::null_default")]
+    pub created: u64,
+    #[serde(default, deserialize_with = "crate::de::null_default")]
     pub model: String,
     pub choices: Vec<Choice>,
-    pub usage: Usage,
+    pub usage: Option<Usage>,
+}
+
+impl CompletionResponse {
+    /// When this was created, see [created](Self::created).
+    #[cfg(feature = "chrono")]
+    pub fn created_at(&self) -> chrono::DateTime<chrono::Utc> {
+        crate::timestamp(self.created)
+    }
+
+    /// The first choice, if there is one.
+    pub fn first_choice(&self) -> Option<&Choice> {
+        self.choices.first()
+    }
+
+    /// The text of the first choice, if there is one.
+    pub fn first_text(&self) -> Option<&str> {
+        self.first_choice().map(|c| c.text.as_str())
+    }
 }
 
 impl std::fmt::Display for CompletionResponse {
-    /// Automatically grab the first choice
+    /// Automatically grab the first choice, or nothing if there are no choices
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.choices[0].text)?;
+        write!(f, "{}", self.first_text().unwrap_or_default())?;
         Ok(())
     }
 }
 
 /// The completion choices of a completion response.
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[non_exhaustive]
 pub struct Choice {
     pub text: String,
+    #[serde(default, deserialize_with = "crate::de::null_default")]
     pub index: u32,
     pub logprobs: Option<LogProbs>,
+    #[serde(default, deserialize_with = "crate::de::null_default")]
     pub finish_reason: String,
 }
 
 /// The log probabilities of a completion response.
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[non_exhaustive]
 pub struct LogProbs {
     pub tokens: Vec<String>,
     pub token_logprobs: Vec<f32>,
@@ -206,7 +319,9 @@ pub struct LogProbs {
 }
 
 /// Infomration about the tokens used by [CompletionResponse].
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[non_exhaustive]
+#[serde(default)]
 pub struct Usage {
     pub prompt_tokens: u32,
     pub completion_tokens: u32,
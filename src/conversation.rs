@@ -0,0 +1,208 @@
+//! A conversation with a chat model, which keeps track of the messages sent and received.
+//!
+//! Conversations can be saved as JSON and loaded again later to continue them,
+//! or exported as a line of a fine-tuning [TrainingFile](crate::fine_tuning::TrainingFile).
+//!
+//! ```no_run
+//! # tokio_test::block_on(async {
+//! # let api_key = "";
+//! use openai_rust::conversation::Conversation;
+//! let client = openai_rust::Client::new(api_key);
+//! let mut conversation = Conversation::new("gpt-4o").system("You are a helpful assistant.");
+//! let reply = conversation.send(&client, "What's the capital of France?").await.unwrap();
+//! println!("{}", reply);
+//!
+//! let json = conversation.to_json();
+//! let mut conversation = Conversation::from_json(&json).unwrap();
+//! let reply = conversation.send(&client, "And of Germany?").await.unwrap();
+//! # })
+//! ```
+//!
+//! To keep conversations across restarts, save them in a [ConversationStore] like a [FileStore]:
+//!
+//! ```no_run
+//! # tokio_test::block_on(async {
+//! # let api_key = "";
+//! use openai_rust::conversation::{Conversation, FileStore};
+//! let client = openai_rust::Client::new(api_key);
+//! let store = FileStore::new("conversations");
+//! let mut conversation = Conversation::load(&store, "user-42")
+//!     .await
+//!     .unwrap()
+//!     .unwrap_or_else(|| Conversation::new("gpt-4o"));
+//! conversation.send(&client, "Where were we?").await.unwrap();
+//! conversation.save(&store, "user-42").await.unwrap();
+//! # })
+//! ```
+use std::future::Future;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::chat::{ChatArguments, ChatCompletion, Message, Role};
+use anyhow::anyhow;
+use serde::{Deserialize, Serialize};
+
+/// The messages of a conversation with a model.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct Conversation {
+    /// The model to send the conversation to.
+    pub model: String,
+    pub messages: Vec<Message>,
+}
+
+impl Conversation {
+    pub fn new(model: impl AsRef<str>) -> Conversation {
+        Conversation {
+            model: model.as_ref().to_owned(),
+            messages: vec![],
+        }
+    }
+
+    /// Start the conversation with a system message.
+    pub fn system(mut self, content: impl AsRef<str>) -> Conversation {
+        self.push(Role::System, content);
+        self
+    }
+
+    /// Add a message to the end of the conversation.
+    pub fn push(&mut self, role: impl Into<Role>, content: impl AsRef<str>) -> &mut Self {
+        self.messages.push(Message::new(role.into(), content.as_ref()));
+        self
+    }
+
+    /// Send a user message and add the reply of the model to the conversation.
+    ///
+    /// If the request fails, the user message is removed again.
+    pub async fn send(&mut self, client: &crate::Client, content: impl AsRef<str>) -> anyhow::Result<ChatCompletion> {
+        self.push(Role::User, content);
+        let args = ChatArguments::new(&self.model, self.messages.clone());
+        match client.create_chat(args).await {
+            Ok(completion) => {
+                if let Some(choice) = completion.first_choice() {
+                    self.messages.push(choice.message.clone());
+                }
+                Ok(completion)
+            }
+            Err(e) => {
+                self.messages.pop();
+                Err(e.into())
+            }
+        }
+    }
+
+    /// Save the conversation, to continue it later with [Conversation::from_json].
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+
+    pub fn from_json(json: &str) -> serde_json::Result<Conversation> {
+        serde_json::from_str(json)
+    }
+
+    /// Load the conversation with an ID from a store, or `None` if it was never saved.
+    pub async fn load(store: &impl ConversationStore, id: &str) -> anyhow::Result<Option<Conversation>> {
+        store.load(id).await
+    }
+
+    /// Save the conversation under an ID in a store, replacing the conversation saved under the same ID.
+    pub async fn save(&self, store: &impl ConversationStore, id: &str) -> anyhow::Result<()> {
+        store.save(id, self).await
+    }
+
+    /// The conversation as a line of a fine-tuning file, including the newline.
+    ///
+    /// Use a [TrainingFile](crate::fine_tuning::TrainingFile) to validate the conversations first.
+    ///
+    /// ```
+    /// use openai_rust::conversation::Conversation;
+    /// let mut conversation = Conversation::new("gpt-4o-mini").system("Marv is a sarcastic chatbot.");
+    /// conversation.push("user", "What's the capital of France?");
+    /// conversation.push("assistant", "Paris, as if everyone doesn't know that already.");
+    /// let jsonl: String = [conversation.clone(), conversation].iter().map(Conversation::to_jsonl).collect();
+    /// assert_eq!(jsonl.lines().count(), 2);
+    /// assert!(jsonl.starts_with(r#"{"messages":[{"role":"system","content":"Marv is a sarcastic chatbot."}"#));
+    /// ```
+    #[cfg(feature = "fine_tuning")]
+    pub fn to_jsonl(&self) -> String {
+        let line = crate::fine_tuning::Line { messages: &self.messages };
+        serde_json::to_string(&line).unwrap() + "\n"
+    }
+}
+
+impl From<Conversation> for Vec<Message> {
+    fn from(conversation: Conversation) -> Self {
+        conversation.messages
+    }
+}
+
+/// Keeps conversations by ID, like in files or a database.
+pub trait ConversationStore: Send + Sync {
+    /// The conversation saved under an ID, or `None` if there is none.
+    fn load(&self, id: &str) -> impl Future<Output = anyhow::Result<Option<Conversation>>> + Send;
+
+    fn save(&self, id: &str, conversation: &Conversation) -> impl Future<Output = anyhow::Result<()>> + Send;
+}
+
+/// Saves every conversation as a JSON file named after its ID, in a directory.
+///
+/// Files are written to a temporary file first and then renamed,
+/// so a crash while saving leaves the previous version intact.
+///
+/// ```
+/// # tokio_test::block_on(async {
+/// use openai_rust::conversation::{Conversation, ConversationStore, FileStore};
+/// let store = FileStore::new(std::env::temp_dir().join("openai-rust-conversations"));
+/// let conversation = Conversation::new("gpt-4o").system("You are a helpful assistant.");
+/// store.save("doc-test", &conversation).await.unwrap();
+/// assert_eq!(store.load("doc-test").await.unwrap(), Some(conversation));
+/// assert_eq!(store.load("never-saved").await.unwrap(), None);
+/// assert!(store.load("../secrets").await.is_err());
+/// # })
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileStore {
+    dir: PathBuf,
+}
+
+impl FileStore {
+    /// Store conversations in a directory, which is created when the first conversation is saved.
+    pub fn new(dir: impl Into<PathBuf>) -> FileStore {
+        FileStore { dir: dir.into() }
+    }
+
+    /// The file of a conversation. IDs may only contain letters, digits, dashes and underscores.
+    fn path(&self, id: &str) -> anyhow::Result<PathBuf> {
+        let valid = !id.is_empty() && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if !valid {
+            return Err(anyhow!("{:?} is not a valid conversation ID", id));
+        }
+        Ok(self.dir.join(format!("{}.json", id)))
+    }
+}
+
+impl ConversationStore for FileStore {
+    async fn load(&self, id: &str) -> anyhow::Result<Option<Conversation>> {
+        match tokio::fs::read_to_string(self.path(id)?).await {
+            Ok(json) => Ok(Some(Conversation::from_json(&json)?)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn save(&self, id: &str, conversation: &Conversation) -> anyhow::Result<()> {
+        let path = self.path(id)?;
+        tokio::fs::create_dir_all(&self.dir).await?;
+        // Concurrent saves of the same conversation each write their own file
+        static SAVES: AtomicU64 = AtomicU64::new(0);
+        let save = SAVES.fetch_add(1, Ordering::Relaxed);
+        let temporary = path.with_extension(format!("json.{}.{}.tmp", std::process::id(), save));
+        let written = match tokio::fs::write(&temporary, conversation.to_json()).await {
+            Ok(()) => tokio::fs::rename(&temporary, &path).await,
+            Err(e) => Err(e),
+        };
+        if written.is_err() {
+            let _ = tokio::fs::remove_file(&temporary).await;
+        }
+        Ok(written?)
+    }
+}
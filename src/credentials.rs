@@ -0,0 +1,112 @@
+//! API keys fetched at runtime, for keys stored in a secret manager and rotated frequently.
+//! Attach with [Client::with_credentials](crate::Client::with_credentials).
+//!
+//! A [CredentialsProvider] is asked for the key when a request is sent.
+//! The key is cached until it expires, so the provider is not called for every request.
+//! When the API responds with `401 Unauthorized` the cached key is dropped,
+//! and the next request fetches a new one.
+//!
+//! Closures returning a future implement [CredentialsProvider]:
+//!
+//! ```
+//! use std::time::Duration;
+//! use openai_rust::credentials::Credentials;
+//! # async fn fetch_from_vault() -> anyhow::Result<String> { Ok(String::new()) }
+//! let client = openai_rust::Client::new("").with_credentials(|| async {
+//!     let key = fetch_from_vault().await?;
+//!     Ok(Credentials::new(key).expires_in(Duration::from_secs(15 * 60)))
+//! });
+//! ```
+use std::future::Future;
+use std::pin::Pin;
+use std::time::{Duration, Instant};
+
+/// How long keys without an expiry are cached.
+pub const DEFAULT_TTL: Duration = Duration::from_secs(5 * 60);
+
+/// An API key, and how long it can be used.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Credentials {
+    pub api_key: String,
+    /// How long the key can be cached. Defaults to [DEFAULT_TTL].
+    pub expires_in: Option<Duration>,
+}
+
+impl Credentials {
+    pub fn new(api_key: impl Into<String>) -> Credentials {
+        Credentials {
+            api_key: api_key.into(),
+            expires_in: None,
+        }
+    }
+
+    pub fn expires_in(mut self, expires_in: Duration) -> Credentials {
+        self.expires_in = Some(expires_in);
+        self
+    }
+}
+
+/// Fetches the API key, like from Vault or a KMS.
+pub trait CredentialsProvider: Send + Sync + 'static {
+    fn credentials(&self) -> impl Future<Output = anyhow::Result<Credentials>> + Send;
+}
+
+impl<F, Fut> CredentialsProvider for F
+where
+    F: Fn() -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = anyhow::Result<Credentials>> + Send,
+{
+    fn credentials(&self) -> impl Future<Output = anyhow::Result<Credentials>> + Send {
+        self()
+    }
+}
+
+/// [CredentialsProvider] with a boxed future, so it can be stored in the [Client](crate::Client).
+trait DynCredentialsProvider: Send + Sync {
+    fn credentials(&self) -> Pin<Box<dyn Future<Output = anyhow::Result<Credentials>> + Send + '_>>;
+}
+
+impl<P: CredentialsProvider> DynCredentialsProvider for P {
+    fn credentials(&self) -> Pin<Box<dyn Future<Output = anyhow::Result<Credentials>> + Send + '_>> {
+        Box::pin(CredentialsProvider::credentials(self))
+    }
+}
+
+/// A [CredentialsProvider] with the last key it returned.
+pub(crate) struct CachedCredentials {
+    provider: Box<dyn DynCredentialsProvider>,
+    /// Locked while fetching, so concurrent requests wait for a single fetch.
+    /// Keys expiring too far in the future to be represented never expire.
+    cached: tokio::sync::Mutex<Option<(String, Option<Instant>)>>,
+}
+
+impl CachedCredentials {
+    pub(crate) fn new(provider: impl CredentialsProvider) -> CachedCredentials {
+        CachedCredentials {
+            provider: Box::new(provider),
+            cached: tokio::sync::Mutex::new(None),
+        }
+    }
+
+    /// The cached key, or a new one if it expired.
+    pub(crate) async fn api_key(&self) -> anyhow::Result<String> {
+        let mut cached = self.cached.lock().await;
+        if let Some((key, expires_at)) = &*cached {
+            if expires_at.is_none_or(|expires_at| Instant::now() < expires_at) {
+                return Ok(key.clone());
+            }
+        }
+        let credentials = self.provider.credentials().await?;
+        let expires_at = Instant::now().checked_add(credentials.expires_in.unwrap_or(DEFAULT_TTL));
+        *cached = Some((credentials.api_key.clone(), expires_at));
+        Ok(credentials.api_key)
+    }
+
+    /// Drop the cached key, if it is still `key`.
+    pub(crate) async fn invalidate(&self, key: &str) {
+        let mut cached = self.cached.lock().await;
+        if cached.as_ref().is_some_and(|(cached, _)| cached == key) {
+            *cached = None;
+        }
+    }
+}
@@ -0,0 +1,22 @@
+//! Helpers for deserializing responses from servers that only loosely follow the OpenAI API,
+//! such as Ollama, llama.cpp and vLLM.
+use serde::{Deserialize, Deserializer};
+
+/// Deserialize `null` as the default value. Combine with `#[serde(default)]` to also allow missing fields.
+pub(crate) fn null_default<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Default + Deserialize<'de>,
+{
+    Ok(Option::<T>::deserialize(deserializer)?.unwrap_or_default())
+}
+
+/// Deserialize an empty map as `None`, so a flattened map of extra parameters stays `None` when there are none.
+#[cfg(any(feature = "chat", feature = "completions", feature = "embeddings", feature = "images"))]
+pub(crate) fn empty_none<'de, D>(deserializer: D) -> Result<Option<serde_json::Map<String, serde_json::Value>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let map = serde_json::Map::deserialize(deserializer)?;
+    Ok(Some(map).filter(|map| !map.is_empty()))
+}
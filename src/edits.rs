@@ -15,7 +15,8 @@ use serde::{Deserialize, Serialize};
 ///     "Complete this sentence.".to_owned()
 /// );
 /// ```
-#[derive(Serialize, Debug, Clone)]
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[non_exhaustive]
 pub struct EditArguments {
     /// ID of the model to use. You can use the `text-davinci-edit-001` or `code-davinci-edit-001` model with this endpoint.
     pub model: String,
@@ -45,6 +46,10 @@ pub struct EditArguments {
     /// We generally recommend altering this or `temperature` but not both.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub top_p: Option<f32>,
+
+    /// Additional parameters sent in the body, for parameters not yet supported by this crate or specific to a provider.
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    pub extra: Option<serde_json::Map<String, serde_json::Value>>,
 }
 
 impl EditArguments {
@@ -52,10 +57,11 @@ impl EditArguments {
         EditArguments {
             model: model.as_ref().to_owned(),
             input: Some(input),
-            instruction: instruction,
+            instruction,
             n: None,
             temperature: None,
             top_p: None,
+            extra: None,
         }
     }
 }
@@ -85,30 +91,51 @@ impl EditArguments {
 /// ```
 /// It implements [Display](std::fmt::Display) as a shortcut to easily extract the content.
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[non_exhaustive]
 pub struct EditResponse {
-    pub created: u32,
+    pub created: u64,
     pub choices: Vec<Choice>,
     pub usage: Usage,
 }
 
+impl EditResponse {
+    /// When this was created, see [created](Self::created).
+    #[cfg(feature = "chrono")]
+    pub fn created_at(&self) -> chrono::DateTime<chrono::Utc> {
+        crate::timestamp(self.created)
+    }
+
+    /// The first choice, if there is one.
+    pub fn first_choice(&self) -> Option<&Choice> {
+        self.choices.first()
+    }
+
+    /// The text of the first choice, if there is one.
+    pub fn first_text(&self) -> Option<&str> {
+        self.first_choice().map(|c| c.text.as_str())
+    }
+}
+
 impl std::fmt::Display for EditResponse {
-    /// Automatically grab the first choice
+    /// Automatically grab the first choice, or nothing if there are no choices
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.choices[0].text)?;
+        write!(f, "{}", self.first_text().unwrap_or_default())?;
         Ok(())
     }
 }
 
 /// The completion choices of an edit response.
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[non_exhaustive]
 pub struct Choice {
     pub text: String,
     pub index: u32,
 }
 
 /// Infomration about the tokens used by [EditResponse].
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[non_exhaustive]
 pub struct Usage {
     pub prompt_tokens: u32,
     pub completion_tokens: u32,
@@ -1,5 +1,8 @@
 //! See <https://platform.openai.com/docs/api-reference/embeddings>.
-//! Use with [Client::create_embeddings](crate::Client::create_embeddings).
+//! Use with [Client::create_embeddings](crate::Client::create_embeddings),
+//! and store the embeddings in an [Index] to search them.
+
+use std::collections::HashMap;
 
 use serde::{Deserialize, Serialize};
 /// Request arguments for embeddings.
@@ -10,9 +13,11 @@ use serde::{Deserialize, Serialize};
 /// openai_rust::embeddings::EmbeddingsArguments::new(
 ///     "text-embedding-ada-002",
 ///     "The food was delicious and the waiter...".to_owned(),
-/// );
+/// )
+/// .user("user-1234");
 /// ```
-#[derive(Serialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
 pub struct EmbeddingsArguments {
     /// ID of the model to use. You can use the [List models](crate::Client::list_models) API to see all of your available models, or see our [Model overview](https://platform.openai.com/docs/models/overview) for descriptions of them.
     pub model: String,
@@ -21,6 +26,9 @@ pub struct EmbeddingsArguments {
     /// A unique identifier representing your end-user, which can help OpenAI to monitor and detect abuse. [Learn more](https://platform.openai.com/docs/guides/safety-best-practices/end-user-ids).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub user: Option<String>,
+    /// Additional parameters sent in the body, for parameters not yet supported by this crate or specific to a provider.
+    #[serde(flatten, skip_serializing_if = "Option::is_none", deserialize_with = "crate::de::empty_none")]
+    pub extra: Option<serde_json::Map<String, serde_json::Value>>,
 }
 
 impl EmbeddingsArguments {
@@ -29,28 +37,223 @@ impl EmbeddingsArguments {
             model: model.as_ref().to_owned(),
             input,
             user: None,
+            extra: None,
         }
     }
+
+    pub fn user(mut self, user: impl AsRef<str>) -> EmbeddingsArguments {
+        self.user = Some(user.as_ref().to_owned());
+        self
+    }
+
+    /// Add a parameter not yet supported by this crate to [extra](EmbeddingsArguments::extra).
+    pub fn extra(mut self, key: impl AsRef<str>, value: impl Into<serde_json::Value>) -> EmbeddingsArguments {
+        self.extra.get_or_insert_with(Default::default).insert(key.as_ref().to_owned(), value.into());
+        self
+    }
 }
 
 /// The response of an embeddings request.
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[non_exhaustive]
 pub struct EmbeddingsResponse {
     pub data: Vec<EmbeddingsData>,
+    #[serde(default, deserialize_with = "crate::de::null_default")]
     pub model: String,
-    pub usage: Usage,
+    pub usage: Option<Usage>,
 }
 
 /// The data from an embeddings request.
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[non_exhaustive]
 pub struct EmbeddingsData {
     pub embedding: Vec<f32>,
+    #[serde(default, deserialize_with = "crate::de::null_default")]
     pub index: usize,
 }
 
 /// Token usage information for an [EmbeddingsResponse].
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[non_exhaustive]
+#[serde(default)]
 pub struct Usage {
     pub prompt_tokens: u32,
     pub total_tokens: u32,
 }
+
+/// An in-memory index of embeddings, searched by cosine similarity.
+///
+/// Every search compares the query with all vectors, which is fast enough for tens of thousands of vectors.
+/// There is no approximate search like HNSW, use a vector database for larger collections.
+/// The index can be saved to disk as JSON and loaded again.
+///
+/// ```
+/// use openai_rust::embeddings::Index;
+/// let mut index = Index::new();
+/// index.add("cat", vec![1.0, 0.0]).unwrap();
+/// index.add("dog", vec![0.8, 0.6]).unwrap();
+/// index.add("car", vec![0.0, 1.0]).unwrap();
+/// let results = index.search(&[1.0, 0.1], 2).unwrap();
+/// assert_eq!(results[0].id, "cat");
+/// assert_eq!(results[1].id, "dog");
+/// assert!(index.add("bike", vec![1.0]).is_err());
+/// assert!(index.add("bike", vec![f32::NAN, 1.0]).is_err());
+/// assert!(index.search(&[1.0], 2).is_err());
+///
+/// let mut index = Index::from_json(&index.to_json()).unwrap();
+/// assert_eq!(index.len(), 3);
+/// assert!(index.remove("cat"));
+/// assert_eq!(index.get("car"), Some(&[0.0, 1.0][..]));
+/// assert!(Index::from_json(r#"{"entries": [{"id": "a", "vector": [1.0]}, {"id": "b", "vector": [1.0, 0.0]}]}"#).is_err());
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(try_from = "Entries")]
+pub struct Index {
+    entries: Vec<Entry>,
+    /// The position of every ID in `entries`.
+    #[serde(skip)]
+    slots: HashMap<String, usize>,
+}
+
+/// A saved [Index], checked like [Index::add] when loaded.
+#[derive(Deserialize)]
+struct Entries {
+    entries: Vec<Entry>,
+}
+
+impl TryFrom<Entries> for Index {
+    type Error = anyhow::Error;
+
+    fn try_from(saved: Entries) -> anyhow::Result<Index> {
+        let mut index = Index::new();
+        for entry in saved.entries {
+            index.add(entry.id, entry.vector)?;
+        }
+        Ok(index)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+struct Entry {
+    id: String,
+    vector: Vec<f32>,
+}
+
+/// A vector found by [Index::search].
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchResult {
+    pub id: String,
+    /// The cosine similarity with the query, between -1 and 1.
+    pub score: f32,
+}
+
+impl Index {
+    pub fn new() -> Index {
+        Index::default()
+    }
+
+    /// Add a vector, replacing the vector with the same ID.
+    ///
+    /// Fails if the vector has another dimension than the vectors already in the index,
+    /// or values that are NaN or infinite, which can't be saved as JSON.
+    pub fn add(&mut self, id: impl Into<String>, vector: Vec<f32>) -> anyhow::Result<()> {
+        self.check_dimensions("vector", &vector)?;
+        if vector.iter().any(|x| !x.is_finite()) {
+            return Err(anyhow::anyhow!("the vector has values that are NaN or infinite"));
+        }
+        let id = id.into();
+        match self.slots.get(&id) {
+            Some(&slot) => self.entries[slot].vector = vector,
+            None => {
+                self.slots.insert(id.clone(), self.entries.len());
+                self.entries.push(Entry { id, vector });
+            }
+        }
+        Ok(())
+    }
+
+    /// Remove a vector, returning whether it was in the index.
+    pub fn remove(&mut self, id: &str) -> bool {
+        let Some(slot) = self.slots.remove(id) else {
+            return false;
+        };
+        self.entries.swap_remove(slot);
+        if let Some(moved) = self.entries.get(slot) {
+            self.slots.insert(moved.id.clone(), slot);
+        }
+        true
+    }
+
+    /// The vector with an ID.
+    pub fn get(&self, id: &str) -> Option<&[f32]> {
+        self.slots.get(id).map(|&slot| self.entries[slot].vector.as_slice())
+    }
+
+    /// The `k` vectors most similar to the query, most similar first.
+    ///
+    /// Fails if the query has another dimension than the vectors in the index.
+    pub fn search(&self, query: &[f32], k: usize) -> anyhow::Result<Vec<SearchResult>> {
+        self.check_dimensions("query", query)?;
+        let mut results: Vec<SearchResult> = self
+            .entries
+            .iter()
+            .map(|entry| SearchResult {
+                id: entry.id.clone(),
+                score: cosine_similarity(query, &entry.vector),
+            })
+            .collect();
+        results.sort_by(|a, b| b.score.total_cmp(&a.score));
+        results.truncate(k);
+        Ok(results)
+    }
+
+    fn check_dimensions(&self, name: &str, vector: &[f32]) -> anyhow::Result<()> {
+        match self.entries.first().filter(|entry| entry.vector.len() != vector.len()) {
+            Some(entry) => Err(anyhow::anyhow!(
+                "the {} has {} dimensions, the index has {}",
+                name,
+                vector.len(),
+                entry.vector.len()
+            )),
+            None => Ok(()),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+
+    pub fn from_json(json: &str) -> serde_json::Result<Index> {
+        serde_json::from_str(json)
+    }
+
+    /// Save the index to a file, to [load](Index::load) it again later.
+    pub async fn save(&self, path: impl AsRef<std::path::Path>) -> std::io::Result<()> {
+        tokio::fs::write(path, self.to_json()).await
+    }
+
+    pub async fn load(path: impl AsRef<std::path::Path>) -> std::io::Result<Index> {
+        let json = tokio::fs::read_to_string(path).await?;
+        Ok(Index::from_json(&json)?)
+    }
+}
+
+/// The cosine of the angle between two vectors, 0 if either is zero.
+pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    let dot: f32 = a.iter().zip(b).map(|(a, b)| a * b).sum();
+    let norm = |v: &[f32]| v.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norms = norm(a) * norm(b);
+    if norms == 0.0 {
+        0.0
+    } else {
+        dot / norms
+    }
+}
@@ -0,0 +1,105 @@
+//! Responses together with their HTTP metadata.
+//! Returned by [Client::create_chat_with_meta](crate::Client::create_chat_with_meta) and the other `_with_meta` methods.
+//!
+//! ```
+//! use reqwest::header::{HeaderMap, HeaderValue};
+//! use openai_rust::envelope::RateLimitInfo;
+//! let mut headers = HeaderMap::new();
+//! headers.insert("x-ratelimit-remaining-tokens", HeaderValue::from_static("149984"));
+//! headers.insert("x-ratelimit-reset-tokens", HeaderValue::from_static("6m0s"));
+//! let rate_limit = RateLimitInfo::from_headers(&headers);
+//! assert_eq!(rate_limit.remaining_tokens, Some(149984));
+//! assert_eq!(rate_limit.reset_tokens, Some(std::time::Duration::from_secs(360)));
+//! ```
+use std::time::Duration;
+
+use reqwest::header::HeaderMap;
+use reqwest::StatusCode;
+
+/// A parsed response body, with the status and headers of the response.
+#[derive(Debug, Clone)]
+pub struct ResponseEnvelope<T> {
+    pub data: T,
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+}
+
+impl<T> ResponseEnvelope<T> {
+    /// The ID of the request from the `x-request-id` header, to include when contacting support.
+    pub fn request_id(&self) -> Option<&str> {
+        self.headers.get("x-request-id").and_then(|v| v.to_str().ok())
+    }
+
+    /// How long the API took to process the request, from the `openai-processing-ms` header.
+    pub fn processing_time(&self) -> Option<Duration> {
+        header(&self.headers, "openai-processing-ms").map(Duration::from_millis)
+    }
+
+    /// The rate limits of the account after this request.
+    pub fn rate_limit(&self) -> RateLimitInfo {
+        RateLimitInfo::from_headers(&self.headers)
+    }
+
+    /// Drop the metadata.
+    pub fn into_inner(self) -> T {
+        self.data
+    }
+}
+
+/// The rate limit state from the `x-ratelimit-*` headers.
+///
+/// See <https://platform.openai.com/docs/guides/rate-limits/rate-limits-in-headers>.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RateLimitInfo {
+    pub limit_requests: Option<u64>,
+    pub limit_tokens: Option<u64>,
+    pub remaining_requests: Option<u64>,
+    pub remaining_tokens: Option<u64>,
+    /// How long until the request limit is reset.
+    pub reset_requests: Option<Duration>,
+    /// How long until the token limit is reset.
+    pub reset_tokens: Option<Duration>,
+}
+
+impl RateLimitInfo {
+    pub fn from_headers(headers: &HeaderMap) -> RateLimitInfo {
+        let reset = |name| headers.get(name).and_then(|v| v.to_str().ok()).and_then(parse_duration);
+        RateLimitInfo {
+            limit_requests: header(headers, "x-ratelimit-limit-requests"),
+            limit_tokens: header(headers, "x-ratelimit-limit-tokens"),
+            remaining_requests: header(headers, "x-ratelimit-remaining-requests"),
+            remaining_tokens: header(headers, "x-ratelimit-remaining-tokens"),
+            reset_requests: reset("x-ratelimit-reset-requests"),
+            reset_tokens: reset("x-ratelimit-reset-tokens"),
+        }
+    }
+}
+
+fn header(headers: &HeaderMap, name: &str) -> Option<u64> {
+    headers.get(name)?.to_str().ok()?.parse().ok()
+}
+
+/// Parse durations like `1s`, `6m0s`, `20ms` or `1h2m3.5s`.
+fn parse_duration(text: &str) -> Option<Duration> {
+    let mut total = 0.0;
+    let mut rest = text.trim();
+    if rest.is_empty() {
+        return None;
+    }
+    while !rest.is_empty() {
+        let number_len = rest.find(|c: char| !c.is_ascii_digit() && c != '.')?;
+        let number: f64 = rest[..number_len].parse().ok()?;
+        rest = &rest[number_len..];
+        let unit_len = rest.find(|c: char| c.is_ascii_digit()).unwrap_or(rest.len());
+        total += number
+            * match &rest[..unit_len] {
+                "h" => 3600.0,
+                "m" => 60.0,
+                "s" => 1.0,
+                "ms" => 0.001,
+                _ => return None,
+            };
+        rest = &rest[unit_len..];
+    }
+    Duration::try_from_secs_f64(total).ok()
+}
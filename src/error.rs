@@ -0,0 +1,353 @@
+//! Errors returned by the [Client](crate::Client), classified so applications can decide whether to retry
+//! or fall back without parsing error messages.
+//!
+//! Client methods fail with an [Error]. When the API responds with an error, it contains an [ApiError].
+//!
+//! ```no_run
+//! # tokio_test::block_on(async {
+//! # let client = openai_rust::Client::new("");
+//! # let args = openai_rust::chat::ChatArguments::new("gpt-4o", vec![]);
+//! use openai_rust::error::{Error, ErrorKind};
+//! loop {
+//!     match client.create_chat(args.clone()).await {
+//!         Ok(completion) => break println!("{}", completion),
+//!         Err(Error::RateLimited { retry_after, .. }) => {
+//!             tokio::time::sleep(retry_after.unwrap_or(std::time::Duration::from_secs(1))).await;
+//!         }
+//!         Err(e) if e.is_retryable() => tokio::time::sleep(std::time::Duration::from_secs(1)).await,
+//!         Err(Error::Api(e)) if e.kind() == ErrorKind::ContextLengthExceeded => break println!("the conversation is too long"),
+//!         Err(Error::Api(e)) if e.kind() == ErrorKind::InsufficientQuota => break println!("out of credits"),
+//!         Err(e) => break println!("error: {}", e),
+//!     }
+//! }
+//! # })
+//! ```
+use std::time::Duration;
+
+use reqwest::header::HeaderMap;
+use reqwest::StatusCode;
+use serde::Deserialize;
+
+/// What went wrong, from the code, type and status of an [ApiError].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// The account ran out of credits or reached its monthly limit. Retrying does not help.
+    InsufficientQuota,
+    /// Too many requests or tokens in a short time. Retry after [ApiError::retry_after].
+    RateLimitExceeded,
+    /// The messages and the maximum amount of tokens do not fit in the context of the model.
+    ContextLengthExceeded,
+    /// The input or output was blocked by a content filter, like the one of Azure OpenAI.
+    ContentFilter,
+    /// The API key is missing, invalid or revoked.
+    InvalidApiKey,
+    /// The API failed or is overloaded.
+    Server,
+    Other,
+}
+
+/// An error response of the API.
+///
+/// ```
+/// use reqwest::header::{HeaderMap, HeaderValue};
+/// use openai_rust::error::{ApiError, ErrorKind};
+/// let mut headers = HeaderMap::new();
+/// headers.insert("retry-after", HeaderValue::from_static("20"));
+/// let body = r#"{"error": {"message": "Rate limit reached for gpt-4o", "type": "requests", "code": "rate_limit_exceeded"}}"#;
+/// let error = ApiError::new(reqwest::StatusCode::TOO_MANY_REQUESTS, headers, body);
+/// assert_eq!(error.kind(), ErrorKind::RateLimitExceeded);
+/// assert!(error.is_retryable());
+/// assert_eq!(error.retry_after(), Some(std::time::Duration::from_secs(20)));
+/// assert_eq!(error.to_string(), "Rate limit reached for gpt-4o (429 Too Many Requests)");
+/// ```
+#[derive(Debug, Clone)]
+pub struct ApiError {
+    pub status: StatusCode,
+    pub message: String,
+    /// The type of error, like `invalid_request_error`.
+    pub error_type: Option<String>,
+    /// A code for the error, like `context_length_exceeded`.
+    pub code: Option<String>,
+    /// The parameter of the request that caused the error.
+    pub param: Option<String>,
+    pub headers: HeaderMap,
+    /// The body of the response, for errors of providers that don't follow the format of OpenAI.
+    pub body: String,
+}
+
+#[derive(Deserialize)]
+struct ErrorBody {
+    error: ErrorObject,
+}
+
+#[derive(Deserialize)]
+struct ErrorObject {
+    message: Option<String>,
+    #[serde(rename = "type")]
+    error_type: Option<String>,
+    #[serde(default, deserialize_with = "string_or_number")]
+    code: Option<String>,
+    param: Option<String>,
+}
+
+/// Some providers send the code as a number.
+fn string_or_number<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<Option<String>, D::Error> {
+    Ok(match Option::<serde_json::Value>::deserialize(deserializer)? {
+        Some(serde_json::Value::String(code)) => Some(code),
+        Some(serde_json::Value::Number(code)) => Some(code.to_string()),
+        _ => None,
+    })
+}
+
+impl ApiError {
+    /// Parse an error response. Bodies that are not in the format of OpenAI become the message.
+    pub fn new(status: StatusCode, headers: HeaderMap, body: impl Into<String>) -> ApiError {
+        let body = body.into();
+        let error = serde_json::from_str::<ErrorBody>(&body).ok().map(|body| body.error);
+        let (message, error_type, code, param) = match error {
+            Some(error) => (error.message, error.error_type, error.code, error.param),
+            None => (None, None, None, None),
+        };
+        ApiError {
+            status,
+            message: message.unwrap_or_else(|| body.trim().to_owned()),
+            error_type,
+            code,
+            param,
+            headers,
+            body,
+        }
+    }
+
+    pub fn kind(&self) -> ErrorKind {
+        let code = self.code.as_deref().unwrap_or_default();
+        match code {
+            "insufficient_quota" => return ErrorKind::InsufficientQuota,
+            "rate_limit_exceeded" => return ErrorKind::RateLimitExceeded,
+            "context_length_exceeded" | "string_above_max_length" => return ErrorKind::ContextLengthExceeded,
+            "content_filter" | "content_policy_violation" => return ErrorKind::ContentFilter,
+            "invalid_api_key" => return ErrorKind::InvalidApiKey,
+            _ => {}
+        }
+        if self.error_type.as_deref() == Some("insufficient_quota") {
+            return ErrorKind::InsufficientQuota;
+        }
+        if self.message.contains("maximum context length") {
+            return ErrorKind::ContextLengthExceeded;
+        }
+        match self.status {
+            StatusCode::TOO_MANY_REQUESTS => ErrorKind::RateLimitExceeded,
+            StatusCode::UNAUTHORIZED => ErrorKind::InvalidApiKey,
+            status if status.is_server_error() => ErrorKind::Server,
+            _ if self.error_type.as_deref() == Some("server_error") => ErrorKind::Server,
+            _ => ErrorKind::Other,
+        }
+    }
+
+    /// Whether the same request may succeed later: rate limits, server errors and timeouts.
+    pub fn is_retryable(&self) -> bool {
+        matches!(self.kind(), ErrorKind::RateLimitExceeded | ErrorKind::Server)
+            || self.status == StatusCode::REQUEST_TIMEOUT
+            || self.status == StatusCode::CONFLICT
+    }
+
+    /// How long to wait before retrying, from the `retry-after-ms` and `retry-after` headers,
+    /// or from the `x-ratelimit-reset-*` headers when the rate limit was exceeded.
+    pub fn retry_after(&self) -> Option<Duration> {
+        let header = |name| self.headers.get(name).and_then(|v| v.to_str().ok());
+        // Negative, infinite and huge values don't fit in a Duration and are ignored
+        let seconds = |secs: f64| Duration::try_from_secs_f64(secs).ok();
+        if let Some(ms) = header("retry-after-ms").and_then(|ms| seconds(ms.parse::<f64>().ok()? / 1000.0)) {
+            return Some(ms);
+        }
+        if let Some(secs) = header("retry-after").and_then(|secs| seconds(secs.parse().ok()?)) {
+            return Some(secs);
+        }
+        if self.kind() != ErrorKind::RateLimitExceeded {
+            return None;
+        }
+        let limits = crate::envelope::RateLimitInfo::from_headers(&self.headers);
+        let exhausted = |remaining: Option<u64>, reset: Option<Duration>| reset.filter(|_| remaining == Some(0));
+        exhausted(limits.remaining_requests, limits.reset_requests)
+            .max(exhausted(limits.remaining_tokens, limits.reset_tokens))
+            .or(limits.reset_requests.max(limits.reset_tokens))
+    }
+
+    /// The ID of the request from the `x-request-id` header, to include when contacting support.
+    pub fn request_id(&self) -> Option<&str> {
+        self.headers.get("x-request-id").and_then(|v| v.to_str().ok())
+    }
+}
+
+impl std::fmt::Display for ApiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({})", self.message, self.status)
+    }
+}
+
+impl std::error::Error for ApiError {}
+
+/// The result of the methods of [Client](crate::Client).
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// An error of a [Client](crate::Client).
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// The API responded with an error.
+    Api(Box<ApiError>),
+    /// The API rejected the request because too many requests or tokens were sent.
+    RateLimited {
+        /// How long the API asked to wait, see [ApiError::retry_after].
+        retry_after: Option<Duration>,
+        error: Box<ApiError>,
+    },
+    /// The request could not be sent, or the response could not be received.
+    Network(reqwest::Error),
+    /// The response is not in the expected format.
+    Deserialization(serde_json::Error),
+    /// Any other error, like an exceeded [budget](crate::budget), a [blocked](crate::rate_limit::Blocked) model,
+    /// an open [circuit breaker](crate::circuit_breaker) or a [shutdown](crate::shutdown). Use [Error::downcast_ref] to check for a specific error.
+    Other(anyhow::Error),
+}
+
+impl Error {
+    /// Whether the same request may succeed later: a retryable [ApiError], or a request that timed out or could not connect.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Error::Api(error) => error.is_retryable(),
+            Error::RateLimited { .. } => true,
+            Error::Network(error) => error.is_timeout() || error.is_connect() || error.is_request(),
+            Error::Deserialization(_) | Error::Other(_) => false,
+        }
+    }
+
+    /// How long the API asked to wait before retrying, see [ApiError::retry_after].
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            Error::Api(error) => error.retry_after(),
+            Error::RateLimited { retry_after, .. } => *retry_after,
+            _ => None,
+        }
+    }
+
+    /// The error response of the API, if the API responded with an error.
+    pub fn api_error(&self) -> Option<&ApiError> {
+        match self {
+            Error::Api(error) | Error::RateLimited { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+
+    /// The underlying error if it is of type `E`, like [ApiError] or [ShuttingDown](crate::shutdown::ShuttingDown).
+    pub fn downcast_ref<E: std::error::Error + Send + Sync + 'static>(&self) -> Option<&E> {
+        let error: &(dyn std::error::Error + 'static) = match self {
+            Error::Api(error) | Error::RateLimited { error, .. } => &**error,
+            Error::Network(error) => error,
+            Error::Deserialization(error) => error,
+            Error::Other(error) => return error.downcast_ref(),
+        };
+        error.downcast_ref()
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Api(error) | Error::RateLimited { error, .. } => std::fmt::Display::fmt(error, f),
+            Error::Network(error) => std::fmt::Display::fmt(error, f),
+            Error::Deserialization(error) => std::fmt::Display::fmt(error, f),
+            Error::Other(error) => std::fmt::Display::fmt(error, f),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Api(_) | Error::RateLimited { .. } => None,
+            Error::Network(error) => error.source(),
+            Error::Deserialization(error) => error.source(),
+            Error::Other(error) => error.source(),
+        }
+    }
+}
+
+impl From<ApiError> for Error {
+    fn from(error: ApiError) -> Error {
+        match error.kind() {
+            ErrorKind::RateLimitExceeded => Error::RateLimited {
+                retry_after: error.retry_after(),
+                error: Box::new(error),
+            },
+            _ => Error::Api(Box::new(error)),
+        }
+    }
+}
+
+impl From<reqwest::Error> for Error {
+    fn from(error: reqwest::Error) -> Error {
+        Error::Network(error)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(error: serde_json::Error) -> Error {
+        Error::Deserialization(error)
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(error: std::io::Error) -> Error {
+        Error::Other(error.into())
+    }
+}
+
+/// Classifies the errors of the plumbing of the client, which uses [anyhow].
+impl From<anyhow::Error> for Error {
+    fn from(error: anyhow::Error) -> Error {
+        let error = match error.downcast::<Error>() {
+            Ok(error) => return error,
+            Err(error) => error,
+        };
+        let error = match error.downcast::<ApiError>() {
+            Ok(error) => return error.into(),
+            Err(error) => error,
+        };
+        let error = match error.downcast::<reqwest::Error>() {
+            Ok(error) => return Error::Network(error),
+            Err(error) => error,
+        };
+        match error.downcast::<serde_json::Error>() {
+            Ok(error) => Error::Deserialization(error),
+            Err(error) => Error::Other(error),
+        }
+    }
+}
+
+/// Whether a request that failed with this error may succeed when retried, see [Error::is_retryable].
+///
+/// For errors of a [Client](crate::Client) that were converted to an [anyhow::Error].
+pub fn is_retryable(error: &anyhow::Error) -> bool {
+    if let Some(error) = error.downcast_ref::<Error>() {
+        return error.is_retryable();
+    }
+    if let Some(error) = error.downcast_ref::<ApiError>() {
+        return error.is_retryable();
+    }
+    if let Some(error) = error.downcast_ref::<reqwest::Error>() {
+        return error.is_timeout() || error.is_connect() || error.is_request();
+    }
+    false
+}
+
+/// How long the API asked to wait before retrying, see [ApiError::retry_after].
+///
+/// For errors of a [Client](crate::Client) that were converted to an [anyhow::Error].
+pub fn retry_after(error: &anyhow::Error) -> Option<Duration> {
+    if let Some(error) = error.downcast_ref::<Error>() {
+        return error.retry_after();
+    }
+    error.downcast_ref::<ApiError>()?.retry_after()
+}
@@ -0,0 +1,150 @@
+//! Failover to other endpoints when the API is down.
+//! Attach with [Client::with_failover](crate::Client::with_failover).
+//!
+//! Requests are sent to the base URL of the client first, and then to each fallback [Endpoint] in order,
+//! until one of them does not fail with a connection error, a timeout or a server error (5xx).
+//!
+//! The health of every endpoint is tracked with a [CircuitBreaker]. Endpoints that failed repeatedly
+//! are skipped for a cooldown, so requests do not keep waiting on an endpoint that is down.
+//!
+//! Streamed uploads cannot be sent twice, so they are only sent to the base URL of the client.
+//! Requests to an endpoint overridden with [Client::with_endpoint_url](crate::Client::with_endpoint_url) are not failed over either.
+//!
+//! ```
+//! use openai_rust::failover::{Endpoint, Failover};
+//! use openai_rust::provider::Provider;
+//! # let (azure_key, openai_key) = ("", "");
+//! let client = openai_rust::Client::new(azure_key)
+//!     .with_provider(Provider::Azure {
+//!         resource: "my-resource".to_owned(),
+//!         deployment: "gpt-4o".to_owned(),
+//!         api_version: "2024-06-01".to_owned(),
+//!     })
+//!     .unwrap()
+//!     .with_failover(Failover::new().fallback(Endpoint::new(Provider::OpenAI, openai_key).unwrap()));
+//! assert!(client.failover().unwrap().is_healthy(1));
+//! ```
+use std::time::Duration;
+
+use reqwest::Url;
+
+use crate::circuit_breaker::CircuitBreaker;
+use crate::provider::Provider;
+
+/// Another server to send requests to.
+#[derive(Debug, Clone)]
+pub struct Endpoint {
+    pub provider: Provider,
+    pub base_url: Url,
+    pub api_key: String,
+}
+
+impl Endpoint {
+    /// An endpoint at the base URL of the provider.
+    ///
+    /// Fails if the provider has no valid base URL, see [Provider::base_url].
+    pub fn new(provider: Provider, api_key: &str) -> anyhow::Result<Endpoint> {
+        Ok(Endpoint {
+            base_url: provider.base_url()?,
+            provider,
+            api_key: api_key.to_owned(),
+        })
+    }
+
+    pub fn base_url(mut self, base_url: Url) -> Endpoint {
+        self.base_url = base_url;
+        self
+    }
+}
+
+/// An ordered list of fallback [Endpoint]s, and the health of each endpoint.
+#[derive(Debug)]
+pub struct Failover {
+    fallbacks: Vec<Endpoint>,
+    threshold: u32,
+    cooldown: Duration,
+    /// The health of the base URL of the client, followed by every fallback.
+    health: Vec<CircuitBreaker>,
+}
+
+impl Default for Failover {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Failover {
+    /// Create a failover without fallbacks, which marks an endpoint unhealthy for 30 seconds after 3 consecutive failures.
+    pub fn new() -> Failover {
+        let (threshold, cooldown) = (3, Duration::from_secs(30));
+        Failover {
+            fallbacks: vec![],
+            threshold,
+            cooldown,
+            health: vec![CircuitBreaker::new(threshold, cooldown)],
+        }
+    }
+
+    /// Add an endpoint to fail over to, after the previous ones.
+    pub fn fallback(mut self, endpoint: Endpoint) -> Failover {
+        self.fallbacks.push(endpoint);
+        self.health.push(CircuitBreaker::new(self.threshold, self.cooldown));
+        self
+    }
+
+    /// Mark an endpoint unhealthy after `threshold` consecutive failures, for `cooldown`.
+    pub fn unhealthy_after(mut self, threshold: u32, cooldown: Duration) -> Failover {
+        self.threshold = threshold;
+        self.cooldown = cooldown;
+        self.health = (0..=self.fallbacks.len())
+            .map(|_| CircuitBreaker::new(threshold, cooldown))
+            .collect();
+        self
+    }
+
+    pub fn fallbacks(&self) -> &[Endpoint] {
+        &self.fallbacks
+    }
+
+    /// Whether requests are sent to an endpoint.
+    /// Endpoint `0` is the base URL of the client, followed by the fallbacks.
+    ///
+    /// Panics if there is no such endpoint.
+    pub fn is_healthy(&self, endpoint: usize) -> bool {
+        !self.health[endpoint].is_open()
+    }
+
+    /// The health of an endpoint, see [Failover::is_healthy].
+    pub fn health(&self, endpoint: usize) -> &CircuitBreaker {
+        &self.health[endpoint]
+    }
+
+    /// Move the URL of a request from the base URL of the client to a fallback.
+    ///
+    /// Returns `None` if the URL is not relative to the base URL, like when it was overridden for an endpoint.
+    pub(crate) fn rebase(url: &Url, base_url: &Url, provider: &Provider, fallback: &Endpoint) -> Option<Url> {
+        if url.origin() != base_url.origin() {
+            return None;
+        }
+        let path = url.path().strip_prefix(base_url.path().trim_end_matches('/'))?;
+        if !path.is_empty() && !path.starts_with('/') {
+            return None;
+        }
+        let mut rebased = fallback.base_url.clone();
+        let base = rebased.path().trim_end_matches('/').to_owned();
+        rebased.set_path(&(base + path));
+
+        // Swap the query parameters of the provider for those of the fallback
+        let provider_query: Vec<_> = provider.query().into_iter().map(|(key, _)| key).collect();
+        let query: Vec<(String, String)> = url
+            .query_pairs()
+            .filter(|(key, _)| !provider_query.contains(&key.as_ref()))
+            .map(|(key, value)| (key.into_owned(), value.into_owned()))
+            .chain(fallback.provider.query().into_iter().map(|(key, value)| (key.to_owned(), value)))
+            .collect();
+        if !query.is_empty() {
+            rebased.query_pairs_mut().extend_pairs(query);
+        }
+        Some(rebased)
+    }
+}
@@ -0,0 +1,91 @@
+//! Few-shot prompts: a system prompt followed by examples of the expected answers.
+//!
+//! The examples are sent as alternating user and assistant messages, followed by the actual input.
+//! With a token budget, examples are dropped from the end until the prompt fits.
+//! Tokens are [estimated](crate::tokens).
+//!
+//! ```
+//! use openai_rust::few_shot::FewShot;
+//! let messages = FewShot::new("Translate English to French.")
+//!     .example("Hello", "Bonjour")
+//!     .example("Goodbye", "Au revoir")
+//!     .build("Thank you");
+//! assert_eq!(messages.len(), 6);
+//! assert_eq!(messages[2].role, "assistant");
+//! assert_eq!(messages[5].content, "Thank you");
+//!
+//! let messages = FewShot::new("Translate English to French.")
+//!     .example("Hello", "Bonjour")
+//!     .example("Goodbye", "Au revoir")
+//!     .max_tokens(35)
+//!     .build("Thank you");
+//! assert_eq!(messages.len(), 4);
+//! ```
+use crate::chat::Message;
+use crate::tokens::estimate_message_tokens;
+
+/// Builds the messages of a few-shot prompt.
+#[derive(Debug, Clone, Default)]
+pub struct FewShot {
+    system: Option<String>,
+    examples: Vec<(String, String)>,
+    max_tokens: Option<usize>,
+}
+
+impl FewShot {
+    /// Start a prompt with a system message.
+    pub fn new(system: impl AsRef<str>) -> FewShot {
+        FewShot {
+            system: Some(system.as_ref().to_owned()),
+            ..Default::default()
+        }
+    }
+
+    /// Start a prompt without a system message.
+    pub fn without_system() -> FewShot {
+        FewShot::default()
+    }
+
+    /// Add an example of an input and the expected answer.
+    pub fn example(mut self, input: impl AsRef<str>, output: impl AsRef<str>) -> FewShot {
+        self.examples.push((input.as_ref().to_owned(), output.as_ref().to_owned()));
+        self
+    }
+
+    /// Drop examples from the end until the prompt takes up at most `max_tokens`.
+    ///
+    /// The system message and the input are never dropped, even if they exceed the budget on their own.
+    pub fn max_tokens(mut self, max_tokens: usize) -> FewShot {
+        self.max_tokens = Some(max_tokens);
+        self
+    }
+
+    /// The messages of the prompt, ending with the actual `input` as a user message.
+    pub fn build(&self, input: impl AsRef<str>) -> Vec<Message> {
+        let mut examples = self.examples.len();
+        loop {
+            let messages = self.messages(examples, input.as_ref());
+            let fits = self
+                .max_tokens
+                .is_none_or(|max_tokens| estimate_message_tokens(&messages) <= max_tokens);
+            if fits || examples == 0 {
+                return messages;
+            }
+            examples -= 1;
+        }
+    }
+
+    /// The messages of the prompt with the first `examples` examples.
+    fn messages(&self, examples: usize, input: &str) -> Vec<Message> {
+        let mut messages = vec![];
+        if let Some(system) = &self.system {
+            messages.push(Message::system(system));
+        }
+        for (example_input, example_output) in &self.examples[..examples] {
+            messages.push(Message::user(example_input));
+            messages.push(Message::assistant(example_output));
+        }
+        messages.push(Message::user(input));
+        messages
+    }
+}
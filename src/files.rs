@@ -0,0 +1,188 @@
+//! Files sent to the API, like the audio given to [Client::create_transcription](crate::Client::create_transcription).
+//!
+//! An [Upload] streams its content into the request, so large files don't have to be read into memory first.
+//! Files uploaded with [Client::upload_file](crate::Client::upload_file) are managed with
+//! [Client::list_files](crate::Client::list_files), [Client::retrieve_file](crate::Client::retrieve_file),
+//! [Client::retrieve_file_content](crate::Client::retrieve_file_content) and [Client::delete_file](crate::Client::delete_file).
+//!
+//! ```no_run
+//! # tokio_test::block_on(async {
+//! use openai_rust::files::Upload;
+//! // from disk
+//! let upload = Upload::file("recording.mp3").await.unwrap();
+//! // from anything implementing AsyncRead, like a pipe or socket
+//! let socket = tokio::net::TcpStream::connect("127.0.0.1:8080").await.unwrap();
+//! let upload = Upload::reader(socket, "recording.mp3");
+//! // from memory
+//! let upload = Upload::bytes(vec![], "recording.mp3");
+//! # })
+//! ```
+use bytes::Bytes;
+use std::path::Path;
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+/// The size of the chunks read from an [AsyncRead].
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// The content and name of a file to upload.
+pub struct Upload {
+    filename: String,
+    body: reqwest::Body,
+    length: Option<u64>,
+}
+
+impl std::fmt::Debug for Upload {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Upload")
+            .field("filename", &self.filename)
+            .field("length", &self.length)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Upload {
+    /// Upload a file that is already in memory.
+    pub fn bytes(bytes: impl Into<Vec<u8>>, filename: impl AsRef<str>) -> Upload {
+        let bytes = bytes.into();
+        Upload {
+            filename: filename.as_ref().to_owned(),
+            length: Some(bytes.len() as u64),
+            body: bytes.into(),
+        }
+    }
+
+    /// Upload the content of a reader, which is read in chunks while the request is sent.
+    ///
+    /// The length is unknown, so the request is sent with chunked transfer encoding
+    /// unless it is given with [Upload::with_length].
+    pub fn reader<R: AsyncRead + Send + Sync + Unpin + 'static>(reader: R, filename: impl AsRef<str>) -> Upload {
+        let stream = futures_util::stream::try_unfold(reader, |mut reader| async move {
+            let mut chunk = Vec::with_capacity(CHUNK_SIZE);
+            let read = (&mut reader).take(CHUNK_SIZE as u64).read_to_end(&mut chunk).await?;
+            if read == 0 {
+                Ok::<_, std::io::Error>(None)
+            } else {
+                Ok(Some((Bytes::from(chunk), reader)))
+            }
+        });
+        Upload::stream(stream, filename)
+    }
+
+    /// Upload the chunks of a stream.
+    ///
+    /// The length is unknown, so the request is sent with chunked transfer encoding
+    /// unless it is given with [Upload::with_length].
+    pub fn stream<S>(stream: S, filename: impl AsRef<str>) -> Upload
+    where
+        S: futures_core::TryStream + Send + Sync + 'static,
+        S::Error: Into<Box<dyn std::error::Error + Send + Sync>>,
+        Bytes: From<S::Ok>,
+    {
+        Upload {
+            filename: filename.as_ref().to_owned(),
+            body: reqwest::Body::wrap_stream(stream),
+            length: None,
+        }
+    }
+
+    /// Upload a file from disk, using its file name.
+    pub async fn file(path: impl AsRef<Path>) -> std::io::Result<Upload> {
+        let path = path.as_ref();
+        let file = tokio::fs::File::open(path).await?;
+        let length = file.metadata().await?.len();
+        let filename = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        Ok(Upload::reader(file, filename).with_length(length))
+    }
+
+    /// The length of the content in bytes, if known.
+    pub fn with_length(mut self, length: u64) -> Upload {
+        self.length = Some(length);
+        self
+    }
+
+    pub fn filename(&self) -> &str {
+        &self.filename
+    }
+
+    pub(crate) fn into_part(self) -> reqwest::multipart::Part {
+        use reqwest::multipart::Part;
+        match self.length {
+            Some(length) => Part::stream_with_length(self.body, length),
+            None => Part::stream(self.body),
+        }
+        .file_name(self.filename)
+    }
+}
+
+/// Add the extra parameters of arguments sent as a form, for parameters not yet supported by this crate.
+/// Strings are sent as is, other values as JSON.
+#[cfg(any(feature = "audio", feature = "images"))]
+pub(crate) fn extra_fields(
+    mut form: reqwest::multipart::Form,
+    extra: Option<serde_json::Map<String, serde_json::Value>>,
+) -> reqwest::multipart::Form {
+    for (key, value) in extra.unwrap_or_default() {
+        form = match value {
+            serde_json::Value::String(text) => form.text(key, text),
+            value => form.text(key, value.to_string()),
+        };
+    }
+    form
+}
+
+/// A file uploaded with [Client::upload_file](crate::Client::upload_file).
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct File {
+    pub id: String,
+    /// The size of the file in bytes.
+    pub bytes: u64,
+    pub created_at: u64,
+    /// When the file expires, if it does.
+    #[serde(default)]
+    pub expires_at: Option<u64>,
+    pub filename: String,
+    /// What the file is used for, like `batch` or `fine-tune`.
+    pub purpose: String,
+}
+
+/// Filters for [Client::list_files](crate::Client::list_files).
+///
+/// ```
+/// let query = openai_rust::files::ListFiles::new().purpose("batch");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ListFiles {
+    /// Only files with this purpose.
+    pub purpose: Option<String>,
+}
+
+impl ListFiles {
+    pub fn new() -> ListFiles {
+        ListFiles::default()
+    }
+
+    pub fn purpose(mut self, purpose: impl AsRef<str>) -> ListFiles {
+        self.purpose = Some(purpose.as_ref().to_owned());
+        self
+    }
+
+    /// The query parameters of the list endpoint.
+    pub(crate) fn query(&self) -> Vec<(String, String)> {
+        self.purpose
+            .iter()
+            .map(|purpose| ("purpose".to_owned(), purpose.clone()))
+            .collect()
+    }
+}
+
+/// The response of [Client::delete_file](crate::Client::delete_file).
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct DeletedFile {
+    pub id: String,
+    pub deleted: bool,
+}
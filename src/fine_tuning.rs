@@ -0,0 +1,657 @@
+//! See <https://platform.openai.com/docs/api-reference/fine-tuning>.
+//! Use with [Client::create_fine_tuning_job](crate::Client::create_fine_tuning_job) and
+//! [Client::list_fine_tuning_jobs](crate::Client::list_fine_tuning_jobs).
+//!
+//! Training data for fine-tuning chat models is uploaded as a JSONL file with one conversation per line.
+//! A [TrainingFile] validates conversations locally before they are turned into such a file,
+//! so mistakes in the dataset are caught before paying for a failed job.
+//!
+//! ```
+//! use openai_rust::chat::Message;
+//! use openai_rust::fine_tuning::TrainingFile;
+//! let message = |role: &str, content: &str| Message::new(role.into(), content);
+//!
+//! let mut file = TrainingFile::new();
+//! for _ in 0..10 {
+//!     file.add(vec![
+//!         message("system", "Marv is a factual chatbot that is also sarcastic."),
+//!         message("user", "What's the capital of France?"),
+//!         message("assistant", "Paris, as if everyone doesn't know that already."),
+//!     ]);
+//! }
+//! let jsonl = file.to_jsonl().unwrap();
+//! assert_eq!(jsonl.lines().count(), 10);
+//! ```
+//!
+//! For [direct preference optimization](https://platform.openai.com/docs/guides/direct-preference-optimization),
+//! use a [PreferenceFile] and [Method::dpo] instead.
+use crate::chat::Message;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// The minimum amount of examples accepted by the fine-tuning API.
+pub const MIN_EXAMPLES: usize = 10;
+
+/// The default maximum amount of tokens in a single example.
+pub const MAX_TOKENS_PER_EXAMPLE: usize = 65_536;
+
+/// Roles that can appear in training conversations.
+const ROLES: &[&str] = &["system", "user", "assistant", "tool"];
+
+/// A set of conversations to fine-tune a chat model on.
+#[derive(Debug, Clone)]
+pub struct TrainingFile {
+    examples: Vec<Vec<Message>>,
+    max_tokens_per_example: usize,
+}
+
+impl Default for TrainingFile {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<Vec<Vec<Message>>> for TrainingFile {
+    fn from(examples: Vec<Vec<Message>>) -> Self {
+        let mut file = TrainingFile::new();
+        file.examples = examples;
+        file
+    }
+}
+
+/// A line of a training file.
+#[derive(Serialize)]
+pub(crate) struct Line<'a> {
+    pub(crate) messages: &'a [Message],
+}
+
+impl TrainingFile {
+    pub fn new() -> TrainingFile {
+        TrainingFile {
+            examples: vec![],
+            max_tokens_per_example: MAX_TOKENS_PER_EXAMPLE,
+        }
+    }
+
+    /// Add a conversation to train on.
+    pub fn add(&mut self, conversation: Vec<Message>) -> &mut Self {
+        self.examples.push(conversation);
+        self
+    }
+
+    /// The maximum amount of tokens in a single example, which depends on the model being fine-tuned.
+    /// Defaults to [MAX_TOKENS_PER_EXAMPLE].
+    ///
+    /// Tokens are estimated using [estimate_message_tokens](crate::tokens::estimate_message_tokens).
+    pub fn max_tokens_per_example(&mut self, max: usize) -> &mut Self {
+        self.max_tokens_per_example = max;
+        self
+    }
+
+    pub fn examples(&self) -> &[Vec<Message>] {
+        &self.examples
+    }
+
+    /// Check the conversations for mistakes that would make the fine-tuning job fail.
+    ///
+    /// ```
+    /// use openai_rust::chat::Message;
+    /// use openai_rust::fine_tuning::{TrainingFile, ValidationError};
+    /// let file = TrainingFile::from(vec![vec![Message::user("What's the capital of France?")]]);
+    /// let errors = file.validate();
+    /// assert!(errors.contains(&ValidationError::NoAssistantMessage { example: 0 }));
+    ///
+    /// // Assistant messages calling tools don't need content
+    /// let call: Message = serde_json::from_value(serde_json::json!({
+    ///     "role": "assistant",
+    ///     "content": null,
+    ///     "tool_calls": [{"id": "call_abc123", "type": "function", "function": {"name": "get_weather", "arguments": "{}"}}]
+    /// }))
+    /// .unwrap();
+    /// let file = TrainingFile::from(vec![vec![
+    ///     Message::user("How cold is it in Paris?"),
+    ///     call,
+    ///     Message::tool_result("call_abc123", "4 degrees"),
+    ///     Message::assistant("It is 4 degrees in Paris."),
+    /// ]]);
+    /// assert!(!file.validate().iter().any(|e| matches!(e, ValidationError::EmptyContent { .. })));
+    /// ```
+    pub fn validate(&self) -> Vec<ValidationError> {
+        let mut errors = vec![];
+        if self.examples.len() < MIN_EXAMPLES {
+            errors.push(ValidationError::TooFewExamples {
+                count: self.examples.len(),
+                min: MIN_EXAMPLES,
+            });
+        }
+        for (example, messages) in self.examples.iter().enumerate() {
+            errors.extend(validate_conversation(example, messages));
+            let tokens = crate::tokens::estimate_message_tokens(messages);
+            if tokens > self.max_tokens_per_example {
+                errors.push(ValidationError::TooManyTokens {
+                    example,
+                    tokens,
+                    max: self.max_tokens_per_example,
+                });
+            }
+        }
+        errors
+    }
+
+    /// Validate the conversations and turn them into the JSONL format expected by the fine-tuning API.
+    pub fn to_jsonl(&self) -> Result<String, Vec<ValidationError>> {
+        let errors = self.validate();
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+        Ok(self
+            .examples
+            .iter()
+            .map(|messages| serde_json::to_string(&Line { messages }).unwrap() + "\n")
+            .collect())
+    }
+}
+
+fn validate_conversation(example: usize, messages: &[Message]) -> Vec<ValidationError> {
+    let mut errors = validate_messages(example, messages);
+    if !messages.is_empty() && !messages.iter().any(|m| m.role == "assistant") {
+        errors.push(ValidationError::NoAssistantMessage { example });
+    }
+    errors
+}
+
+fn validate_messages(example: usize, messages: &[Message]) -> Vec<ValidationError> {
+    let mut errors = vec![];
+    if messages.is_empty() {
+        errors.push(ValidationError::EmptyExample { example });
+        return errors;
+    }
+
+    let mut previous: Option<&str> = None;
+    for (message, m) in messages.iter().enumerate() {
+        let role = m.role.as_str();
+        if !ROLES.contains(&role) {
+            errors.push(ValidationError::UnknownRole {
+                example,
+                message,
+                role: m.role.to_string(),
+            });
+        }
+        if m.content.text().trim().is_empty() && m.tool_calls.is_empty() && m.function_call.is_none() {
+            errors.push(ValidationError::EmptyContent { example, message });
+        }
+        if role == "system" && message != 0 {
+            errors.push(ValidationError::MisplacedSystemMessage { example, message });
+        }
+        if (role == "user" || role == "assistant") && previous == Some(role) {
+            errors.push(ValidationError::RepeatedRole { example, message });
+        }
+        previous = Some(role);
+    }
+    errors
+}
+
+/// A set of prompts with a preferred and a non-preferred response,
+/// to fine-tune a chat model on using [Method::dpo].
+///
+/// ```
+/// use openai_rust::chat::Message;
+/// use openai_rust::fine_tuning::PreferenceFile;
+/// let message = |role: &str, content: &str| Message::new(role.into(), content);
+///
+/// let mut file = PreferenceFile::new();
+/// for _ in 0..10 {
+///     file.add(
+///         vec![message("user", "Hello, can you tell me how cold San Francisco is today?")],
+///         message("assistant", "Today in San Francisco, it is not quite cold as expected."),
+///         message("assistant", "It is not particularly cold in San Francisco today."),
+///     );
+/// }
+/// let jsonl = file.to_jsonl().unwrap();
+/// assert!(jsonl.starts_with(r#"{"input":{"messages":[{"role":"user""#));
+/// ```
+#[derive(Debug, Clone)]
+pub struct PreferenceFile {
+    examples: Vec<PreferenceExample>,
+    max_tokens_per_example: usize,
+}
+
+/// A prompt with a preferred and a non-preferred response.
+#[derive(Debug, Clone)]
+pub struct PreferenceExample {
+    /// The conversation leading up to the responses.
+    pub input: Vec<Message>,
+    pub preferred: Message,
+    pub non_preferred: Message,
+}
+
+impl Default for PreferenceFile {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<Vec<PreferenceExample>> for PreferenceFile {
+    fn from(examples: Vec<PreferenceExample>) -> Self {
+        let mut file = PreferenceFile::new();
+        file.examples = examples;
+        file
+    }
+}
+
+#[derive(Serialize)]
+struct PreferenceLine<'a> {
+    input: Line<'a>,
+    preferred_output: [&'a Message; 1],
+    non_preferred_output: [&'a Message; 1],
+}
+
+impl PreferenceFile {
+    pub fn new() -> PreferenceFile {
+        PreferenceFile {
+            examples: vec![],
+            max_tokens_per_example: MAX_TOKENS_PER_EXAMPLE,
+        }
+    }
+
+    /// Add a conversation with a preferred and a non-preferred assistant response to it.
+    pub fn add(&mut self, input: Vec<Message>, preferred: Message, non_preferred: Message) -> &mut Self {
+        self.examples.push(PreferenceExample {
+            input,
+            preferred,
+            non_preferred,
+        });
+        self
+    }
+
+    /// The maximum amount of tokens in a single example.
+    /// See [TrainingFile::max_tokens_per_example].
+    pub fn max_tokens_per_example(&mut self, max: usize) -> &mut Self {
+        self.max_tokens_per_example = max;
+        self
+    }
+
+    pub fn examples(&self) -> &[PreferenceExample] {
+        &self.examples
+    }
+
+    /// Check the examples for mistakes that would make the fine-tuning job fail.
+    pub fn validate(&self) -> Vec<ValidationError> {
+        let mut errors = vec![];
+        if self.examples.len() < MIN_EXAMPLES {
+            errors.push(ValidationError::TooFewExamples {
+                count: self.examples.len(),
+                min: MIN_EXAMPLES,
+            });
+        }
+        for (example, e) in self.examples.iter().enumerate() {
+            errors.extend(validate_messages(example, &e.input));
+            if e.input.last().is_some_and(|m| m.role != "user") {
+                errors.push(ValidationError::InputNotEndingWithUser { example });
+            }
+            for output in [&e.preferred, &e.non_preferred] {
+                if output.role != "assistant" {
+                    errors.push(ValidationError::OutputNotFromAssistant { example });
+                }
+                if output.content.text().trim().is_empty() {
+                    errors.push(ValidationError::EmptyOutput { example });
+                }
+            }
+            if e.preferred.content == e.non_preferred.content {
+                errors.push(ValidationError::IdenticalOutputs { example });
+            }
+            let tokens = crate::tokens::estimate_message_tokens(&e.input)
+                + crate::tokens::estimate_tokens(&e.preferred.content.text())
+                    .max(crate::tokens::estimate_tokens(&e.non_preferred.content.text()));
+            if tokens > self.max_tokens_per_example {
+                errors.push(ValidationError::TooManyTokens {
+                    example,
+                    tokens,
+                    max: self.max_tokens_per_example,
+                });
+            }
+        }
+        errors
+    }
+
+    /// Validate the examples and turn them into the JSONL format expected by the fine-tuning API.
+    pub fn to_jsonl(&self) -> Result<String, Vec<ValidationError>> {
+        let errors = self.validate();
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+        Ok(self
+            .examples
+            .iter()
+            .map(|e| {
+                let line = PreferenceLine {
+                    input: Line { messages: &e.input },
+                    preferred_output: [&e.preferred],
+                    non_preferred_output: [&e.non_preferred],
+                };
+                serde_json::to_string(&line).unwrap() + "\n"
+            })
+            .collect())
+    }
+}
+
+/// A mistake in a [TrainingFile] or [PreferenceFile].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationError {
+    /// The fine-tuning API requires a minimum amount of examples.
+    TooFewExamples { count: usize, min: usize },
+    /// A conversation without messages.
+    EmptyExample { example: usize },
+    /// A message with a role other than `system`, `user`, `assistant` or `tool`.
+    UnknownRole { example: usize, message: usize, role: String },
+    /// A message without content, tool calls or a function call.
+    EmptyContent { example: usize, message: usize },
+    /// A system message that is not the first message of the conversation.
+    MisplacedSystemMessage { example: usize, message: usize },
+    /// Two user or two assistant messages in a row.
+    RepeatedRole { example: usize, message: usize },
+    /// A conversation without an assistant message to learn from.
+    NoAssistantMessage { example: usize },
+    /// A conversation that is too long, and would be truncated.
+    TooManyTokens { example: usize, tokens: usize, max: usize },
+    /// The input of a preference example that does not end with a user message.
+    InputNotEndingWithUser { example: usize },
+    /// A preferred or non-preferred output that is not an assistant message.
+    OutputNotFromAssistant { example: usize },
+    /// A preferred or non-preferred output without content.
+    EmptyOutput { example: usize },
+    /// A preference example where both outputs are the same, so there is nothing to learn.
+    IdenticalOutputs { example: usize },
+}
+
+impl std::fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ValidationError::TooFewExamples { count, min } => {
+                write!(f, "{} examples given, but at least {} are required", count, min)
+            }
+            ValidationError::EmptyExample { example } => write!(f, "example {} has no messages", example),
+            ValidationError::UnknownRole { example, message, role } => {
+                write!(f, "message {} of example {} has unknown role {:?}", message, example, role)
+            }
+            ValidationError::EmptyContent { example, message } => {
+                write!(f, "message {} of example {} has no content", message, example)
+            }
+            ValidationError::MisplacedSystemMessage { example, message } => write!(
+                f,
+                "message {} of example {} is a system message, which is only allowed as the first message",
+                message, example
+            ),
+            ValidationError::RepeatedRole { example, message } => write!(
+                f,
+                "message {} of example {} has the same role as the message before it",
+                message, example
+            ),
+            ValidationError::NoAssistantMessage { example } => {
+                write!(f, "example {} has no assistant message", example)
+            }
+            ValidationError::TooManyTokens { example, tokens, max } => write!(
+                f,
+                "example {} has about {} tokens, more than the maximum of {}",
+                example, tokens, max
+            ),
+            ValidationError::InputNotEndingWithUser { example } => {
+                write!(f, "the input of example {} does not end with a user message", example)
+            }
+            ValidationError::OutputNotFromAssistant { example } => {
+                write!(f, "an output of example {} is not an assistant message", example)
+            }
+            ValidationError::EmptyOutput { example } => write!(f, "an output of example {} has no content", example),
+            ValidationError::IdenticalOutputs { example } => {
+                write!(f, "the preferred and non-preferred output of example {} are the same", example)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+/// Request arguments for creating a fine-tuning job.
+///
+/// See <https://platform.openai.com/docs/api-reference/fine-tuning/create>.
+///
+/// ```
+/// use openai_rust::fine_tuning::*;
+/// let mut args = FineTuningJobArguments::new("gpt-4o-mini-2024-07-18", "file-abc123");
+/// args.hyperparameters = Some(Hyperparameters {
+///     n_epochs: Some(Hyperparameter::Value(3)),
+///     batch_size: Some(Hyperparameter::Auto),
+///     learning_rate_multiplier: Some(Hyperparameter::Value(0.5)),
+/// });
+/// # assert_eq!(
+/// #     serde_json::to_string(&args.hyperparameters).unwrap(),
+/// #     r#"{"n_epochs":3,"batch_size":"auto","learning_rate_multiplier":0.5}"#
+/// # );
+/// ```
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct FineTuningJobArguments {
+    /// The name of the model to fine-tune.
+    pub model: String,
+
+    /// The ID of an uploaded file that contains the training data.
+    pub training_file: String,
+
+    /// The ID of an uploaded file that contains the validation data.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub validation_file: Option<String>,
+
+    /// The hyperparameters used for the fine-tuning job.
+    ///
+    /// Deprecated by OpenAI in favor of the hyperparameters in [method](FineTuningJobArguments::method).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hyperparameters: Option<Hyperparameters>,
+
+    /// The method used for fine-tuning. Defaults to [Method::Supervised].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub method: Option<Method>,
+
+    /// A string of up to 64 characters that will be added to the name of the fine-tuned model.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suffix: Option<String>,
+
+    /// The seed controls the reproducibility of the job.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u32>,
+
+    /// Additional parameters sent in the body, for parameters not yet supported by this crate or specific to a provider.
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    pub extra: Option<serde_json::Map<String, serde_json::Value>>,
+}
+
+impl FineTuningJobArguments {
+    pub fn new(model: impl AsRef<str>, training_file: impl AsRef<str>) -> FineTuningJobArguments {
+        FineTuningJobArguments {
+            model: model.as_ref().to_owned(),
+            training_file: training_file.as_ref().to_owned(),
+            validation_file: None,
+            hyperparameters: None,
+            method: None,
+            suffix: None,
+            seed: None,
+            extra: None,
+        }
+    }
+}
+
+/// The hyperparameters of a fine-tuning job. Unset values are chosen by OpenAI.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct Hyperparameters {
+    /// The number of epochs to train the model for.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub n_epochs: Option<Hyperparameter<u32>>,
+
+    /// Number of examples in each batch.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub batch_size: Option<Hyperparameter<u32>>,
+
+    /// Scaling factor for the learning rate.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub learning_rate_multiplier: Option<Hyperparameter<f64>>,
+}
+
+/// A hyperparameter which is either set, or chosen automatically based on the dataset.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Hyperparameter<T> {
+    /// Serialized as `"auto"`.
+    Auto,
+    Value(T),
+}
+
+impl<T: Serialize> Serialize for Hyperparameter<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Hyperparameter::Auto => serializer.serialize_str("auto"),
+            Hyperparameter::Value(value) => value.serialize(serializer),
+        }
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Hyperparameter<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Raw<T> {
+            Value(T),
+            Auto(String),
+        }
+        match Raw::deserialize(deserializer)? {
+            Raw::Value(value) => Ok(Hyperparameter::Value(value)),
+            Raw::Auto(s) if s == "auto" => Ok(Hyperparameter::Auto),
+            Raw::Auto(s) => Err(serde::de::Error::custom(format!("invalid hyperparameter {:?}", s))),
+        }
+    }
+}
+
+/// The method used for fine-tuning.
+///
+/// ```
+/// use openai_rust::fine_tuning::*;
+/// let mut args = FineTuningJobArguments::new("gpt-4o-2024-08-06", "file-abc123");
+/// args.method = Some(Method::dpo(DpoHyperparameters {
+///     beta: Some(Hyperparameter::Value(0.1)),
+///     ..Default::default()
+/// }));
+/// # assert_eq!(
+/// #     serde_json::to_string(&args.method).unwrap(),
+/// #     r#"{"type":"dpo","dpo":{"hyperparameters":{"beta":0.1}}}"#
+/// # );
+/// # let method: Method = serde_json::from_str(r#"{"type":"reinforcement","reinforcement":{}}"#).unwrap();
+/// # assert_eq!(method, Method::Other);
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Method {
+    /// Supervised fine-tuning on a [TrainingFile].
+    Supervised {
+        #[serde(default)]
+        supervised: SupervisedMethod,
+    },
+    /// Direct preference optimization on a [PreferenceFile].
+    Dpo {
+        #[serde(default)]
+        dpo: DpoMethod,
+    },
+    /// A method not yet supported by this crate, like `reinforcement`.
+    #[serde(other)]
+    Other,
+}
+
+impl Method {
+    /// Supervised fine-tuning with the given hyperparameters.
+    pub fn supervised(hyperparameters: Hyperparameters) -> Method {
+        Method::Supervised {
+            supervised: SupervisedMethod {
+                hyperparameters: Some(hyperparameters),
+            },
+        }
+    }
+
+    /// Direct preference optimization with the given hyperparameters.
+    pub fn dpo(hyperparameters: DpoHyperparameters) -> Method {
+        Method::Dpo {
+            dpo: DpoMethod {
+                hyperparameters: Some(hyperparameters),
+            },
+        }
+    }
+}
+
+/// Configuration for [Method::Supervised].
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct SupervisedMethod {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hyperparameters: Option<Hyperparameters>,
+}
+
+/// Configuration for [Method::Dpo].
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct DpoMethod {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hyperparameters: Option<DpoHyperparameters>,
+}
+
+/// The hyperparameters of a [Method::Dpo] fine-tuning job. Unset values are chosen by OpenAI.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct DpoHyperparameters {
+    /// Weight of the penalty for diverging from the base model.
+    /// A higher value makes the fine-tuned model more conservative.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub beta: Option<Hyperparameter<f64>>,
+
+    /// The number of epochs to train the model for.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub n_epochs: Option<Hyperparameter<u32>>,
+
+    /// Number of examples in each batch.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub batch_size: Option<Hyperparameter<u32>>,
+
+    /// Scaling factor for the learning rate.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub learning_rate_multiplier: Option<Hyperparameter<f64>>,
+}
+
+/// A fine-tuning job.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct FineTuningJob {
+    pub id: String,
+    pub created_at: u64,
+    /// The base model that is being fine-tuned.
+    pub model: String,
+    /// The name of the fine-tuned model, once the job has succeeded.
+    pub fine_tuned_model: Option<String>,
+    /// One of `validating_files`, `queued`, `running`, `succeeded`, `failed`, or `cancelled`.
+    pub status: String,
+    pub training_file: String,
+    pub validation_file: Option<String>,
+    #[serde(default)]
+    pub hyperparameters: Hyperparameters,
+    #[serde(default)]
+    pub method: Option<Method>,
+    /// The total number of billable tokens processed, once the job has finished.
+    pub trained_tokens: Option<u64>,
+    pub finished_at: Option<u64>,
+    /// The IDs of the files with the results of the job.
+    #[serde(default)]
+    pub result_files: Vec<String>,
+}
+
+impl FineTuningJob {
+    /// When the job was created, as a date.
+    #[cfg(feature = "chrono")]
+    pub fn created_at(&self) -> chrono::DateTime<chrono::Utc> {
+        crate::timestamp(self.created_at)
+    }
+
+    /// When the job finished, as a date.
+    #[cfg(feature = "chrono")]
+    pub fn finished_at(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        self.finished_at.map(crate::timestamp)
+    }
+}
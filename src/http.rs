@@ -0,0 +1,61 @@
+//! Sending requests through another HTTP client, like [reqwest_middleware](https://docs.rs/reqwest-middleware),
+//! an instrumented client or a mock in tests. Attach with [Client::with_http_client](crate::Client::with_http_client).
+//!
+//! The [Client](crate::Client) still builds the requests, and an [HttpClient] sends them.
+//! Closures returning a future implement [HttpClient]:
+//!
+//! ```
+//! # tokio_test::block_on(async {
+//! let client = openai_rust::Client::new("").with_http_client(|_req: reqwest::Request| async {
+//!     let res = http::Response::new(r#"{"object":"list","data":[]}"#);
+//!     Ok(reqwest::Response::from(res))
+//! });
+//! let models = client.list_models().await.unwrap();
+//! assert!(models.is_empty());
+//! # })
+//! ```
+//!
+//! A `reqwest_middleware::ClientWithMiddleware` is wrapped the same way:
+//!
+//! ```ignore
+//! let middleware: reqwest_middleware::ClientWithMiddleware = todo!();
+//! let client = openai_rust::Client::new(api_key).with_http_client(move |req| {
+//!     let middleware = middleware.clone();
+//!     async move { Ok(middleware.execute(req).await?) }
+//! });
+//! ```
+use std::future::Future;
+use std::pin::Pin;
+
+/// Sends the requests of a [Client](crate::Client).
+pub trait HttpClient: Send + Sync + 'static {
+    fn execute(&self, req: reqwest::Request) -> impl Future<Output = anyhow::Result<reqwest::Response>> + Send;
+}
+
+impl HttpClient for reqwest::Client {
+    fn execute(&self, req: reqwest::Request) -> impl Future<Output = anyhow::Result<reqwest::Response>> + Send {
+        let res = reqwest::Client::execute(self, req);
+        async move { Ok(res.await?) }
+    }
+}
+
+impl<F, Fut> HttpClient for F
+where
+    F: Fn(reqwest::Request) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = anyhow::Result<reqwest::Response>> + Send,
+{
+    fn execute(&self, req: reqwest::Request) -> impl Future<Output = anyhow::Result<reqwest::Response>> + Send {
+        self(req)
+    }
+}
+
+/// [HttpClient] with a boxed future, so it can be stored in the [Client](crate::Client).
+pub(crate) trait DynHttpClient: Send + Sync {
+    fn execute(&self, req: reqwest::Request) -> Pin<Box<dyn Future<Output = anyhow::Result<reqwest::Response>> + Send + '_>>;
+}
+
+impl<H: HttpClient> DynHttpClient for H {
+    fn execute(&self, req: reqwest::Request) -> Pin<Box<dyn Future<Output = anyhow::Result<reqwest::Response>> + Send + '_>> {
+        Box::pin(HttpClient::execute(self, req))
+    }
+}
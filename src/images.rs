@@ -1,56 +1,414 @@
 //! See <https://platform.openai.com/docs/api-reference/images>.
-//! Use with [Client::create_image](crate::Client::create_image).
+//! Use with [Client::create_image](crate::Client::create_image), [Client::create_image_edit](crate::Client::create_image_edit)
+//! and [Client::create_image_variation](crate::Client::create_image_variation).
 
 use serde::{Deserialize, Serialize};
 
+use crate::files::Upload;
+
 /// The format in which the generated images are returned.
-#[derive(Serialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub enum ResponseFormat {
+    /// A URL to the image, valid for an hour.
+    #[serde(rename = "url")]
     Url,
+    /// The image encoded as base64, in [ImageObject::b64_json].
+    #[serde(rename = "b64_json")]
     Base64JSON,
 }
 
-#[derive(Serialize, Debug, Clone)]
+impl ResponseFormat {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            ResponseFormat::Url => "url",
+            ResponseFormat::Base64JSON => "b64_json",
+        }
+    }
+}
+
+/// The size of generated images.
+///
+/// `dall-e-2` supports the square sizes, `dall-e-3` supports 1024x1024 and the landscape and portrait sizes.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Size {
+    #[serde(rename = "256x256")]
+    Square256,
+    #[serde(rename = "512x512")]
+    Square512,
+    #[serde(rename = "1024x1024")]
+    Square1024,
+    #[serde(rename = "1792x1024")]
+    Landscape,
+    #[serde(rename = "1024x1792")]
+    Portrait,
+}
+
+impl Size {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            Size::Square256 => "256x256",
+            Size::Square512 => "512x512",
+            Size::Square1024 => "1024x1024",
+            Size::Landscape => "1792x1024",
+            Size::Portrait => "1024x1792",
+        }
+    }
+}
+
+/// The quality of images generated by `dall-e-3`.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum Quality {
+    Standard,
+    /// Finer details and more consistency across the image, at a higher price.
+    Hd,
+}
+
+/// The style of images generated by `dall-e-3`.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum Style {
+    /// Hyper-real and dramatic images.
+    Vivid,
+    /// More natural, less hyper-real images.
+    Natural,
+}
+
+/// Request arguments for image generation.
+///
+/// See <https://platform.openai.com/docs/api-reference/images/create>.
+///
+/// ```
+/// use openai_rust::images::{ImageArguments, ResponseFormat};
+/// let args = ImageArguments::new("A cute baby sea otter")
+///     .n(2)
+///     .response_format(ResponseFormat::Base64JSON);
+/// ```
+///
+/// With `dall-e-3`:
+///
+/// ```
+/// use openai_rust::images::{ImageArguments, Quality, Size, Style};
+/// let args = ImageArguments::new("A cute baby sea otter")
+///     .model("dall-e-3")
+///     .size(Size::Landscape)
+///     .quality(Quality::Hd)
+///     .style(Style::Natural);
+/// let body = serde_json::to_value(&args).unwrap();
+/// assert_eq!(body["size"], "1792x1024");
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[non_exhaustive]
 pub struct ImageArguments {
-    /// A text description of the desired image(s). The maximum length is 1000 characters.
+    /// A text description of the desired image(s). The maximum length is 1000 characters for `dall-e-2` and 4000 characters for `dall-e-3`.
     pub prompt: String,
-    /// The number of images to generate. Must be between 1 and 10. Defaults to 1.
+    /// The model to use, like `dall-e-2` or `dall-e-3`. Defaults to `dall-e-2`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// The number of images to generate. Must be between 1 and 10, and 1 for `dall-e-3`. Defaults to 1.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub n: Option<u32>,
+    /// The quality of the images, only supported by `dall-e-3`. Defaults to [Quality::Standard].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quality: Option<Quality>,
+    /// The style of the images, only supported by `dall-e-3`. Defaults to [Style::Vivid].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub style: Option<Style>,
     /// The format in which the generated images are returned Defaults to `url`.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub response_format: Option<ResponseFormat>,
-    /// The size of the generated images. Must be one of `256x256`, `512x512`, or `1024x1024`. Defaults to `1024x1024`.
+    /// The size of the generated images. Defaults to [Size::Square1024].
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub size: Option<u32>,
+    pub size: Option<Size>,
     /// A unique identifier representing your end-user, which can help OpenAI to monitor and detect abuse. [Learn more](https://platform.openai.com/docs/guides/safety-best-practices/end-user-ids).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub user: Option<String>,
+    /// Additional parameters sent in the body, for parameters not yet supported by this crate or specific to a provider.
+    #[serde(flatten, skip_serializing_if = "Option::is_none", deserialize_with = "crate::de::empty_none")]
+    pub extra: Option<serde_json::Map<String, serde_json::Value>>,
 }
 
 impl ImageArguments {
     pub fn new(prompt: impl AsRef<str>) -> Self {
         Self {
             prompt: prompt.as_ref().to_owned(),
+            model: None,
             n: None,
+            quality: None,
+            style: None,
             response_format: None,
             size: None,
             user: None,
+            extra: None,
         }
     }
+
+    pub fn model(mut self, model: impl AsRef<str>) -> Self {
+        self.model = Some(model.as_ref().to_owned());
+        self
+    }
+
+    pub fn n(mut self, n: u32) -> Self {
+        self.n = Some(n);
+        self
+    }
+
+    pub fn quality(mut self, quality: Quality) -> Self {
+        self.quality = Some(quality);
+        self
+    }
+
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    pub fn response_format(mut self, response_format: ResponseFormat) -> Self {
+        self.response_format = Some(response_format);
+        self
+    }
+
+    pub fn size(mut self, size: Size) -> Self {
+        self.size = Some(size);
+        self
+    }
+
+    pub fn user(mut self, user: impl AsRef<str>) -> Self {
+        self.user = Some(user.as_ref().to_owned());
+        self
+    }
+
+    /// Add a parameter not yet supported by this crate to [extra](ImageArguments::extra).
+    pub fn extra(mut self, key: impl AsRef<str>, value: impl Into<serde_json::Value>) -> Self {
+        self.extra.get_or_insert_with(Default::default).insert(key.as_ref().to_owned(), value.into());
+        self
+    }
+}
+
+/// Request arguments for editing an image.
+///
+/// See <https://platform.openai.com/docs/api-reference/images/createEdit>.
+///
+/// ```
+/// use openai_rust::files::Upload;
+/// use openai_rust::images::{ImageEditArguments, Size};
+/// # let (image, mask) = (vec![], vec![]);
+/// let args = ImageEditArguments::new(Upload::bytes(image, "otter.png"), "A cute baby sea otter wearing a beret")
+///     .mask(Upload::bytes(mask, "mask.png"))
+///     .n(2)
+///     .size(Size::Square512);
+/// ```
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct ImageEditArguments {
+    /// The image to edit, a square PNG smaller than 4MB. Without a mask, it needs transparent areas to edit.
+    pub image: Upload,
+    /// A text description of the desired image(s). The maximum length is 1000 characters.
+    pub prompt: String,
+    /// A PNG of the same size as the image, whose transparent areas mark where the image is edited.
+    pub mask: Option<Upload>,
+    /// The model to use, like `dall-e-2`. Defaults to `dall-e-2`.
+    pub model: Option<String>,
+    /// The number of images to generate. Must be between 1 and 10. Defaults to 1.
+    pub n: Option<u32>,
+    /// The size of the generated images. Must be one of the square sizes. Defaults to [Size::Square1024].
+    pub size: Option<Size>,
+    /// The format in which the generated images are returned. Defaults to [ResponseFormat::Url].
+    pub response_format: Option<ResponseFormat>,
+    /// A unique identifier representing your end-user, which can help OpenAI to monitor and detect abuse. [Learn more](https://platform.openai.com/docs/guides/safety-best-practices/end-user-ids).
+    pub user: Option<String>,
+    /// Additional parameters sent as form fields, for parameters not yet supported by this crate or specific to a provider.
+    /// Strings are sent as is, other values as JSON.
+    pub extra: Option<serde_json::Map<String, serde_json::Value>>,
+}
+
+impl ImageEditArguments {
+    pub fn new(image: Upload, prompt: impl AsRef<str>) -> ImageEditArguments {
+        ImageEditArguments {
+            image,
+            prompt: prompt.as_ref().to_owned(),
+            mask: None,
+            model: None,
+            n: None,
+            size: None,
+            response_format: None,
+            user: None,
+            extra: None,
+        }
+    }
+
+    pub fn mask(mut self, mask: Upload) -> ImageEditArguments {
+        self.mask = Some(mask);
+        self
+    }
+
+    pub fn model(mut self, model: impl AsRef<str>) -> ImageEditArguments {
+        self.model = Some(model.as_ref().to_owned());
+        self
+    }
+
+    pub fn n(mut self, n: u32) -> ImageEditArguments {
+        self.n = Some(n);
+        self
+    }
+
+    pub fn size(mut self, size: Size) -> ImageEditArguments {
+        self.size = Some(size);
+        self
+    }
+
+    pub fn response_format(mut self, response_format: ResponseFormat) -> ImageEditArguments {
+        self.response_format = Some(response_format);
+        self
+    }
+
+    pub fn user(mut self, user: impl AsRef<str>) -> ImageEditArguments {
+        self.user = Some(user.as_ref().to_owned());
+        self
+    }
+
+    pub(crate) fn into_form(self) -> reqwest::multipart::Form {
+        let mut form = reqwest::multipart::Form::new()
+            .part("image", self.image.into_part())
+            .text("prompt", self.prompt);
+        if let Some(mask) = self.mask {
+            form = form.part("mask", mask.into_part());
+        }
+        let form = common_fields(form, self.model, self.n, self.size, self.response_format, self.user);
+        crate::files::extra_fields(form, self.extra)
+    }
+}
+
+/// Request arguments for creating variations of an image.
+///
+/// See <https://platform.openai.com/docs/api-reference/images/createVariation>.
+///
+/// ```
+/// use openai_rust::files::Upload;
+/// use openai_rust::images::ImageVariationArguments;
+/// # let image = vec![];
+/// let args = ImageVariationArguments::new(Upload::bytes(image, "otter.png")).n(4);
+/// ```
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct ImageVariationArguments {
+    /// The image to use as the basis for the variations, a square PNG smaller than 4MB.
+    pub image: Upload,
+    /// The model to use. Only `dall-e-2` is supported.
+    pub model: Option<String>,
+    /// The number of images to generate. Must be between 1 and 10. Defaults to 1.
+    pub n: Option<u32>,
+    /// The size of the generated images. Must be one of the square sizes. Defaults to [Size::Square1024].
+    pub size: Option<Size>,
+    /// The format in which the generated images are returned. Defaults to [ResponseFormat::Url].
+    pub response_format: Option<ResponseFormat>,
+    /// A unique identifier representing your end-user, which can help OpenAI to monitor and detect abuse. [Learn more](https://platform.openai.com/docs/guides/safety-best-practices/end-user-ids).
+    pub user: Option<String>,
+    /// Additional parameters sent as form fields, for parameters not yet supported by this crate or specific to a provider.
+    /// Strings are sent as is, other values as JSON.
+    pub extra: Option<serde_json::Map<String, serde_json::Value>>,
+}
+
+impl ImageVariationArguments {
+    pub fn new(image: Upload) -> ImageVariationArguments {
+        ImageVariationArguments {
+            image,
+            model: None,
+            n: None,
+            size: None,
+            response_format: None,
+            user: None,
+            extra: None,
+        }
+    }
+
+    pub fn model(mut self, model: impl AsRef<str>) -> ImageVariationArguments {
+        self.model = Some(model.as_ref().to_owned());
+        self
+    }
+
+    pub fn n(mut self, n: u32) -> ImageVariationArguments {
+        self.n = Some(n);
+        self
+    }
+
+    pub fn size(mut self, size: Size) -> ImageVariationArguments {
+        self.size = Some(size);
+        self
+    }
+
+    pub fn response_format(mut self, response_format: ResponseFormat) -> ImageVariationArguments {
+        self.response_format = Some(response_format);
+        self
+    }
+
+    pub fn user(mut self, user: impl AsRef<str>) -> ImageVariationArguments {
+        self.user = Some(user.as_ref().to_owned());
+        self
+    }
+
+    pub(crate) fn into_form(self) -> reqwest::multipart::Form {
+        let form = reqwest::multipart::Form::new().part("image", self.image.into_part());
+        let form = common_fields(form, self.model, self.n, self.size, self.response_format, self.user);
+        crate::files::extra_fields(form, self.extra)
+    }
+}
+
+/// Add the optional fields shared by edits and variations to a form.
+fn common_fields(
+    mut form: reqwest::multipart::Form,
+    model: Option<String>,
+    n: Option<u32>,
+    size: Option<Size>,
+    response_format: Option<ResponseFormat>,
+    user: Option<String>,
+) -> reqwest::multipart::Form {
+    if let Some(model) = model {
+        form = form.text("model", model);
+    }
+    if let Some(n) = n {
+        form = form.text("n", n.to_string());
+    }
+    if let Some(size) = size {
+        form = form.text("size", size.as_str());
+    }
+    if let Some(response_format) = response_format {
+        form = form.text("response_format", response_format.as_str());
+    }
+    if let Some(user) = user {
+        form = form.text("user", user);
+    }
+    form
 }
 
-#[derive(Deserialize, Debug)]
-pub(crate) enum ImageObject {
-    #[serde(alias = "url")]
-    Url(String),
-    #[serde(alias = "b64_json")]
-    Base64JSON(String), 
+/// An image generated by [Client::create_image](crate::Client::create_image), an edit or a variation.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ImageObject {
+    /// The URL of the image, with [ResponseFormat::Url].
+    #[serde(default)]
+    pub url: Option<String>,
+    /// The image encoded as base64, with [ResponseFormat::Base64JSON].
+    #[serde(default)]
+    pub b64_json: Option<String>,
+    /// The prompt the image was generated from, when the model rewrote the prompt, like `dall-e-3` does.
+    #[serde(default)]
+    pub revised_prompt: Option<String>,
 }
 
-#[derive(Deserialize, Debug)]
-pub(crate) struct ImageResponse {
-    #[allow(dead_code)]
-    created: u32,
+/// The images returned by [Client::create_image](crate::Client::create_image),
+/// [Client::create_image_edit](crate::Client::create_image_edit) and [Client::create_image_variation](crate::Client::create_image_variation).
+///
+/// ```
+/// # let json = r#"{"created": 1589478378, "data": [{"url": "https://example.com/otter.png", "revised_prompt": "A baby sea otter"}]}"#;
+/// let res: openai_rust::images::ImageResponse = serde_json::from_str(json).unwrap();
+/// for image in &res.data {
+///     println!("{:?}: {:?}", image.revised_prompt, image.url);
+/// }
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ImageResponse {
+    pub created: u64,
     pub data: Vec<ImageObject>,
 }
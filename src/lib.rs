@@ -1,304 +1,3035 @@
 #![doc = include_str!("../README.md")]
 //#![feature(str_split_remainder)]
-use anyhow::{anyhow, Result};
+use anyhow::anyhow;
+use error::Result;
 use lazy_static::lazy_static;
-use reqwest;
+use std::sync::Arc;
 
 pub extern crate futures_util;
 
 lazy_static! {
     static ref BASE_URL: reqwest::Url =
-        reqwest::Url::parse("https://api.openai.com/v1/models").unwrap();
+        reqwest::Url::parse("https://api.openai.com/v1").unwrap();
+}
+
+/// The `User-Agent` sent by clients that build their own [reqwest::Client].
+const USER_AGENT: &str = concat!("openai-rust/", env!("CARGO_PKG_VERSION"));
+
+/// A header value, or an error naming the header when it contains characters that are not allowed.
+fn header_value(name: &str, value: &str) -> anyhow::Result<reqwest::header::HeaderValue> {
+    reqwest::header::HeaderValue::from_str(value).map_err(|e| anyhow!("invalid value of header {}: {}", name, e))
 }
 
 /// This is the main interface to interact with the api.
+///
+/// Clones share the [queue](Client::with_queue), [rate limiter](Client::with_rate_limiter),
+/// [budget](Client::with_budget), [circuit breaker](Client::with_circuit_breaker), cached credentials
+/// and [requests in flight](Client::shutdown) of the original,
+/// so a clone can change the settings of a few requests, like the [project](Client::with_project) they are billed to.
+#[derive(Clone)]
 pub struct Client {
     req_client: reqwest::Client,
+    http_client: Option<Arc<dyn http::DynHttpClient>>,
     key: String,
+    credentials: Option<Arc<credentials::CachedCredentials>>,
+    failover: Option<Arc<failover::Failover>>,
+    budget: Option<Arc<budget::Budget>>,
+    logger: Option<Arc<logger::Logger>>,
+    metrics: Option<Arc<dyn metrics::MetricsSink>>,
+    in_flight: Arc<shutdown::Tracker>,
+    base_url: reqwest::Url,
+    headers: reqwest::header::HeaderMap,
+    provider: provider::Provider,
+    endpoint_urls: std::collections::HashMap<String, reqwest::Url>,
+    versions: versions::Versions,
+    circuit_breaker: Option<Arc<circuit_breaker::CircuitBreaker>>,
+    queue: Option<queue::RequestQueue>,
+    rate_limiter: Option<Arc<rate_limit::RateLimiter>>,
+    retry: Option<retry::RetryPolicy>,
+    priority: queue::Priority,
+    #[cfg(feature = "moderations")]
+    moderation: Option<String>,
+    #[cfg(feature = "chat")]
+    auto_max_tokens: bool,
+    #[cfg(feature = "chat")]
+    auto_continue: u32,
+    #[cfg(feature = "chat")]
+    json_repair: u32,
+}
+
+#[cfg(feature = "assistants")]
+pub mod assistants;
+#[cfg(feature = "audio")]
+pub mod audio;
+#[cfg(feature = "audio")]
+pub mod audio_chunks;
+#[cfg(feature = "batches")]
+pub mod batches;
+pub mod budget;
+mod de;
+pub mod logger;
+pub mod logit_bias;
+pub mod metrics;
+pub mod models;
+#[cfg(feature = "moderations")]
+pub mod moderations;
+#[cfg(feature = "chat")]
+pub mod chat;
+pub mod circuit_breaker;
+#[cfg(feature = "completions")]
+pub mod completions;
+#[cfg(feature = "chat")]
+pub mod conversation;
+pub mod credentials;
+#[cfg(feature = "completions")]
+pub mod edits;
+#[cfg(feature = "embeddings")]
+pub mod embeddings;
+pub mod envelope;
+pub mod error;
+pub mod failover;
+#[cfg(feature = "chat")]
+pub mod few_shot;
+#[cfg(feature = "files")]
+pub mod files;
+#[cfg(feature = "fine_tuning")]
+pub mod fine_tuning;
+#[cfg(feature = "images")]
+pub mod images;
+pub mod http;
+pub mod openrouter;
+#[cfg(any(
+    feature = "chat",
+    feature = "responses",
+    feature = "assistants",
+    feature = "vector_stores",
+    feature = "fine_tuning",
+    feature = "batches",
+    feature = "files",
+))]
+pub mod pagination;
+pub mod partial_json;
+pub mod provider;
+pub mod queue;
+#[cfg(feature = "embeddings")]
+pub mod rag;
+pub mod rate_limit;
+#[cfg(feature = "realtime")]
+pub mod realtime;
+#[cfg(feature = "responses")]
+pub mod responses;
+pub mod retry;
+pub mod shutdown;
+#[cfg(any(feature = "chat", feature = "completions"))]
+pub mod stop;
+#[cfg(any(feature = "chat", feature = "assistants"))]
+mod sse;
+#[cfg(feature = "chat")]
+pub mod structured;
+#[cfg(feature = "test_utils")]
+pub mod test_utils;
+#[cfg(feature = "assistants")]
+pub mod threads;
+pub mod tokens;
+pub mod tools;
+#[cfg(feature = "vector_stores")]
+pub mod vector_stores;
+pub mod versions;
+pub mod vision;
+pub mod webhooks;
+
+/// Builds a [Client] with network settings of the underlying [reqwest::Client],
+/// like a proxy or the root certificates of a corporate network, the server it talks to,
+/// and headers sent with every request. Create with [Client::builder].
+///
+/// Proxies from the `HTTP_PROXY` and `HTTPS_PROXY` environment variables are used unless a proxy is set
+/// or [no_proxy](ClientBuilder::no_proxy) is called.
+///
+/// ```no_run
+/// # let api_key = "";
+/// let pem = std::fs::read("corporate-ca.pem").unwrap();
+/// let client = openai_rust::Client::builder(api_key)
+///     .proxy("http://proxy.example.com:8080")
+///     .proxy_auth("alice", "hunter2")
+///     .root_certificate_pem(&pem)
+///     .build()
+///     .unwrap();
+/// ```
+///
+/// Invalid header values make [build](ClientBuilder::build) fail, like they make [Client::with_organization] fail.
+///
+/// ```
+/// # let api_key = "";
+/// let client = openai_rust::Client::builder(api_key)
+///     .organization("org_abc123")
+///     .project("proj_abc123")
+///     .header("X-Request-Source", "nightly-job")
+///     .timeout(std::time::Duration::from_secs(120))
+///     .build()
+///     .unwrap();
+/// ```
+///
+/// To reach Azure OpenAI through a gateway, set the [provider](ClientBuilder::provider) for the authentication
+/// and the `api-version`, and the [base URL](ClientBuilder::base_url) of the gateway:
+///
+/// ```
+/// # let api_key = "";
+/// use openai_rust::provider::Provider;
+/// let client = openai_rust::Client::builder(api_key)
+///     .provider(Provider::Azure {
+///         resource: "my-resource".to_owned(),
+///         deployment: "gpt-4o".to_owned(),
+///         api_version: "2024-06-01".to_owned(),
+///     })
+///     .base_url("https://gateway.example.com/openai/deployments/gpt-4o")
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Debug)]
+pub struct ClientBuilder {
+    api_key: String,
+    base_url: Option<String>,
+    provider: Option<provider::Provider>,
+    organization: Option<String>,
+    project: Option<String>,
+    headers: Vec<(String, String)>,
+    timeout: Option<std::time::Duration>,
+    proxy: Option<(String, Option<(String, String)>)>,
+    no_proxy: bool,
+    certificates: Vec<Certificate>,
+    built_in_roots: bool,
+}
+
+#[derive(Debug)]
+enum Certificate {
+    Pem(Vec<u8>),
+    Der(Vec<u8>),
+}
+
+impl ClientBuilder {
+    /// Send requests to another server implementing the OpenAI API, see [Client::with_base_url].
+    pub fn base_url(mut self, url: &str) -> ClientBuilder {
+        self.base_url = Some(url.to_owned());
+        self
+    }
+
+    /// Target another vendor of an OpenAI compatible API, see [Client::with_provider].
+    /// A [base URL](ClientBuilder::base_url) replaces the one of the provider.
+    pub fn provider(mut self, provider: provider::Provider) -> ClientBuilder {
+        self.provider = Some(provider);
+        self
+    }
+
+    /// Bill requests to an organization, see [Client::with_organization].
+    pub fn organization(mut self, organization: &str) -> ClientBuilder {
+        self.organization = Some(organization.to_owned());
+        self
+    }
+
+    /// Bill requests to a project, see [Client::with_project].
+    pub fn project(mut self, project: &str) -> ClientBuilder {
+        self.project = Some(project.to_owned());
+        self
+    }
+
+    /// Send a header with every request, like one expected by a gateway.
+    pub fn header(mut self, name: &str, value: &str) -> ClientBuilder {
+        self.headers.push((name.to_owned(), value.to_owned()));
+        self
+    }
+
+    /// Fail requests that take longer than this, from connecting until the whole response has been read.
+    ///
+    /// This includes reading [streamed](Client::create_chat_stream) responses, so allow enough time for long answers.
+    pub fn timeout(mut self, timeout: std::time::Duration) -> ClientBuilder {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Send all requests through a proxy, like `http://proxy.example.com:8080` or `socks5://127.0.0.1:1080`.
+    /// Credentials can be part of the URL, or set with [proxy_auth](ClientBuilder::proxy_auth).
+    pub fn proxy(mut self, url: &str) -> ClientBuilder {
+        self.proxy = Some((url.to_owned(), None));
+        self
+    }
+
+    /// Authenticate to the proxy with basic authentication.
+    pub fn proxy_auth(mut self, username: &str, password: &str) -> ClientBuilder {
+        if let Some((_, auth)) = &mut self.proxy {
+            *auth = Some((username.to_owned(), password.to_owned()));
+        }
+        self
+    }
+
+    /// Connect directly, ignoring the proxy environment variables.
+    pub fn no_proxy(mut self) -> ClientBuilder {
+        self.no_proxy = true;
+        self
+    }
+
+    /// Trust an extra root certificate in PEM format, like one of a TLS-inspecting proxy.
+    pub fn root_certificate_pem(mut self, pem: &[u8]) -> ClientBuilder {
+        self.certificates.push(Certificate::Pem(pem.to_owned()));
+        self
+    }
+
+    /// Trust an extra root certificate in DER format.
+    pub fn root_certificate_der(mut self, der: &[u8]) -> ClientBuilder {
+        self.certificates.push(Certificate::Der(der.to_owned()));
+        self
+    }
+
+    /// Trust only the root certificates added to the builder, not the ones built into the system or the TLS library.
+    pub fn only_added_root_certificates(mut self) -> ClientBuilder {
+        self.built_in_roots = false;
+        self
+    }
+
+    /// Build the client, failing if the base URL, a header, the proxy URL or a certificate is not valid.
+    pub fn build(self) -> Result<Client> {
+        use reqwest::header::HeaderName;
+        let base_url = match &self.base_url {
+            Some(url) => Some(reqwest::Url::parse(url).map_err(|e| anyhow!("invalid base URL {:?}: {}", url, e))?),
+            None => None,
+        };
+        let organization = self.organization.iter().map(|id| ("OpenAI-Organization", id));
+        let project = self.project.iter().map(|id| ("OpenAI-Project", id));
+        let mut headers = reqwest::header::HeaderMap::new();
+        let custom = self.headers.iter().map(|(name, value)| (name.as_str(), value));
+        for (name, value) in custom.chain(organization).chain(project) {
+            let name = HeaderName::from_bytes(name.as_bytes()).map_err(|e| anyhow!("invalid header name {:?}: {}", name, e))?;
+            let value = header_value(name.as_str(), value)?;
+            headers.insert(name, value);
+        }
+        let mut builder = reqwest::ClientBuilder::new()
+            .user_agent(USER_AGENT)
+            .tls_built_in_root_certs(self.built_in_roots);
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+        if let Some((url, auth)) = &self.proxy {
+            let mut proxy = reqwest::Proxy::all(url)?;
+            if let Some((username, password)) = auth {
+                proxy = proxy.basic_auth(username, password);
+            }
+            builder = builder.proxy(proxy);
+        } else if self.no_proxy {
+            builder = builder.no_proxy();
+        }
+        for certificate in &self.certificates {
+            builder = builder.add_root_certificate(match certificate {
+                Certificate::Pem(pem) => reqwest::Certificate::from_pem(pem)?,
+                Certificate::Der(der) => reqwest::Certificate::from_der(der)?,
+            });
+        }
+        let mut client = Client::new_with_client(&self.api_key, builder.build()?);
+        client.headers.extend(headers);
+        if let Some(provider) = self.provider {
+            client = client.with_provider(provider)?;
+        }
+        if let Some(base_url) = base_url {
+            client = client.with_base_url(base_url);
+        }
+        Ok(client)
+    }
 }
 
-pub mod models;
-pub mod chat;
-pub mod completions;
-pub mod edits;
-pub mod embeddings;
-pub mod images;
+impl Client {
+    /// Create a new client.
+    /// This will automatically build a [reqwest::Client] used internally,
+    /// which sends a `User-Agent` like `openai-rust/1.5.1`.
+    pub fn new(api_key: &str) -> Client {
+        let req_client = reqwest::ClientBuilder::new().user_agent(USER_AGENT).build().unwrap();
+        Client::new_with_client(api_key, req_client)
+    }
+
+    /// Create a client for another server implementing the OpenAI API, like LocalAI or a gateway.
+    ///
+    /// ```
+    /// # let api_key = "";
+    /// let url = reqwest::Url::parse("http://localhost:8080/v1").unwrap();
+    /// let client = openai_rust::Client::new_with_base_url(api_key, url);
+    /// ```
+    pub fn new_with_base_url(api_key: &str, base_url: reqwest::Url) -> Client {
+        Client::new(api_key).with_base_url(base_url)
+    }
+
+    /// Configure the network settings of a new client, like a proxy or custom root certificates.
+    /// See [ClientBuilder].
+    pub fn builder(api_key: &str) -> ClientBuilder {
+        ClientBuilder {
+            api_key: api_key.to_owned(),
+            base_url: None,
+            provider: None,
+            organization: None,
+            project: None,
+            headers: vec![],
+            timeout: None,
+            proxy: None,
+            no_proxy: false,
+            certificates: vec![],
+            built_in_roots: true,
+        }
+    }
+
+    /// Create a client which talks HTTP/3 to the API from the first request, without trying HTTP/1 or HTTP/2.
+    ///
+    /// On networks with packet loss, QUIC can shorten the time to the first token of streamed responses.
+    /// Requests fail if the server or a proxy on the way does not support HTTP/3.
+    ///
+    /// Requires the `http3` feature, and building with `RUSTFLAGS="--cfg reqwest_unstable"` because
+    /// HTTP/3 support in reqwest is still experimental.
+    #[cfg(all(reqwest_unstable, feature = "http3"))]
+    pub fn new_http3(api_key: &str) -> Client {
+        let req_client = reqwest::ClientBuilder::new()
+            .user_agent(USER_AGENT)
+            .http3_prior_knowledge()
+            .build()
+            .unwrap();
+        Client::new_with_client(api_key, req_client)
+    }
+
+    /// Build a client using your own [reqwest::Client].
+    ///
+    /// The `User-Agent` of the [reqwest::Client] is kept, or none is sent if it has none.
+    pub fn new_with_client(api_key: &str, req_client: reqwest::Client) -> Client {
+        Client {
+            req_client,
+            http_client: None,
+            key: api_key.to_owned(),
+            credentials: None,
+            failover: None,
+            budget: None,
+            logger: None,
+            metrics: None,
+            in_flight: Default::default(),
+            base_url: BASE_URL.clone(),
+            headers: reqwest::header::HeaderMap::new(),
+            provider: provider::Provider::OpenAI,
+            endpoint_urls: std::collections::HashMap::new(),
+            versions: Default::default(),
+            circuit_breaker: None,
+            queue: None,
+            rate_limiter: None,
+            retry: None,
+            priority: queue::Priority::default(),
+            #[cfg(feature = "moderations")]
+            moderation: None,
+            #[cfg(feature = "chat")]
+            auto_max_tokens: false,
+            #[cfg(feature = "chat")]
+            auto_continue: 0,
+            #[cfg(feature = "chat")]
+            json_repair: 0,
+        }
+    }
+
+    /// Create a client for [OpenRouter](openrouter).
+    ///
+    /// ```
+    /// # let api_key = "";
+    /// let client = openai_rust::Client::new_openrouter(api_key)
+    ///     .with_openrouter_app("https://example.com", "My cool program")
+    ///     .unwrap();
+    /// ```
+    pub fn new_openrouter(api_key: &str) -> Client {
+        Client::new(api_key).with_base_url(reqwest::Url::parse(openrouter::BASE_URL).unwrap())
+    }
+
+    /// Identify your app to OpenRouter with the `HTTP-Referer` and `X-Title` headers,
+    /// which are used for the rankings on openrouter.ai.
+    ///
+    /// Fails if the URL or title contain characters that are not allowed in a header.
+    pub fn with_openrouter_app(mut self, referer: &str, title: &str) -> Result<Client> {
+        let referer = header_value("HTTP-Referer", referer)?;
+        let title = header_value("X-Title", title)?;
+        self.headers.insert("HTTP-Referer", referer);
+        self.headers.insert("X-Title", title);
+        Ok(self)
+    }
+
+    /// Append an identifier of your application to the `User-Agent`, like `openai-rust/1.5.1 my-app/2.0`,
+    /// so its requests can be told apart in the logs of gateways and proxies.
+    ///
+    /// This replaces the `User-Agent` of a [reqwest::Client] passed to [Client::new_with_client].
+    /// Fails if the identifier contains characters that are not allowed in a header.
+    ///
+    /// ```
+    /// # let api_key = "";
+    /// let client = openai_rust::Client::new(api_key).with_app_identifier("my-app/2.0").unwrap();
+    /// ```
+    pub fn with_app_identifier(mut self, app: &str) -> Result<Client> {
+        let user_agent = header_value("User-Agent", &format!("{} {}", USER_AGENT, app))?;
+        self.headers.insert(reqwest::header::USER_AGENT, user_agent);
+        Ok(self)
+    }
+
+    /// Bill requests to an organization, for API keys that belong to several organizations.
+    ///
+    /// Fails if the ID contains characters that are not allowed in a header.
+    pub fn with_organization(mut self, organization: &str) -> Result<Client> {
+        self.headers.insert("OpenAI-Organization", header_value("OpenAI-Organization", organization)?);
+        Ok(self)
+    }
+
+    /// Bill requests to a project, for API keys that have access to several projects.
+    ///
+    /// To bill a single request to another project, such as in a service with a project per customer,
+    /// set the project on a clone of the client, which shares the queue and rate limits with the original.
+    ///
+    /// Fails if the ID contains characters that are not allowed in a header.
+    ///
+    /// ```no_run
+    /// # tokio_test::block_on(async {
+    /// # let api_key = "";
+    /// # let args = openai_rust::chat::ChatArguments::new("gpt-4o", vec![]);
+    /// let client = openai_rust::Client::new(api_key).with_organization("org_abc123").unwrap();
+    /// let res = client.clone().with_project("proj_customer_a").unwrap().create_chat(args).await.unwrap();
+    /// # })
+    /// ```
+    pub fn with_project(mut self, project: &str) -> Result<Client> {
+        self.headers.insert("OpenAI-Project", header_value("OpenAI-Project", project)?);
+        Ok(self)
+    }
+
+    /// Send requests to another server implementing the OpenAI API, such as [OpenRouter](openrouter).
+    ///
+    /// Endpoint paths are appended to the base URL, which defaults to `https://api.openai.com/v1`.
+    ///
+    /// ```
+    /// # let api_key = "";
+    /// let url = reqwest::Url::parse("http://localhost:8080/v1").unwrap();
+    /// let client = openai_rust::Client::new(api_key).with_base_url(url);
+    /// ```
+    pub fn with_base_url(mut self, base_url: reqwest::Url) -> Client {
+        self.base_url = base_url;
+        self
+    }
+
+    /// Send requests for a single endpoint to another server, like embeddings to a local server and chat to OpenAI.
+    ///
+    /// The `endpoint` is matched against the start of the path of each request, like `/embeddings` or `/audio`.
+    /// When several overrides match, the longest one is used.
+    /// The path is appended to the given base URL, and the request is sent with the same API key.
+    ///
+    /// ```
+    /// # let api_key = "";
+    /// let local = reqwest::Url::parse("http://localhost:11434/v1").unwrap();
+    /// let client = openai_rust::Client::new(api_key).with_endpoint_url("/embeddings", local);
+    /// ```
+    pub fn with_endpoint_url(mut self, endpoint: &str, base_url: reqwest::Url) -> Client {
+        self.endpoint_urls.insert(endpoint.to_owned(), base_url);
+        self
+    }
+
+    /// Pin the version of the endpoints starting with `endpoint` with a header, like `OpenAI-Beta`.
+    ///
+    /// This replaces the value of the header set by the crate, see [versions].
+    /// Fails if the header name or value contain characters that are not allowed in a header.
+    ///
+    /// ```
+    /// # let api_key = "";
+    /// let client = openai_rust::Client::new(api_key)
+    ///     .with_api_version("/assistants", "OpenAI-Beta", "assistants=v3")
+    ///     .unwrap();
+    /// ```
+    pub fn with_api_version(mut self, endpoint: &str, header: &str, value: &str) -> Result<Client> {
+        let name = reqwest::header::HeaderName::from_bytes(header.as_bytes())
+            .map_err(|e| anyhow!("invalid header name {:?}: {}", header, e))?;
+        let value = header_value(header, value)?;
+        self.versions.set(endpoint, name, value);
+        Ok(self)
+    }
+
+    /// Fetch the API key from a [credentials::CredentialsProvider] instead of using a fixed key.
+    ///
+    /// The key passed to [Client::new] is ignored. The key is cached until it expires, see [credentials].
+    ///
+    /// ```
+    /// use openai_rust::credentials::Credentials;
+    /// let client = openai_rust::Client::new("")
+    ///     .with_credentials(|| async { Ok(Credentials::new(std::env::var("OPENAI_API_KEY")?)) });
+    /// ```
+    pub fn with_credentials(mut self, provider: impl credentials::CredentialsProvider) -> Client {
+        self.credentials = Some(Arc::new(credentials::CachedCredentials::new(provider)));
+        self
+    }
+
+    /// Send requests through an [http::HttpClient] instead of the [reqwest::Client] of the client,
+    /// like a `reqwest_middleware::ClientWithMiddleware` or a mock, see [http].
+    ///
+    /// The requests are still built by the [reqwest::Client], but its default headers,
+    /// like the `User-Agent`, and its timeout are not applied.
+    pub fn with_http_client(mut self, http_client: impl http::HttpClient) -> Client {
+        self.http_client = Some(Arc::new(http_client));
+        self
+    }
+
+    /// Target another vendor of an OpenAI compatible API.
+    ///
+    /// This sets the base URL and the way the API key is sent,
+    /// and strips parameters the vendor does not support from all requests.
+    ///
+    /// Fails if the provider has no valid base URL, see [provider::Provider::base_url].
+    ///
+    /// ```
+    /// # let api_key = "";
+    /// use openai_rust::provider::Provider;
+    /// let client = openai_rust::Client::new(api_key)
+    ///     .with_provider(Provider::Azure {
+    ///         resource: "my-resource".to_owned(),
+    ///         deployment: "gpt-4o".to_owned(),
+    ///         api_version: "2024-06-01".to_owned(),
+    ///     })
+    ///     .unwrap();
+    /// ```
+    pub fn with_provider(mut self, provider: provider::Provider) -> Result<Client> {
+        self.base_url = provider.base_url()?;
+        self.provider = provider;
+        Ok(self)
+    }
+
+    /// Fail over to other endpoints when the base URL is down, see [failover].
+    ///
+    /// ```
+    /// # let api_key = "";
+    /// use openai_rust::failover::{Endpoint, Failover};
+    /// let backup = reqwest::Url::parse("https://backup.example.com/v1").unwrap();
+    /// let client = openai_rust::Client::new(api_key).with_failover(
+    ///     Failover::new().fallback(Endpoint::new(Default::default(), api_key).unwrap().base_url(backup)),
+    /// );
+    /// ```
+    pub fn with_failover(mut self, failover: failover::Failover) -> Client {
+        self.failover = Some(Arc::new(failover));
+        self
+    }
+
+    /// The [failover::Failover] configured with [Client::with_failover].
+    pub fn failover(&self) -> Option<&failover::Failover> {
+        self.failover.as_deref()
+    }
+
+    /// The [provider::Provider] configured with [Client::with_provider].
+    pub fn provider(&self) -> &provider::Provider {
+        &self.provider
+    }
+
+    /// Limit the amount of tokens or dollars this client can spend.
+    ///
+    /// Usage is counted from the responses of the chat, completion, edit, embeddings and responses endpoints.
+    /// Streamed chat completions only report usage with [include_usage](chat::StreamOptions::include_usage),
+    /// so without it they are checked against the budget, but not counted.
+    ///
+    /// ```
+    /// # let api_key = "";
+    /// use openai_rust::budget::Budget;
+    /// let client = openai_rust::Client::new(api_key)
+    ///     .with_budget(Budget::tokens_per_hour(100_000).defer());
+    /// ```
+    pub fn with_budget(mut self, budget: budget::Budget) -> Client {
+        self.budget = Some(Arc::new(budget));
+        self
+    }
+
+    /// Check the user messages of chat requests with the moderation endpoint before they are sent.
+    ///
+    /// When a message is flagged, the chat request is not sent and fails with a [moderations::Flagged] error.
+    /// See [moderations] for an example.
+    #[cfg(feature = "moderations")]
+    pub fn with_moderation(self) -> Client {
+        self.with_moderation_model(moderations::DEFAULT_MODEL)
+    }
+
+    /// Like [Client::with_moderation], with another moderation model.
+    #[cfg(feature = "moderations")]
+    pub fn with_moderation_model(mut self, model: &str) -> Client {
+        self.moderation = Some(model.to_owned());
+        self
+    }
+
+    /// Set the maximum tokens of chat requests without one to what fits in the context of the model after the prompt,
+    /// so long prompts don't fail with "context length exceeded".
+    ///
+    /// Only models known to [models::info] are limited. Reasoning models get `max_completion_tokens`, others `max_tokens`.
+    /// When the prompt leaves no room for a completion, the maximum is left unset and the API reports the context length.
+    #[cfg(feature = "chat")]
+    pub fn with_auto_max_tokens(mut self) -> Client {
+        self.auto_max_tokens = true;
+        self
+    }
+
+    /// Ask the model to continue replies that were cut off by the maximum amount of tokens, up to `max_continuations` times.
+    ///
+    /// [Client::create_chat] then returns the reply and its continuations as a single completion, with their usage added up.
+    /// This includes the replies of a [Conversation](conversation::Conversation).
+    /// The model is asked to continue with [chat::CONTINUE_PROMPT].
+    ///
+    /// ```
+    /// # let api_key = "";
+    /// let client = openai_rust::Client::new(api_key).with_auto_continue(3);
+    /// ```
+    #[cfg(feature = "chat")]
+    pub fn with_auto_continue(mut self, max_continuations: u32) -> Client {
+        self.auto_continue = max_continuations;
+        self
+    }
+
+    /// Validate the replies of chat requests in JSON mode or with a JSON schema, and ask the model to correct
+    /// invalid JSON up to `max_retries` times.
+    ///
+    /// Replies are checked with [structured::validate]. The model is shown what is wrong with its reply,
+    /// and when it still is not valid after the retries, the request fails with a [structured::InvalidOutput]
+    /// error holding every attempt. Refusals are returned without retrying.
+    ///
+    /// ```no_run
+    /// # tokio_test::block_on(async {
+    /// # let api_key = "";
+    /// # let args = openai_rust::chat::ChatArguments::new("gpt-4o", vec![]);
+    /// use openai_rust::structured::InvalidOutput;
+    /// let client = openai_rust::Client::new(api_key).with_json_repair(2);
+    /// if let Err(e) = client.create_chat(args).await {
+    ///     if let Some(invalid) = e.downcast_ref::<InvalidOutput>() {
+    ///         for attempt in &invalid.attempts {
+    ///             println!("{}: {:?}", attempt.content, attempt.errors);
+    ///         }
+    ///     }
+    /// }
+    /// # })
+    /// ```
+    #[cfg(feature = "chat")]
+    pub fn with_json_repair(mut self, max_retries: u32) -> Client {
+        self.json_repair = max_retries;
+        self
+    }
+
+    /// Report every request and the tokens of every completion to a [metrics::MetricsSink],
+    /// like the [PrometheusMetrics](metrics::PrometheusMetrics) of the `prometheus` feature.
+    pub fn with_metrics(mut self, metrics: impl metrics::MetricsSink + 'static) -> Client {
+        self.metrics = Some(Arc::new(metrics));
+        self
+    }
+
+    /// Stop sending new requests and wait for the requests in flight to finish, for example before a deploy.
+    ///
+    /// A request is in flight until its response is read, which for streams is until the stream ends or is dropped.
+    /// Requests still in flight after the timeout are aborted. New requests and aborted requests fail with a
+    /// [shutdown::ShuttingDown] error. This shuts down all clones of the client.
+    ///
+    /// Returns whether all requests finished before the timeout.
+    ///
+    /// ```no_run
+    /// # tokio_test::block_on(async {
+    /// # let client = openai_rust::Client::new("");
+    /// use std::time::Duration;
+    /// if !client.shutdown(Duration::from_secs(30)).await {
+    ///     eprintln!("aborted unfinished requests");
+    /// }
+    /// # })
+    /// ```
+    pub async fn shutdown(&self, timeout: std::time::Duration) -> bool {
+        self.in_flight.shutdown(timeout).await
+    }
+
+    /// The [budget::Budget] configured with [Client::with_budget].
+    pub fn budget(&self) -> Option<&budget::Budget> {
+        self.budget.as_deref()
+    }
+
+    /// Print requests and responses for debugging.
+    ///
+    /// ```
+    /// # let api_key = "";
+    /// use openai_rust::logger::Logger;
+    /// let client = openai_rust::Client::new(api_key)
+    ///     .with_logger(Logger::new().redact_content(true));
+    /// ```
+    pub fn with_logger(mut self, logger: logger::Logger) -> Client {
+        self.logger = Some(Arc::new(logger));
+        self
+    }
+
+    /// Fail fast while the API is failing.
+    ///
+    /// ```
+    /// # let api_key = "";
+    /// use std::time::Duration;
+    /// use openai_rust::circuit_breaker::CircuitBreaker;
+    /// let client = openai_rust::Client::new(api_key)
+    ///     .with_circuit_breaker(CircuitBreaker::new(5, Duration::from_secs(30)));
+    /// ```
+    pub fn with_circuit_breaker(mut self, circuit_breaker: circuit_breaker::CircuitBreaker) -> Client {
+        self.circuit_breaker = Some(Arc::new(circuit_breaker));
+        self
+    }
+
+    /// The [circuit_breaker::CircuitBreaker] configured with [Client::with_circuit_breaker].
+    pub fn circuit_breaker(&self) -> Option<&circuit_breaker::CircuitBreaker> {
+        self.circuit_breaker.as_deref()
+    }
+
+    /// Limit the amount of concurrent requests, sending waiting requests by priority.
+    ///
+    /// Share the queue between a client for interactive requests and one for background jobs,
+    /// so the interactive requests are sent first when the queue is full.
+    /// A request holds its place until the response starts arriving.
+    ///
+    /// ```
+    /// # let api_key = "";
+    /// use openai_rust::queue::{Priority, RequestQueue};
+    /// let queue = RequestQueue::new(8);
+    /// let chat = openai_rust::Client::new(api_key).with_queue(queue.clone());
+    /// let jobs = openai_rust::Client::new(api_key)
+    ///     .with_queue(queue)
+    ///     .with_priority(Priority::Background);
+    /// ```
+    pub fn with_queue(mut self, queue: queue::RequestQueue) -> Client {
+        self.queue = Some(queue);
+        self
+    }
+
+    /// The [queue::RequestQueue] configured with [Client::with_queue].
+    pub fn queue(&self) -> Option<&queue::RequestQueue> {
+        self.queue.as_ref()
+    }
+
+    /// The priority of the requests of this client in its [queue](Client::with_queue)
+    /// and [rate limiter](Client::with_rate_limiter).
+    /// Defaults to [queue::Priority::Interactive].
+    pub fn with_priority(mut self, priority: queue::Priority) -> Client {
+        self.priority = priority;
+        self
+    }
+
+    /// Delay requests to stay under the rate limits of your account.
+    ///
+    /// Requests to the chat, completion, edit, embeddings and responses endpoints are limited,
+    /// and wait for their turn by the [priority](Client::with_priority) of the client.
+    /// Clones of the client share the rate limiter, so set a lower priority on a clone for background jobs.
+    /// Tokens are estimated, unless the rate limiter has a [tokenizer](rate_limit::RateLimiter::tokenizer).
+    ///
+    /// ```
+    /// # let api_key = "";
+    /// use openai_rust::rate_limit::{Limits, RateLimiter};
+    /// let client = openai_rust::Client::new(api_key)
+    ///     .with_rate_limiter(RateLimiter::new().limit("gpt-4o", Limits::new(500, 30_000)));
+    /// ```
+    pub fn with_rate_limiter(mut self, rate_limiter: rate_limit::RateLimiter) -> Client {
+        self.rate_limiter = Some(Arc::new(rate_limiter));
+        self
+    }
+
+    /// The [rate_limit::RateLimiter] configured with [Client::with_rate_limiter].
+    pub fn rate_limiter(&self) -> Option<&rate_limit::RateLimiter> {
+        self.rate_limiter.as_deref()
+    }
+
+    /// Retry requests that hit a rate limit, a server error or a connection error, see [retry].
+    ///
+    /// ```
+    /// # let api_key = "";
+    /// use openai_rust::retry::RetryPolicy;
+    /// let client = openai_rust::Client::new(api_key).with_retries(RetryPolicy::new().max_attempts(5));
+    /// ```
+    pub fn with_retries(mut self, policy: retry::RetryPolicy) -> Client {
+        self.retry = Some(policy);
+        self
+    }
+
+    /// The URL of an endpoint, relative to the base URL or the override set with [Client::with_endpoint_url].
+    fn url(&self, path: &str) -> reqwest::Url {
+        let endpoint_url = self
+            .endpoint_urls
+            .iter()
+            .filter(|(endpoint, _)| path.starts_with(endpoint.as_str()))
+            .max_by_key(|(endpoint, _)| endpoint.len())
+            .map(|(_, url)| url);
+        let mut url = endpoint_url.unwrap_or(&self.base_url).clone();
+        let base = url.path().trim_end_matches('/').to_owned();
+        url.set_path(&(base + path));
+        if endpoint_url.is_none() {
+            for (key, value) in self.provider.query() {
+                url.query_pairs_mut().append_pair(key, &value);
+            }
+        }
+        url
+    }
+
+    /// Send a request, which stays in flight until its response is read, see [Client::shutdown].
+    async fn send(&self, req: reqwest::RequestBuilder) -> anyhow::Result<reqwest::Response> {
+        let guard = self.in_flight.enter()?;
+        let aborted = std::pin::pin!(self.in_flight.aborted());
+        let res = match futures_util::future::select(std::pin::pin!(self.send_with_retries(req)), aborted).await {
+            futures_util::future::Either::Left((res, _)) => res?,
+            futures_util::future::Either::Right(_) => return Err(shutdown::ShuttingDown.into()),
+        };
+        let mut tracked = ::http::Response::builder().status(res.status()).version(res.version());
+        if let Some(headers) = tracked.headers_mut() {
+            *headers = res.headers().clone();
+        }
+        let body = reqwest::Body::wrap_stream(shutdown::TrackedBody::new(res, guard));
+        Ok(tracked.body(body)?.into())
+    }
+
+    /// Send a request, and send it again while it fails and the [retry::RetryPolicy] allows it.
+    async fn send_with_retries(&self, mut req: reqwest::RequestBuilder) -> anyhow::Result<reqwest::Response> {
+        let Some(policy) = &self.retry else {
+            return self.send_authenticated(req).await;
+        };
+        let mut attempt = 1;
+        loop {
+            let retry = match req.try_clone() {
+                Some(retry) if policy.retries_after(attempt) => retry,
+                _ => return self.send_authenticated(req).await,
+            };
+            let retry_after = match self.send_authenticated(req).await {
+                Ok(res) if retry::retryable_status(res.status()) => {
+                    let error = self.read_error(res).await;
+                    if !error.is_retryable() {
+                        return Err(error.into());
+                    }
+                    error.retry_after()
+                }
+                Ok(res) => return Ok(res),
+                Err(e) if error::is_retryable(&e) => error::retry_after(&e),
+                Err(e) => return Err(e),
+            };
+            tokio::time::sleep(policy.delay(attempt, retry_after)).await;
+            req = retry;
+            attempt += 1;
+        }
+    }
+
+    /// Authenticate and send a request.
+    async fn send_authenticated(&self, req: reqwest::RequestBuilder) -> anyhow::Result<reqwest::Response> {
+        let key = match &self.credentials {
+            Some(credentials) => std::borrow::Cow::Owned(credentials.api_key().await?),
+            None => std::borrow::Cow::Borrowed(&self.key),
+        };
+        let mut req = req.headers(self.headers.clone()).build()?;
+        self.pin_versions(&mut req);
+        let _permit = match &self.queue {
+            Some(queue) => Some(queue.acquire(self.priority).await),
+            None => None,
+        };
+        let _probe = match &self.circuit_breaker {
+            Some(breaker) => Some(breaker.check()?),
+            None => None,
+        };
+        let started = std::time::Instant::now();
+        let (method, path) = (req.method().to_string(), req.url().path().to_owned());
+        let res = match &self.failover {
+            Some(failover) => self.execute_with_failover(failover, req, &key).await,
+            None => self.execute(req, &self.provider, &key).await,
+        };
+        if let Some(sink) = &self.metrics {
+            sink.request(&metrics::RequestMetrics {
+                endpoint: metrics::endpoint(&path, self.base_url.path()),
+                method,
+                status: res.as_ref().ok().map(|res| res.status().as_u16()),
+                duration: started.elapsed(),
+            });
+        }
+        if let Some(breaker) = &self.circuit_breaker {
+            breaker.record(&res);
+        }
+        if let (Some(credentials), Ok(res)) = (&self.credentials, &res) {
+            if res.status() == reqwest::StatusCode::UNAUTHORIZED {
+                credentials.invalidate(&key).await;
+            }
+        }
+        res
+    }
+
+    /// Add the version headers of the endpoint of a request.
+    fn pin_versions(&self, req: &mut reqwest::Request) {
+        let path = req.url().path().to_owned();
+        let relative = std::iter::once(&self.base_url)
+            .chain(self.endpoint_urls.values())
+            .filter(|url| url.origin() == req.url().origin())
+            .find_map(|url| path.strip_prefix(url.path().trim_end_matches('/')));
+        if let Some(relative) = relative {
+            self.versions.apply(relative, req);
+        }
+    }
+
+    /// Authenticate a request for a provider and send it.
+    async fn execute(&self, mut req: reqwest::Request, provider: &provider::Provider, key: &str) -> anyhow::Result<reqwest::Response> {
+        provider.authenticate(&mut req, key)?;
+        provider.strip_unsupported(&mut req);
+        if let Some(logger) = &self.logger {
+            logger.log_request(&req);
+        }
+        match &self.http_client {
+            Some(http_client) => http_client.execute(req).await,
+            None => Ok(self.req_client.execute(req).await?),
+        }
+    }
+
+    /// Send a request to the first healthy endpoint that does not fail.
+    async fn execute_with_failover(
+        &self,
+        failover: &failover::Failover,
+        req: reqwest::Request,
+        key: &str,
+    ) -> anyhow::Result<reqwest::Response> {
+        if req.try_clone().is_none() {
+            return self.execute(req, &self.provider, key).await;
+        }
+        let mut last = None;
+        for endpoint in 0..=failover.fallbacks().len() {
+            let mut attempt = req.try_clone().unwrap();
+            let (provider, key) = match endpoint.checked_sub(1) {
+                None => (&self.provider, key),
+                Some(i) => {
+                    let fallback = &failover.fallbacks()[i];
+                    let Some(url) = failover::Failover::rebase(req.url(), &self.base_url, &self.provider, fallback) else {
+                        break;
+                    };
+                    *attempt.url_mut() = url;
+                    (&fallback.provider, fallback.api_key.as_str())
+                }
+            };
+            let health = failover.health(endpoint);
+            let Ok(_probe) = health.check() else {
+                continue;
+            };
+            let res = self.execute(attempt, provider, key).await;
+            health.record(&res);
+            if circuit_breaker::is_failure(&res) != Some(true) {
+                return res;
+            }
+            last = Some(res);
+        }
+        last.unwrap_or_else(|| Err(anyhow!("all endpoints are unhealthy")))
+    }
+
+    /// Deserialize the body of a successful response, or turn it into an error.
+    async fn read_json<T: serde::de::DeserializeOwned>(&self, res: reqwest::Response) -> Result<T> {
+        let text = self.read_text(res).await?;
+        Ok(serde_json::from_str(&text)?)
+    }
+
+    /// Deserialize the body of a successful response together with its status and headers.
+    #[cfg(any(
+        feature = "chat",
+        feature = "completions",
+        feature = "embeddings",
+        feature = "responses",
+    ))]
+    async fn read_envelope<T: serde::de::DeserializeOwned>(
+        &self,
+        res: reqwest::Response,
+    ) -> Result<envelope::ResponseEnvelope<T>> {
+        let status = res.status();
+        let headers = res.headers().clone();
+        Ok(envelope::ResponseEnvelope {
+            data: self.read_json(res).await?,
+            status,
+            headers,
+        })
+    }
+
+    /// Read the body of a successful response as text, or turn it into an error.
+    async fn read_text(&self, res: reqwest::Response) -> Result<String> {
+        if res.status() != 200 {
+            return Err(self.read_error(res).await);
+        }
+        let status = res.status();
+        let text = res.text().await?;
+        if let Some(logger) = &self.logger {
+            logger.log_response(status, &text);
+        }
+        Ok(text)
+    }
+
+    /// The [error::ApiError] of a response that failed.
+    async fn read_error(&self, res: reqwest::Response) -> error::Error {
+        let status = res.status();
+        let headers = res.headers().clone();
+        let text = match res.text().await {
+            Ok(text) => text,
+            Err(e) => return e.into(),
+        };
+        if let Some(logger) = &self.logger {
+            logger.log_response(status, &text);
+        }
+        error::ApiError::new(status, headers, text).into()
+    }
+
+    /// Stream the items of a list endpoint, fetching the next page when needed.
+    #[cfg(any(
+        feature = "chat",
+        feature = "responses",
+        feature = "assistants",
+        feature = "vector_stores",
+        feature = "fine_tuning",
+        feature = "batches",
+        feature = "files",
+    ))]
+    fn paginate<'a, T: serde::de::DeserializeOwned + 'a>(
+        &'a self,
+        path: String,
+        query: Vec<(String, String)>,
+        options: pagination::ListOptions,
+    ) -> impl futures_core::Stream<Item = Result<T>> + Unpin + 'a {
+        use futures_util::{stream, TryStreamExt};
+        use pagination::Cursor;
+        Box::pin(stream::try_unfold(options.cursor(), move |cursor| {
+            let mut url = self.url(&path);
+            url.query_pairs_mut().extend_pairs(&query).extend_pairs(options.query());
+            async move {
+                match &cursor {
+                    Cursor::Start => {}
+                    Cursor::After(after) => {
+                        url.query_pairs_mut().append_pair("after", after);
+                    }
+                    Cursor::Before(before) => {
+                        url.query_pairs_mut().append_pair("before", before);
+                    }
+                    Cursor::Done => return Ok::<_, error::Error>(None),
+                };
+                let res = self.send(self.req_client.get(url)).await?;
+                let mut page: pagination::Page<serde_json::Value> = self.read_json(res).await?;
+                page.fill_ids();
+                let next = cursor.next(&page);
+                let items = page.data.into_iter().map(|item| Ok::<T, error::Error>(serde_json::from_value(item)?));
+                Ok(Some((stream::iter(items), next)))
+            }
+        })
+        .try_flatten())
+    }
+
+    /// Pass through a successful streaming response, or turn it into an error.
+    #[cfg(any(feature = "chat", feature = "audio", feature = "assistants", feature = "files"))]
+    async fn check_stream(&self, res: reqwest::Response) -> Result<reqwest::Response> {
+        let status = res.status();
+        if status != 200 {
+            return Err(self.read_error(res).await);
+        }
+        if let Some(logger) = &self.logger {
+            logger.log_stream(status);
+        }
+        Ok(res)
+    }
+
+    #[cfg(any(
+        feature = "chat",
+        feature = "completions",
+        feature = "embeddings",
+        feature = "responses",
+    ))]
+    async fn acquire_budget(&self) -> anyhow::Result<()> {
+        if let Some(budget) = &self.budget {
+            budget.acquire().await?;
+        }
+        Ok(())
+    }
+
+    /// Wait for room in the rate limits for the tokens of a request,
+    /// which `tokens` counts using a function counting the tokens of a text.
+    #[cfg(any(
+        feature = "chat",
+        feature = "completions",
+        feature = "embeddings",
+        feature = "responses",
+    ))]
+    async fn acquire_rate_limit(
+        &self,
+        model: &str,
+        tokens: impl FnOnce(&dyn Fn(&str) -> usize) -> usize,
+    ) -> anyhow::Result<()> {
+        if let Some(limiter) = &self.rate_limiter {
+            let tokens = tokens(&|text| limiter.count_tokens(text));
+            limiter
+                .acquire(model, tokens.try_into().unwrap_or(u32::MAX), self.priority)
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Fail with [moderations::Flagged] if a user message is flagged, when enabled with [Client::with_moderation].
+    #[cfg(all(feature = "chat", feature = "moderations"))]
+    async fn moderate(&self, messages: &[chat::Message]) -> anyhow::Result<()> {
+        let Some(model) = &self.moderation else {
+            return Ok(());
+        };
+        let input: Vec<String> = messages
+            .iter()
+            .filter(|message| message.role == "user")
+            .map(|message| message.content.to_string())
+            .collect();
+        if input.is_empty() {
+            return Ok(());
+        }
+        let res = self.create_moderation(moderations::ModerationArguments::new(model, input.clone())).await?;
+        match input.into_iter().zip(res.results).find(|(_, result)| result.flagged) {
+            Some((input, result)) => Err(moderations::Flagged {
+                input,
+                categories: result.flagged_categories(),
+                result,
+            }
+            .into()),
+            None => Ok(()),
+        }
+    }
+
+    /// Limit the completion to the context left after the prompt, when enabled with [Client::with_auto_max_tokens].
+    #[cfg(feature = "chat")]
+    fn fill_max_tokens(&self, args: &mut chat::ChatArguments) {
+        if !self.auto_max_tokens || args.max_tokens.is_some() || args.max_completion_tokens.is_some() {
+            return;
+        }
+        let Some(info) = models::info(&args.model) else {
+            return;
+        };
+        let max = models::max_output_tokens(&args.model, tokens::estimate_message_tokens(&args.messages));
+        match info.reasoning {
+            true => args.max_completion_tokens = max,
+            false => args.max_tokens = max,
+        }
+    }
+
+    #[cfg(any(
+        feature = "chat",
+        feature = "completions",
+        feature = "embeddings",
+        feature = "responses",
+    ))]
+    fn record_usage(&self, model: &str, prompt_tokens: u32, completion_tokens: u32) {
+        if let Some(budget) = &self.budget {
+            budget.record(model, prompt_tokens, completion_tokens);
+        }
+        if let Some(sink) = &self.metrics {
+            sink.usage(model, prompt_tokens, completion_tokens);
+        }
+    }
+
+    /// List and describe the various models available in the API. You can refer to the [Models](https://platform.openai.com/docs/models) documentation to understand what models are available and the differences between them.
+    ///
+    /// ```no_run
+    /// # let api_key = "";
+    /// # tokio_test::block_on(async {
+    /// let client = openai_rust::Client::new(api_key);
+    /// let models = client.list_models().await.unwrap();
+    /// # })
+    /// ```
+    ///
+    /// Unlike other lists, the models are returned all at once, as the endpoint is not paginated.
+    ///
+    /// See <https://platform.openai.com/docs/api-reference/models/list>.
+    pub async fn list_models(&self) -> Result<Vec<models::Model>> {
+        let url = self.url("/models");
+
+        let res = self.send(self.req_client.get(url)).await?;
+
+        Ok(self.read_json::<models::ListModelsResponse>(res).await?.data)
+    }
+
+    /// Send a JSON body to an endpoint this crate does not wrap yet, and parse the JSON response.
+    ///
+    /// The path is relative to the base URL, like `/vector_stores`. The request goes through the same
+    /// credentials, headers, queue, circuit breaker and failover as the other methods.
+    ///
+    /// ```no_run
+    /// # tokio_test::block_on(async {
+    /// # let api_key = "";
+    /// use serde_json::{json, Value};
+    /// let client = openai_rust::Client::new(api_key);
+    /// let store: Value = client.post("/vector_stores", &json!({ "name": "Support FAQ" })).await.unwrap();
+    /// println!("created {}", store["id"]);
+    /// # })
+    /// ```
+    pub async fn post<Req: serde::Serialize + ?Sized, Res: serde::de::DeserializeOwned>(
+        &self,
+        path: &str,
+        body: &Req,
+    ) -> Result<Res> {
+        let url = self.url(path);
+
+        let res = self.send(self.req_client.post(url).json(body)).await?;
+
+        self.read_json(res).await
+    }
+
+    /// Like [Client::post], for endpoints that are read with a GET request.
+    ///
+    /// ```no_run
+    /// # tokio_test::block_on(async {
+    /// # let api_key = "";
+    /// let client = openai_rust::Client::new(api_key);
+    /// let store: serde_json::Value = client.get("/vector_stores/vs_abc123").await.unwrap();
+    /// # })
+    /// ```
+    pub async fn get<Res: serde::de::DeserializeOwned>(&self, path: &str) -> Result<Res> {
+        let url = self.url(path);
+
+        let res = self.send(self.req_client.get(url)).await?;
+
+        self.read_json(res).await
+    }
+
+    /// Given a list of messages comprising a conversation, the model will return a response.
+    ///
+    /// See <https://platform.openai.com/docs/api-reference/chat>.
+    /// ```no_run
+    /// # use tokio_test;
+    /// # tokio_test::block_on(async {
+    /// # use openai_rust;
+    /// # let api_key = "";
+    /// let client = openai_rust::Client::new(api_key);
+    /// let args = openai_rust::chat::ChatArguments::new("gpt-3.5-turbo", vec![
+    ///    openai_rust::chat::Message::user("Hello GPT!")
+    /// ]);
+    /// let res = client.create_chat(args).await.unwrap();
+    /// println!("{}", res.choices[0].message.content);
+    /// # })
+    /// ```
+    #[cfg(feature = "chat")]
+    pub async fn create_chat(
+        &self,
+        args: chat::ChatArguments,
+    ) -> Result<chat::ChatCompletion> {
+        // Once for the request, the repairs and continuations only add the replies of the model and our prompts
+        #[cfg(feature = "moderations")]
+        self.moderate(&args.messages).await?;
+        let format = match &args.response_format {
+            Some(format) if self.json_repair > 0 && *format != chat::ResponseFormat::Text => format.clone(),
+            _ => return self.create_chat_continued(args).await,
+        };
+        let mut args = args;
+        let mut attempts = vec![];
+        for _ in 0..=self.json_repair {
+            let completion = self.create_chat_continued(args.clone()).await?;
+            let Some(choice) = completion.first_choice().filter(|c| c.message.refusal.is_none()) else {
+                return Ok(completion);
+            };
+            let errors = structured::check(&choice.message.content.text(), &format);
+            if errors.is_empty() {
+                return Ok(completion);
+            }
+            args.messages.push(choice.message.clone());
+            args.messages.push(chat::Message::user(structured::repair_prompt(&errors)));
+            attempts.push(structured::Attempt {
+                content: choice.message.content.to_string(),
+                errors,
+            });
+        }
+        Err(error::Error::Other(structured::InvalidOutput { attempts }.into()))
+    }
+
+    /// A chat completion, continued when enabled with [Client::with_auto_continue].
+    #[cfg(feature = "chat")]
+    async fn create_chat_continued(&self, args: chat::ChatArguments) -> Result<chat::ChatCompletion> {
+        if self.auto_continue == 0 {
+            return Ok(self.send_chat(args).await?.data);
+        }
+        let mut args = args;
+        let mut completion = self.send_chat(args.clone()).await?.data;
+        let mut last = completion.clone();
+        for _ in 0..self.auto_continue {
+            if !last.is_truncated() {
+                break;
+            }
+            args.messages.push(last.choices.swap_remove(0).message);
+            args.messages.push(chat::Message::user(chat::CONTINUE_PROMPT));
+            last = self.send_chat(args.clone()).await?.data;
+            completion.append(last.clone());
+        }
+        Ok(completion)
+    }
+
+    /// Like [Client::create_chat], but also returns the status, headers and rate limits of the response.
+    ///
+    /// See [envelope].
+    ///
+    /// ```no_run
+    /// # tokio_test::block_on(async {
+    /// # let api_key = "";
+    /// # let args = openai_rust::chat::ChatArguments::new("gpt-3.5-turbo", vec![]);
+    /// let client = openai_rust::Client::new(api_key);
+    /// let res = client.create_chat_with_meta(args).await.unwrap();
+    /// println!("{} ({:?})", res.data, res.request_id());
+    /// println!("{:?} tokens left", res.rate_limit().remaining_tokens);
+    /// # })
+    /// ```
+    #[cfg(feature = "chat")]
+    pub async fn create_chat_with_meta(
+        &self,
+        args: chat::ChatArguments,
+    ) -> Result<envelope::ResponseEnvelope<chat::ChatCompletion>> {
+        #[cfg(feature = "moderations")]
+        self.moderate(&args.messages).await?;
+        self.send_chat(args).await
+    }
+
+    /// Send a chat request without [moderating](Client::with_moderation) it.
+    #[cfg(feature = "chat")]
+    async fn send_chat(&self, mut args: chat::ChatArguments) -> Result<envelope::ResponseEnvelope<chat::ChatCompletion>> {
+        let url = self.url("/chat/completions");
+
+        self.fill_max_tokens(&mut args);
+        self.acquire_budget().await?;
+        self.acquire_rate_limit(&args.model, |count| {
+            tokens::count_message_tokens(&args.messages, count)
+                + args.max_tokens.or(args.max_completion_tokens).unwrap_or(0) as usize
+        })
+        .await?;
+
+        let res = self.send(self.req_client.post(url).json(&args)).await?;
+
+        let envelope = self.read_envelope::<chat::ChatCompletion>(res).await?;
+        if let Some(usage) = &envelope.data.usage {
+            self.record_usage(&args.model, usage.prompt_tokens, usage.completion_tokens);
+        }
+        Ok(envelope)
+    }
+
+    /// Like [Client::create_chat], but the reply follows the JSON schema of `T` and is parsed into it.
+    ///
+    /// The schema is checked for strict mode before the request is sent.
+    /// If the model refuses, the error is a [structured::Refusal].
+    /// See the [structured] module for an example.
+    #[cfg(feature = "chat")]
+    pub async fn create_structured_chat<T: serde::de::DeserializeOwned + structured::JsonSchema>(
+        &self,
+        mut args: chat::ChatArguments,
+    ) -> Result<T> {
+        args.response_format = Some(structured::response_format::<T>()?);
+
+        let completion = self.create_chat(args).await?;
+        let message = &completion
+            .first_choice()
+            .ok_or_else(|| anyhow!("the completion has no choices"))?
+            .message;
+        if let Some(refusal) = &message.refusal {
+            return Err(error::Error::Other(structured::Refusal { message: refusal.clone() }.into()));
+        }
+        Ok(serde_json::from_str(&message.content.text())?)
+    }
+
+    /// Like [Client::create_chat] but with streaming.
+    ///
+    /// See <https://platform.openai.com/docs/api-reference/chat>.
+    ///
+    /// This method will return a stream of [chat::stream::ChatCompletionChunk]s. Use with [futures_util::StreamExt::next].
+    ///
+    /// ```no_run
+    /// # use tokio_test;
+    /// # tokio_test::block_on(async {
+    /// # use openai_rust;
+    /// # use std::io::Write;
+    /// # let client = openai_rust::Client::new("");
+    /// # let args = openai_rust::chat::ChatArguments::new("gpt-3.5-turbo", vec![
+    /// #    openai_rust::chat::Message::user("Hello GPT!")
+    /// # ]);
+    /// use openai_rust::futures_util::StreamExt;
+    /// let mut res = client.create_chat_stream(args).await.unwrap();
+    /// while let Some(chunk) = res.next().await {
+    ///     print!("{}", chunk.unwrap());
+    ///     std::io::stdout().flush().unwrap();
+    /// }
+    /// # })
+    /// ```
+    ///
+    #[cfg(feature = "chat")]
+    pub async fn create_chat_stream(
+        &self,
+        args: chat::ChatArguments,
+    ) -> Result<chat::stream::ChatCompletionChunkStream> {
+        let url = self.url("/chat/completions");
+
+        // Enable streaming
+        let mut args = args;
+        args.stream = Some(true);
+
+        #[cfg(feature = "moderations")]
+        self.moderate(&args.messages).await?;
+        self.fill_max_tokens(&mut args);
+        self.acquire_budget().await?;
+        self.acquire_rate_limit(&args.model, |count| {
+            tokens::count_message_tokens(&args.messages, count)
+                + args.max_tokens.or(args.max_completion_tokens).unwrap_or(0) as usize
+        })
+        .await?;
+
+        let res = self.send(self.req_client.post(url).json(&args)).await?;
+
+        let res = self.check_stream(res).await?;
+        let client = self.clone();
+        let model = args.model;
+        Ok(chat::stream::ChatCompletionChunkStream::new(Box::pin(res.bytes_stream())).on_usage(move |usage| {
+            client.record_usage(&model, usage.prompt_tokens, usage.completion_tokens)
+        }))
+    }
+
+    /// Lists stored chat completions, created with [store](chat::ChatArguments::store) set to `true`.
+    ///
+    /// See <https://platform.openai.com/docs/api-reference/chat/list>
+    ///
+    /// ```no_run
+    /// # use tokio_test;
+    /// # tokio_test::block_on(async {
+    /// # let api_key = "";
+    /// use openai_rust::chat::ListChatCompletions;
+    /// use openai_rust::futures_util::StreamExt;
+    /// let c = openai_rust::Client::new(api_key);
+    /// let query = ListChatCompletions::new().metadata("user_id", "user_123");
+    /// let mut completions = c.list_chat_completions(query, Default::default());
+    /// while let Some(completion) = completions.next().await {
+    ///     println!("{}", completion.unwrap());
+    /// }
+    /// # })
+    /// ```
+    #[cfg(feature = "chat")]
+    pub fn list_chat_completions(
+        &self,
+        query: chat::ListChatCompletions,
+        options: pagination::ListOptions,
+    ) -> impl futures_core::Stream<Item = Result<chat::ChatCompletion>> + Unpin + '_ {
+        self.paginate("/chat/completions".to_owned(), query.query(), options)
+    }
+
+    /// Returns the messages of a stored chat completion, in the order they were sent.
+    ///
+    /// See <https://platform.openai.com/docs/api-reference/chat/getMessages>
+    ///
+    /// ```no_run
+    /// # use tokio_test;
+    /// # tokio_test::block_on(async {
+    /// # let api_key = "";
+    /// use openai_rust::futures_util::TryStreamExt;
+    /// let c = openai_rust::Client::new(api_key);
+    /// let messages: Vec<_> = c.list_chat_completion_messages("chatcmpl-123", Default::default()).try_collect().await.unwrap();
+    /// for message in messages {
+    ///     println!("{}: {}", message.role, message.content.unwrap_or_default());
+    /// }
+    /// # })
+    /// ```
+    #[cfg(feature = "chat")]
+    pub fn list_chat_completion_messages(
+        &self,
+        completion_id: &str,
+        options: pagination::ListOptions,
+    ) -> impl futures_core::Stream<Item = Result<chat::StoredMessage>> + Unpin + '_ {
+        self.paginate(format!("/chat/completions/{}/messages", completion_id), vec![], options)
+    }
+
+    /// Retrieves a stored chat completion.
+    ///
+    /// See <https://platform.openai.com/docs/api-reference/chat/get>
+    #[cfg(feature = "chat")]
+    pub async fn retrieve_chat_completion(&self, completion_id: &str) -> Result<chat::ChatCompletion> {
+        let url = self.url(&format!("/chat/completions/{}", completion_id));
+
+        let res = self.send(self.req_client.get(url)).await?;
+
+        self.read_json(res).await
+    }
+
+    /// Replaces the metadata of a stored chat completion.
+    ///
+    /// See <https://platform.openai.com/docs/api-reference/chat/update>
+    #[cfg(feature = "chat")]
+    pub async fn update_chat_completion(
+        &self,
+        completion_id: &str,
+        metadata: std::collections::HashMap<String, String>,
+    ) -> Result<chat::ChatCompletion> {
+        let url = self.url(&format!("/chat/completions/{}", completion_id));
+
+        let body = serde_json::json!({ "metadata": metadata });
+        let res = self.send(self.req_client.post(url).json(&body)).await?;
+
+        self.read_json(res).await
+    }
+
+    /// Deletes a stored chat completion.
+    ///
+    /// See <https://platform.openai.com/docs/api-reference/chat/delete>
+    #[cfg(feature = "chat")]
+    pub async fn delete_chat_completion(&self, completion_id: &str) -> Result<chat::DeletedChatCompletion> {
+        let url = self.url(&format!("/chat/completions/{}", completion_id));
+
+        let res = self.send(self.req_client.delete(url)).await?;
+
+        self.read_json(res).await
+    }
+
+    /// Generates audio from the input text, in the [format](audio::SpeechArguments::response_format) of the arguments.
+    ///
+    /// See <https://platform.openai.com/docs/api-reference/audio/createSpeech>
+    ///
+    /// ```no_run
+    /// # tokio_test::block_on(async {
+    /// # let api_key = "";
+    /// use openai_rust::audio::{SpeechArguments, Voice};
+    /// let c = openai_rust::Client::new(api_key);
+    /// let args = SpeechArguments::new("tts-1", "Today is a wonderful day to build something people love!", Voice::Alloy);
+    /// let audio = c.create_speech(args).await.unwrap();
+    /// std::fs::write("speech.mp3", audio).unwrap();
+    /// # })
+    /// ```
+    #[cfg(feature = "audio")]
+    pub async fn create_speech(&self, args: audio::SpeechArguments) -> Result<bytes::Bytes> {
+        let url = self.url("/audio/speech");
+
+        let res = self.send(self.req_client.post(url).json(&args)).await?;
+
+        let res = self.check_stream(res).await?;
+        Ok(res.bytes().await?)
+    }
+
+    /// Generates audio from the input text, yielding chunks of audio as they arrive,
+    /// to start playback before the whole text has been synthesized.
+    ///
+    /// Use [AudioFormat::Pcm](audio::AudioFormat::Pcm) or [AudioFormat::Wav](audio::AudioFormat::Wav)
+    /// for the lowest latency, since they don't need to be decoded.
+    ///
+    /// ```no_run
+    /// # tokio_test::block_on(async {
+    /// # let api_key = "";
+    /// # fn play(chunk: &[u8]) {}
+    /// use openai_rust::audio::{AudioFormat, SpeechArguments, Voice};
+    /// use openai_rust::futures_util::StreamExt;
+    /// let c = openai_rust::Client::new(api_key);
+    /// let mut args = SpeechArguments::new("tts-1", "Today is a wonderful day to build something people love!", Voice::Alloy);
+    /// args.response_format = Some(AudioFormat::Pcm);
+    /// let mut chunks = c.create_speech_chunks(args).await.unwrap();
+    /// while let Some(chunk) = chunks.next().await {
+    ///     play(&chunk.unwrap());
+    /// }
+    /// # })
+    /// ```
+    #[cfg(feature = "audio")]
+    pub async fn create_speech_chunks(
+        &self,
+        args: audio::SpeechArguments,
+    ) -> Result<impl futures_core::Stream<Item = Result<bytes::Bytes>> + Send + Unpin> {
+        use futures_util::TryStreamExt;
+        let url = self.url("/audio/speech");
+
+        let res = self.send(self.req_client.post(url).json(&args)).await?;
+
+        let res = self.check_stream(res).await?;
+        Ok(res.bytes_stream().map_err(error::Error::from))
+    }
+
+    /// Generates audio from the input text, and writes it into `writer` as it arrives.
+    /// This makes it possible to start playback before the whole text has been synthesized.
+    ///
+    /// Returns the amount of bytes written.
+    ///
+    /// See <https://platform.openai.com/docs/api-reference/audio/createSpeech>
+    ///
+    /// ```no_run
+    /// # use tokio_test;
+    /// # tokio_test::block_on(async {
+    /// # let api_key = "";
+    /// use openai_rust::audio::{SpeechArguments, Voice};
+    /// let c = openai_rust::Client::new(api_key);
+    /// let args = SpeechArguments::new("tts-1", "Today is a wonderful day to build something people love!", Voice::Alloy);
+    /// let mut file = tokio::fs::File::create("speech.mp3").await.unwrap();
+    /// c.create_speech_stream(args, &mut file).await.unwrap();
+    /// # })
+    /// ```
+    #[cfg(feature = "audio")]
+    pub async fn create_speech_stream<W: tokio::io::AsyncWrite + Unpin>(
+        &self,
+        args: audio::SpeechArguments,
+        writer: &mut W,
+    ) -> Result<u64> {
+        use futures_util::StreamExt;
+        use tokio::io::AsyncWriteExt;
+        let url = self.url("/audio/speech");
+
+        let res = self.send(self.req_client.post(url).json(&args)).await?;
+
+        let res = self.check_stream(res).await?;
+        let mut stream = res.bytes_stream();
+        let mut written = 0;
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk?;
+            writer.write_all(&chunk).await?;
+            written += chunk.len() as u64;
+        }
+        writer.flush().await?;
+        Ok(written)
+    }
+
+    /// Transcribes audio into the input language.
+    ///
+    /// See <https://platform.openai.com/docs/api-reference/audio/createTranscription>
+    ///
+    /// ```no_run
+    /// # use tokio_test;
+    /// # tokio_test::block_on(async {
+    /// # let api_key = "";
+    /// use openai_rust::audio::TranscriptionArguments;
+    /// use openai_rust::files::Upload;
+    /// let c = openai_rust::Client::new(api_key);
+    /// let audio = Upload::file("speech.mp3").await.unwrap();
+    /// let mut args = TranscriptionArguments::new("whisper-1", audio);
+    /// args.language = Some("en".to_owned());
+    /// println!("{}", c.create_transcription(args).await.unwrap());
+    /// # })
+    /// ```
+    #[cfg(feature = "audio")]
+    pub async fn create_transcription(
+        &self,
+        args: audio::TranscriptionArguments,
+    ) -> Result<audio::Transcription> {
+        let url = self.url("/audio/transcriptions");
+        let json = args.response_format.is_none_or(|f| f.is_json());
+
+        let res = self.send(self.req_client.post(url).multipart(args.into_form())).await?;
+
+        if json {
+            self.read_json(res).await
+        } else {
+            Ok(audio::Transcription {
+                text: self.read_text(res).await?,
+                ..Default::default()
+            })
+        }
+    }
+
+    /// Transcribe audio of any size, by splitting it into chunks which are transcribed four at a time, see [audio_chunks].
+    ///
+    /// The arguments for each chunk are made by `args`. Use [audio::TranscriptionFormat::VerboseJson] to get
+    /// the timestamps of the full audio, which can be turned into subtitles with [audio::Transcription::to_srt].
+    /// Subtitle formats are not supported, because their timestamps can't be corrected.
+    ///
+    /// ```no_run
+    /// # tokio_test::block_on(async {
+    /// # let api_key = "";
+    /// use std::time::Duration;
+    /// use openai_rust::audio::{TranscriptionArguments, TranscriptionFormat};
+    /// use openai_rust::audio_chunks::{WavSplitter, MAX_FILE_SIZE};
+    /// let c = openai_rust::Client::new(api_key);
+    /// let audio = tokio::fs::read("lecture.wav").await.unwrap();
+    /// let splitter = WavSplitter::new(MAX_FILE_SIZE).at_silence(Duration::from_secs(10));
+    /// let transcript = c
+    ///     .create_chunked_transcription(audio, "lecture.wav", &splitter, |file| {
+    ///         let mut args = TranscriptionArguments::new("whisper-1", file);
+    ///         args.response_format = Some(TranscriptionFormat::VerboseJson);
+    ///         args
+    ///     })
+    ///     .await
+    ///     .unwrap();
+    /// println!("{}", transcript.to_srt());
+    /// # })
+    /// ```
+    #[cfg(feature = "audio")]
+    pub async fn create_chunked_transcription(
+        &self,
+        audio: Vec<u8>,
+        filename: &str,
+        splitter: &(impl audio_chunks::Splitter + Sync),
+        args: impl Fn(files::Upload) -> audio::TranscriptionArguments,
+    ) -> Result<audio::Transcription> {
+        use futures_util::StreamExt;
+        use audio::TranscriptionFormat::{Srt, Vtt};
+
+        let mut requests = vec![];
+        for chunk in splitter.split(audio, filename)? {
+            let args = args(files::Upload::bytes(chunk.audio, chunk.filename));
+            if matches!(args.response_format, Some(Srt | Vtt)) {
+                return Err(anyhow!("subtitles of chunks can't be joined, use verbose JSON and Transcription::to_srt").into());
+            }
+            requests.push(async move { Ok::<_, error::Error>((chunk.offset, self.create_transcription(args).await?)) });
+        }
+        let parts = futures_util::stream::iter(requests)
+            .buffered(4)
+            .collect::<Vec<_>>()
+            .await
+            .into_iter()
+            .collect::<Result<Vec<_>>>()?;
+        Ok(audio_chunks::stitch(parts))
+    }
+
+    /// Given a prompt, the model will return one or more predicted completions, and can also return the probabilities of alternative tokens at each position.
+    ///
+    /// See <https://platform.openai.com/docs/api-reference/completions>
+    ///
+    /// ```no_run
+    /// # use openai_rust::*;
+    /// # use tokio_test;
+    /// # tokio_test::block_on(async {
+    /// # let api_key = "";
+    /// let c = openai_rust::Client::new(api_key);
+    /// let args = openai_rust::completions::CompletionArguments::new("text-davinci-003", "The quick brown fox".to_owned());
+    /// println!("{}", c.create_completion(args).await.unwrap().choices[0].text);
+    /// # })
+    /// ```
+    #[cfg(feature = "completions")]
+    pub async fn create_completion(
+        &self,
+        args: completions::CompletionArguments,
+    ) -> Result<completions::CompletionResponse> {
+        Ok(self.create_completion_with_meta(args).await?.data)
+    }
+
+    /// Like [Client::create_completion], but also returns the status, headers and rate limits of the response.
+    ///
+    /// See [envelope].
+    #[cfg(feature = "completions")]
+    pub async fn create_completion_with_meta(
+        &self,
+        args: completions::CompletionArguments,
+    ) -> Result<envelope::ResponseEnvelope<completions::CompletionResponse>> {
+        let url = self.url("/completions");
+
+        self.acquire_budget().await?;
+        self.acquire_rate_limit(&args.model, |count| {
+            count(args.prompt.as_deref().unwrap_or_default()) + args.max_tokens.unwrap_or(16) as usize
+        })
+        .await?;
+
+        let res = self.send(self.req_client.post(url).json(&args)).await?;
+
+        let envelope = self.read_envelope::<completions::CompletionResponse>(res).await?;
+        if let Some(usage) = &envelope.data.usage {
+            self.record_usage(&args.model, usage.prompt_tokens, usage.completion_tokens);
+        }
+        Ok(envelope)
+    }
+
+    /// Given a prompt and an instruction, the model will return an edited version of the prompt.
+    ///
+    /// See <https://platform.openai.com/docs/api-reference/edits>
+    ///
+    /// ```no_run
+    /// # use openai_rust;
+    /// # use tokio_test;
+    /// # tokio_test::block_on(async {
+    /// # let api_key = "";
+    /// let c = openai_rust::Client::new(api_key);
+    /// let args = openai_rust::edits::EditArguments::new("text-davinci-edit-001", "The quick brown fox".to_owned(), "Complete this sentence.".to_owned());
+    /// println!("{}", c.create_edit(args).await.unwrap().to_string());
+    /// # })
+    /// ```
+    ///
+    #[cfg(feature = "completions")]
+    #[deprecated = "Use the chat api instead"]
+    #[allow(deprecated)]
+    pub async fn create_edit(&self, args: edits::EditArguments) -> Result<edits::EditResponse> {
+        let url = self.url("/edits");
+
+        self.acquire_budget().await?;
+        self.acquire_rate_limit(&args.model, |count| {
+            count(args.input.as_deref().unwrap_or_default()) + count(&args.instruction)
+        })
+        .await?;
+
+        let res = self.send(self.req_client.post(url).json(&args)).await?;
+
+        let response: edits::EditResponse = self.read_json(res).await?;
+        self.record_usage(&args.model, response.usage.prompt_tokens, response.usage.completion_tokens);
+        Ok(response)
+    }
+
+    /// Get a vector representation of a given input that can be easily consumed by machine learning models and algorithms.
+    ///
+    /// See <https://platform.openai.com/docs/api-reference/embeddings>
+    ///
+    /// ```no_run
+    /// # use openai_rust;
+    /// # use tokio_test;
+    /// # tokio_test::block_on(async {
+    /// # let api_key = "";
+    /// let c = openai_rust::Client::new(api_key);
+    /// let args = openai_rust::embeddings::EmbeddingsArguments::new("text-embedding-ada-002", "The food was delicious and the waiter...".to_owned());
+    /// println!("{:?}", c.create_embeddings(args).await.unwrap().data);
+    /// # })
+    /// ```
+    ///
+    #[cfg(feature = "embeddings")]
+    pub async fn create_embeddings(
+        &self,
+        args: embeddings::EmbeddingsArguments,
+    ) -> Result<embeddings::EmbeddingsResponse> {
+        Ok(self.create_embeddings_with_meta(args).await?.data)
+    }
+
+    /// Like [Client::create_embeddings], but also returns the status, headers and rate limits of the response.
+    ///
+    /// See [envelope].
+    #[cfg(feature = "embeddings")]
+    pub async fn create_embeddings_with_meta(
+        &self,
+        args: embeddings::EmbeddingsArguments,
+    ) -> Result<envelope::ResponseEnvelope<embeddings::EmbeddingsResponse>> {
+        let url = self.url("/embeddings");
+
+        self.acquire_budget().await?;
+        self.acquire_rate_limit(&args.model, |count| count(&args.input)).await?;
+
+        let res = self.send(self.req_client.post(url).json(&args)).await?;
+
+        let envelope = self.read_envelope::<embeddings::EmbeddingsResponse>(res).await?;
+        if let Some(usage) = &envelope.data.usage {
+            self.record_usage(&args.model, usage.prompt_tokens, 0);
+        }
+        Ok(envelope)
+    }
+
+    /// Classifies whether texts are potentially harmful.
+    ///
+    /// See <https://platform.openai.com/docs/api-reference/moderations/create>.
+    ///
+    /// ```no_run
+    /// # tokio_test::block_on(async {
+    /// # let api_key = "";
+    /// use openai_rust::moderations::ModerationArguments;
+    /// let c = openai_rust::Client::new(api_key);
+    /// let args = ModerationArguments::new("omni-moderation-latest", vec!["I want to hurt them.".to_owned()]);
+    /// let res = c.create_moderation(args).await.unwrap();
+    /// println!("flagged for {:?}", res.results[0].flagged_categories());
+    /// # })
+    /// ```
+    #[cfg(feature = "moderations")]
+    pub async fn create_moderation(
+        &self,
+        args: moderations::ModerationArguments,
+    ) -> Result<moderations::ModerationResponse> {
+        let url = self.url("/moderations");
+
+        let res = self.send(self.req_client.post(url).json(&args)).await?;
+
+        self.read_json(res).await
+    }
+
+    /// Creates a model response. Provide text or messages as input, and the model will generate text.
+    ///
+    /// See <https://platform.openai.com/docs/api-reference/responses/create>
+    ///
+    /// ```no_run
+    /// # use tokio_test;
+    /// # tokio_test::block_on(async {
+    /// # let api_key = "";
+    /// let c = openai_rust::Client::new(api_key);
+    /// let args = openai_rust::responses::ResponseArguments::new("gpt-4o", "Tell me a three sentence bedtime story about a unicorn.");
+    /// println!("{}", c.create_response(args).await.unwrap());
+    /// # })
+    /// ```
+    #[cfg(feature = "responses")]
+    pub async fn create_response(
+        &self,
+        args: responses::ResponseArguments,
+    ) -> Result<responses::Response> {
+        Ok(self.create_response_with_meta(args).await?.data)
+    }
+
+    /// Like [Client::create_response], but also returns the status, headers and rate limits of the response.
+    ///
+    /// See [envelope].
+    #[cfg(feature = "responses")]
+    pub async fn create_response_with_meta(
+        &self,
+        args: responses::ResponseArguments,
+    ) -> Result<envelope::ResponseEnvelope<responses::Response>> {
+        let url = self.url("/responses");
+
+        self.acquire_budget().await?;
+        self.acquire_rate_limit(&args.model, |count| {
+            args.input.count_tokens(count)
+                + count(args.instructions.as_deref().unwrap_or_default())
+                + args.max_output_tokens.unwrap_or(0) as usize
+        })
+        .await?;
+
+        let res = self.send(self.req_client.post(url).json(&args)).await?;
+
+        let envelope = self.read_envelope::<responses::Response>(res).await?;
+        if let Some(usage) = &envelope.data.usage {
+            self.record_usage(&args.model, usage.input_tokens, usage.output_tokens);
+        }
+        Ok(envelope)
+    }
+
+    /// Retrieves a model response with the given ID.
+    ///
+    /// See <https://platform.openai.com/docs/api-reference/responses/get>
+    #[cfg(feature = "responses")]
+    pub async fn get_response(&self, response_id: &str) -> Result<responses::Response> {
+        let url = self.url(&format!("/responses/{}", response_id));
+
+        let res = self.send(self.req_client.get(url)).await?;
+
+        self.read_json(res).await
+    }
+
+    /// Deletes a model response with the given ID.
+    ///
+    /// See <https://platform.openai.com/docs/api-reference/responses/delete>
+    #[cfg(feature = "responses")]
+    pub async fn delete_response(&self, response_id: &str) -> Result<responses::DeletedResponse> {
+        let url = self.url(&format!("/responses/{}", response_id));
+
+        let res = self.send(self.req_client.delete(url)).await?;
+
+        self.read_json(res).await
+    }
+
+    /// Cancels a model response with the given ID.
+    /// Only responses created with [background](responses::ResponseArguments::background) set to `true` can be cancelled.
+    ///
+    /// See <https://platform.openai.com/docs/api-reference/responses/cancel>
+    ///
+    /// ```no_run
+    /// # use tokio_test;
+    /// # tokio_test::block_on(async {
+    /// # let api_key = "";
+    /// let c = openai_rust::Client::new(api_key);
+    /// let mut args = openai_rust::responses::ResponseArguments::new("o3", "Write a very long novel.");
+    /// args.background = Some(true);
+    /// let res = c.create_response(args).await.unwrap();
+    /// let res = c.cancel_response(&res.id).await.unwrap();
+    /// assert_eq!(res.status, "cancelled");
+    /// # })
+    /// ```
+    #[cfg(feature = "responses")]
+    pub async fn cancel_response(&self, response_id: &str) -> Result<responses::Response> {
+        let url = self.url(&format!("/responses/{}/cancel", response_id));
+
+        let res = self.send(self.req_client.post(url)).await?;
+
+        self.read_json(res).await
+    }
+
+    /// Returns a list of input items for a given response.
+    ///
+    /// See <https://platform.openai.com/docs/api-reference/responses/input-items>
+    ///
+    /// ```no_run
+    /// # use tokio_test;
+    /// # tokio_test::block_on(async {
+    /// # let api_key = "";
+    /// use openai_rust::futures_util::StreamExt;
+    /// let c = openai_rust::Client::new(api_key);
+    /// let mut items = c.list_response_input_items("resp_123", Default::default());
+    /// while let Some(item) = items.next().await {
+    ///     println!("{:?}", item.unwrap());
+    /// }
+    /// # })
+    /// ```
+    #[cfg(feature = "responses")]
+    pub fn list_response_input_items(
+        &self,
+        response_id: &str,
+        options: pagination::ListOptions,
+    ) -> impl futures_core::Stream<Item = Result<responses::InputItem>> + Unpin + '_ {
+        self.paginate(format!("/responses/{}/input_items", response_id), vec![], options)
+    }
+
+    /// Create an assistant with a model and instructions.
+    ///
+    /// See <https://platform.openai.com/docs/api-reference/assistants/createAssistant>
+    ///
+    /// ```no_run
+    /// # use tokio_test;
+    /// # tokio_test::block_on(async {
+    /// # let api_key = "";
+    /// let c = openai_rust::Client::new(api_key);
+    /// let mut args = openai_rust::assistants::AssistantArguments::new("gpt-4o");
+    /// args.name = Some("Math Tutor".to_owned());
+    /// args.tools = Some(vec![openai_rust::assistants::Tool::CodeInterpreter]);
+    /// let assistant = c.create_assistant(args).await.unwrap();
+    /// # })
+    /// ```
+    #[cfg(feature = "assistants")]
+    pub async fn create_assistant(
+        &self,
+        args: assistants::AssistantArguments,
+    ) -> Result<assistants::Assistant> {
+        let url = self.url("/assistants");
+
+        let res = self
+            .send(self.req_client.post(url).json(&args))
+            .await?;
+
+        self.read_json(res).await
+    }
+
+    /// Returns the assistants, newest first unless the [order](pagination::ListOptions::order) is changed.
+    ///
+    /// See <https://platform.openai.com/docs/api-reference/assistants/listAssistants>
+    ///
+    /// ```no_run
+    /// # tokio_test::block_on(async {
+    /// # let api_key = "";
+    /// use openai_rust::futures_util::StreamExt;
+    /// let c = openai_rust::Client::new(api_key);
+    /// let mut assistants = c.list_assistants(Default::default());
+    /// while let Some(assistant) = assistants.next().await {
+    ///     let assistant = assistant.unwrap();
+    ///     println!("{}: {}", assistant.id, assistant.name.unwrap_or_default());
+    /// }
+    /// # })
+    /// ```
+    #[cfg(feature = "assistants")]
+    pub fn list_assistants(
+        &self,
+        options: pagination::ListOptions,
+    ) -> impl futures_core::Stream<Item = Result<assistants::Assistant>> + Unpin + '_ {
+        self.paginate("/assistants".to_owned(), vec![], options)
+    }
+
+    /// Retrieves an assistant.
+    ///
+    /// See <https://platform.openai.com/docs/api-reference/assistants/getAssistant>
+    #[cfg(feature = "assistants")]
+    pub async fn retrieve_assistant(&self, assistant_id: &str) -> Result<assistants::Assistant> {
+        let url = self.url(&format!("/assistants/{}", assistant_id));
+
+        let res = self.send(self.req_client.get(url)).await?;
+
+        self.read_json(res).await
+    }
+
+    /// Changes the given fields of an assistant.
+    ///
+    /// See <https://platform.openai.com/docs/api-reference/assistants/modifyAssistant>
+    #[cfg(feature = "assistants")]
+    pub async fn modify_assistant(
+        &self,
+        assistant_id: &str,
+        args: assistants::ModifyAssistantArguments,
+    ) -> Result<assistants::Assistant> {
+        let url = self.url(&format!("/assistants/{}", assistant_id));
+
+        let res = self.send(self.req_client.post(url).json(&args)).await?;
+
+        self.read_json(res).await
+    }
+
+    /// Deletes an assistant.
+    ///
+    /// See <https://platform.openai.com/docs/api-reference/assistants/deleteAssistant>
+    #[cfg(feature = "assistants")]
+    pub async fn delete_assistant(&self, assistant_id: &str) -> Result<assistants::DeletedAssistant> {
+        let url = self.url(&format!("/assistants/{}", assistant_id));
+
+        let res = self.send(self.req_client.delete(url)).await?;
+
+        self.read_json(res).await
+    }
+
+    /// Creates a thread, optionally with messages to start it with.
+    ///
+    /// See <https://platform.openai.com/docs/api-reference/threads/createThread>
+    #[cfg(feature = "assistants")]
+    pub async fn create_thread(&self, args: threads::ThreadArguments) -> Result<threads::Thread> {
+        let url = self.url("/threads");
+
+        let res = self.send(self.req_client.post(url).json(&args)).await?;
+
+        self.read_json(res).await
+    }
+
+    /// Retrieves a thread.
+    ///
+    /// See <https://platform.openai.com/docs/api-reference/threads/getThread>
+    #[cfg(feature = "assistants")]
+    pub async fn retrieve_thread(&self, thread_id: &str) -> Result<threads::Thread> {
+        let url = self.url(&format!("/threads/{}", thread_id));
+
+        let res = self.send(self.req_client.get(url)).await?;
+
+        self.read_json(res).await
+    }
+
+    /// Changes the given fields of a thread.
+    ///
+    /// See <https://platform.openai.com/docs/api-reference/threads/modifyThread>
+    #[cfg(feature = "assistants")]
+    pub async fn modify_thread(
+        &self,
+        thread_id: &str,
+        args: threads::ModifyThreadArguments,
+    ) -> Result<threads::Thread> {
+        let url = self.url(&format!("/threads/{}", thread_id));
+
+        let res = self.send(self.req_client.post(url).json(&args)).await?;
+
+        self.read_json(res).await
+    }
+
+    /// Deletes a thread.
+    ///
+    /// See <https://platform.openai.com/docs/api-reference/threads/deleteThread>
+    #[cfg(feature = "assistants")]
+    pub async fn delete_thread(&self, thread_id: &str) -> Result<threads::DeletedThread> {
+        let url = self.url(&format!("/threads/{}", thread_id));
+
+        let res = self.send(self.req_client.delete(url)).await?;
+
+        self.read_json(res).await
+    }
+
+    /// Adds a message to a thread.
+    ///
+    /// See <https://platform.openai.com/docs/api-reference/messages/createMessage>
+    #[cfg(feature = "assistants")]
+    pub async fn create_message(
+        &self,
+        thread_id: &str,
+        args: threads::MessageArguments,
+    ) -> Result<threads::Message> {
+        let url = self.url(&format!("/threads/{}/messages", thread_id));
+
+        let res = self.send(self.req_client.post(url).json(&args)).await?;
+
+        self.read_json(res).await
+    }
+
+    /// Returns the messages of a thread, newest first unless the [order](pagination::ListOptions::order) is changed.
+    ///
+    /// See <https://platform.openai.com/docs/api-reference/messages/listMessages>
+    #[cfg(feature = "assistants")]
+    pub fn list_messages(
+        &self,
+        thread_id: &str,
+        options: pagination::ListOptions,
+    ) -> impl futures_core::Stream<Item = Result<threads::Message>> + Unpin + '_ {
+        self.paginate(format!("/threads/{}/messages", thread_id), vec![], options)
+    }
+
+    /// Retrieves a message of a thread.
+    ///
+    /// See <https://platform.openai.com/docs/api-reference/messages/getMessage>
+    #[cfg(feature = "assistants")]
+    pub async fn retrieve_message(&self, thread_id: &str, message_id: &str) -> Result<threads::Message> {
+        let url = self.url(&format!("/threads/{}/messages/{}", thread_id, message_id));
+
+        let res = self.send(self.req_client.get(url)).await?;
+
+        self.read_json(res).await
+    }
+
+    /// Create a run of an assistant on a thread.
+    ///
+    /// See <https://platform.openai.com/docs/api-reference/runs/createRun>
+    #[cfg(feature = "assistants")]
+    pub async fn create_run(
+        &self,
+        thread_id: &str,
+        args: threads::RunArguments,
+    ) -> Result<threads::Run> {
+        let url = self.url(&format!("/threads/{}/runs", thread_id));
+
+        let res = self
+            .send(self.req_client.post(url).json(&args))
+            .await?;
+
+        self.read_json(res).await
+    }
+
+    /// Like [Client::create_run], but streams the events of the run as they happen.
+    ///
+    /// See <https://platform.openai.com/docs/api-reference/runs/createRun> and [threads::RunEventStream].
+    #[cfg(feature = "assistants")]
+    pub async fn create_run_stream(
+        &self,
+        thread_id: &str,
+        args: threads::RunArguments,
+    ) -> Result<threads::RunEventStream> {
+        let url = self.url(&format!("/threads/{}/runs", thread_id));
+
+        // Enable streaming
+        let mut args = args;
+        args.stream = Some(true);
+
+        let res = self.send(self.req_client.post(url).json(&args)).await?;
+
+        let res = self.check_stream(res).await?;
+        Ok(threads::RunEventStream::new(Box::pin(res.bytes_stream())))
+    }
+
+    /// Retrieves a run, to check its status.
+    ///
+    /// See <https://platform.openai.com/docs/api-reference/runs/getRun>
+    #[cfg(feature = "assistants")]
+    pub async fn retrieve_run(&self, thread_id: &str, run_id: &str) -> Result<threads::Run> {
+        let url = self.url(&format!("/threads/{}/runs/{}", thread_id, run_id));
+
+        let res = self.send(self.req_client.get(url)).await?;
+
+        self.read_json(res).await
+    }
+
+    /// Polls a run every `interval` until it is no longer [active](threads::Run::is_active),
+    /// which is when it finished or [requires action](threads::Run::required_action).
+    #[cfg(feature = "assistants")]
+    pub async fn wait_for_run(
+        &self,
+        thread_id: &str,
+        run_id: &str,
+        interval: std::time::Duration,
+    ) -> Result<threads::Run> {
+        loop {
+            let run = self.retrieve_run(thread_id, run_id).await?;
+            if !run.is_active() {
+                return Ok(run);
+            }
+            tokio::time::sleep(interval).await;
+        }
+    }
+
+    /// Submits the outputs of the tool calls of a run with the status `requires_action`, so the run can continue.
+    ///
+    /// See <https://platform.openai.com/docs/api-reference/runs/submitToolOutputs>
+    ///
+    /// ```no_run
+    /// # use tokio_test;
+    /// # tokio_test::block_on(async {
+    /// # let api_key = "";
+    /// # fn call_function(name: &str, arguments: &str) -> serde_json::Value { serde_json::Value::Null }
+    /// use openai_rust::threads::{RunArguments, ToolCall, ToolOutput};
+    /// let c = openai_rust::Client::new(api_key);
+    /// let run = c.create_run("thread_abc123", RunArguments::new("asst_abc123")).await.unwrap();
+    /// if let Some(action) = run.required_action {
+    ///     let outputs = action
+    ///         .submit_tool_outputs
+    ///         .tool_calls
+    ///         .iter()
+    ///         .filter_map(|call| match call {
+    ///             ToolCall::Function { function, .. } => {
+    ///                 let value = call_function(&function.name, &function.arguments);
+    ///                 Some(ToolOutput::new(call, &value).unwrap())
+    ///             }
+    ///             _ => None,
+    ///         })
+    ///         .collect();
+    ///     c.submit_tool_outputs("thread_abc123", &run.id, outputs).await.unwrap();
+    /// }
+    /// # })
+    /// ```
+    #[cfg(feature = "assistants")]
+    pub async fn submit_tool_outputs(
+        &self,
+        thread_id: &str,
+        run_id: &str,
+        tool_outputs: Vec<threads::ToolOutput>,
+    ) -> Result<threads::Run> {
+        let url = self.url(&format!("/threads/{}/runs/{}/submit_tool_outputs", thread_id, run_id));
+
+        let body = serde_json::json!({ "tool_outputs": tool_outputs });
+        let res = self
+            .send(self.req_client.post(url).json(&body))
+            .await?;
+
+        self.read_json(res).await
+    }
+
+    /// Like [Client::submit_tool_outputs], but streams the events of the continued run.
+    ///
+    /// See <https://platform.openai.com/docs/api-reference/runs/submitToolOutputs> and [threads::RunEventStream].
+    #[cfg(feature = "assistants")]
+    pub async fn submit_tool_outputs_stream(
+        &self,
+        thread_id: &str,
+        run_id: &str,
+        tool_outputs: Vec<threads::ToolOutput>,
+    ) -> Result<threads::RunEventStream> {
+        let url = self.url(&format!("/threads/{}/runs/{}/submit_tool_outputs", thread_id, run_id));
+
+        let body = serde_json::json!({ "tool_outputs": tool_outputs, "stream": true });
+        let res = self.send(self.req_client.post(url).json(&body)).await?;
+
+        let res = self.check_stream(res).await?;
+        Ok(threads::RunEventStream::new(Box::pin(res.bytes_stream())))
+    }
+
+    /// Returns the steps of a run, which show what the assistant did during the run.
+    ///
+    /// See <https://platform.openai.com/docs/api-reference/run-steps/listRunSteps>
+    ///
+    /// ```no_run
+    /// # use tokio_test;
+    /// # tokio_test::block_on(async {
+    /// # let api_key = "";
+    /// use openai_rust::futures_util::StreamExt;
+    /// use openai_rust::threads::StepDetails;
+    /// let c = openai_rust::Client::new(api_key);
+    /// let mut steps = c.list_run_steps("thread_abc123", "run_abc123", Default::default());
+    /// while let Some(step) = steps.next().await {
+    ///     match step.unwrap().step_details {
+    ///         StepDetails::MessageCreation { message_creation } => println!("wrote {}", message_creation.message_id),
+    ///         StepDetails::ToolCalls { tool_calls } => println!("called {} tools", tool_calls.len()),
+    ///     }
+    /// }
+    /// # })
+    /// ```
+    #[cfg(feature = "assistants")]
+    pub fn list_run_steps(
+        &self,
+        thread_id: &str,
+        run_id: &str,
+        options: pagination::ListOptions,
+    ) -> impl futures_core::Stream<Item = Result<threads::RunStep>> + Unpin + '_ {
+        self.paginate(format!("/threads/{}/runs/{}/steps", thread_id, run_id), vec![], options)
+    }
+
+    /// Retrieves a step of a run.
+    ///
+    /// See <https://platform.openai.com/docs/api-reference/run-steps/getRunStep>
+    #[cfg(feature = "assistants")]
+    pub async fn retrieve_run_step(
+        &self,
+        thread_id: &str,
+        run_id: &str,
+        step_id: &str,
+    ) -> Result<threads::RunStep> {
+        let url = self.url(&format!("/threads/{}/runs/{}/steps/{}", thread_id, run_id, step_id));
+
+        let res = self
+            .send(self.req_client.get(url))
+            .await?;
+
+        self.read_json(res).await
+    }
+
+    /// Creates a vector store, optionally with files to add to it.
+    ///
+    /// See <https://platform.openai.com/docs/api-reference/vector-stores/create>
+    #[cfg(feature = "vector_stores")]
+    pub async fn create_vector_store(
+        &self,
+        args: vector_stores::VectorStoreArguments,
+    ) -> Result<vector_stores::VectorStore> {
+        let url = self.url("/vector_stores");
+
+        let res = self.send(self.req_client.post(url).json(&args)).await?;
+
+        self.read_json(res).await
+    }
+
+    /// Returns the vector stores, newest first unless the [order](pagination::ListOptions::order) is changed.
+    ///
+    /// See <https://platform.openai.com/docs/api-reference/vector-stores/list>
+    #[cfg(feature = "vector_stores")]
+    pub fn list_vector_stores(
+        &self,
+        options: pagination::ListOptions,
+    ) -> impl futures_core::Stream<Item = Result<vector_stores::VectorStore>> + Unpin + '_ {
+        self.paginate("/vector_stores".to_owned(), vec![], options)
+    }
+
+    /// Retrieves a vector store.
+    ///
+    /// See <https://platform.openai.com/docs/api-reference/vector-stores/retrieve>
+    #[cfg(feature = "vector_stores")]
+    pub async fn retrieve_vector_store(&self, vector_store_id: &str) -> Result<vector_stores::VectorStore> {
+        let url = self.url(&format!("/vector_stores/{}", vector_store_id));
 
-impl Client {
-    /// Create a new client.
-    /// This will automatically build a [reqwest::Client] used internally.
-    pub fn new(api_key: &str) -> Client {
-        let req_client = reqwest::ClientBuilder::new().build().unwrap();
-        Client {
-            req_client,
-            key: api_key.to_owned(),
+        let res = self.send(self.req_client.get(url)).await?;
+
+        self.read_json(res).await
+    }
+
+    /// Changes the given fields of a vector store.
+    ///
+    /// See <https://platform.openai.com/docs/api-reference/vector-stores/modify>
+    #[cfg(feature = "vector_stores")]
+    pub async fn modify_vector_store(
+        &self,
+        vector_store_id: &str,
+        args: vector_stores::ModifyVectorStoreArguments,
+    ) -> Result<vector_stores::VectorStore> {
+        let url = self.url(&format!("/vector_stores/{}", vector_store_id));
+
+        let res = self.send(self.req_client.post(url).json(&args)).await?;
+
+        self.read_json(res).await
+    }
+
+    /// Deletes a vector store. Its files are not deleted from the Files API.
+    ///
+    /// See <https://platform.openai.com/docs/api-reference/vector-stores/delete>
+    #[cfg(feature = "vector_stores")]
+    pub async fn delete_vector_store(&self, vector_store_id: &str) -> Result<vector_stores::DeletedVectorStore> {
+        let url = self.url(&format!("/vector_stores/{}", vector_store_id));
+
+        let res = self.send(self.req_client.delete(url)).await?;
+
+        self.read_json(res).await
+    }
+
+    /// Polls a vector store every `interval` until all of its files were processed.
+    #[cfg(feature = "vector_stores")]
+    pub async fn wait_for_vector_store(
+        &self,
+        vector_store_id: &str,
+        interval: std::time::Duration,
+    ) -> Result<vector_stores::VectorStore> {
+        loop {
+            let store = self.retrieve_vector_store(vector_store_id).await?;
+            if !store.is_in_progress() {
+                return Ok(store);
+            }
+            tokio::time::sleep(interval).await;
         }
     }
 
-    /// Build a client using your own [reqwest::Client].
-    pub fn new_with_client(api_key: &str, req_client: reqwest::Client) -> Client {
-        Client {
-            req_client,
-            key: api_key.to_owned(),
+    /// Adds a file to a vector store, after which it is chunked and embedded in the background.
+    ///
+    /// See <https://platform.openai.com/docs/api-reference/vector-stores-files/createFile>
+    #[cfg(feature = "vector_stores")]
+    pub async fn create_vector_store_file(
+        &self,
+        vector_store_id: &str,
+        args: vector_stores::VectorStoreFileArguments,
+    ) -> Result<vector_stores::VectorStoreFile> {
+        let url = self.url(&format!("/vector_stores/{}/files", vector_store_id));
+
+        let res = self.send(self.req_client.post(url).json(&args)).await?;
+
+        self.read_json(res).await
+    }
+
+    /// Returns the files of a vector store, newest first unless the [order](pagination::ListOptions::order) is changed.
+    /// Only returns files with the given status, one of `in_progress`, `completed`, `failed` or `cancelled`, if given.
+    ///
+    /// See <https://platform.openai.com/docs/api-reference/vector-stores-files/listFiles>
+    #[cfg(feature = "vector_stores")]
+    pub fn list_vector_store_files(
+        &self,
+        vector_store_id: &str,
+        status: Option<&str>,
+        options: pagination::ListOptions,
+    ) -> impl futures_core::Stream<Item = Result<vector_stores::VectorStoreFile>> + Unpin + '_ {
+        let query = status.map(|status| ("filter".to_owned(), status.to_owned())).into_iter().collect();
+        self.paginate(format!("/vector_stores/{}/files", vector_store_id), query, options)
+    }
+
+    /// Retrieves a file of a vector store.
+    ///
+    /// See <https://platform.openai.com/docs/api-reference/vector-stores-files/getFile>
+    #[cfg(feature = "vector_stores")]
+    pub async fn retrieve_vector_store_file(
+        &self,
+        vector_store_id: &str,
+        file_id: &str,
+    ) -> Result<vector_stores::VectorStoreFile> {
+        let url = self.url(&format!("/vector_stores/{}/files/{}", vector_store_id, file_id));
+
+        let res = self.send(self.req_client.get(url)).await?;
+
+        self.read_json(res).await
+    }
+
+    /// Removes a file from a vector store. The file itself is not deleted, use [Client::delete_file] for that.
+    ///
+    /// See <https://platform.openai.com/docs/api-reference/vector-stores-files/deleteFile>
+    #[cfg(feature = "vector_stores")]
+    pub async fn delete_vector_store_file(
+        &self,
+        vector_store_id: &str,
+        file_id: &str,
+    ) -> Result<vector_stores::DeletedVectorStoreFile> {
+        let url = self.url(&format!("/vector_stores/{}/files/{}", vector_store_id, file_id));
+
+        let res = self.send(self.req_client.delete(url)).await?;
+
+        self.read_json(res).await
+    }
+
+    /// Polls a file of a vector store every `interval` until it was processed, or failed to.
+    #[cfg(feature = "vector_stores")]
+    pub async fn wait_for_vector_store_file(
+        &self,
+        vector_store_id: &str,
+        file_id: &str,
+        interval: std::time::Duration,
+    ) -> Result<vector_stores::VectorStoreFile> {
+        loop {
+            let file = self.retrieve_vector_store_file(vector_store_id, file_id).await?;
+            if !file.is_in_progress() {
+                return Ok(file);
+            }
+            tokio::time::sleep(interval).await;
         }
     }
 
-    /// List and describe the various models available in the API. You can refer to the [Models](https://platform.openai.com/docs/models) documentation to understand what models are available and the differences between them.
+    /// Adds several files to a vector store at once.
+    ///
+    /// See <https://platform.openai.com/docs/api-reference/vector-stores-file-batches/createBatch>
+    #[cfg(feature = "vector_stores")]
+    pub async fn create_vector_store_file_batch(
+        &self,
+        vector_store_id: &str,
+        args: vector_stores::FileBatchArguments,
+    ) -> Result<vector_stores::VectorStoreFileBatch> {
+        let url = self.url(&format!("/vector_stores/{}/file_batches", vector_store_id));
+
+        let res = self.send(self.req_client.post(url).json(&args)).await?;
+
+        self.read_json(res).await
+    }
+
+    /// Retrieves a file batch of a vector store.
+    ///
+    /// See <https://platform.openai.com/docs/api-reference/vector-stores-file-batches/getBatch>
+    #[cfg(feature = "vector_stores")]
+    pub async fn retrieve_vector_store_file_batch(
+        &self,
+        vector_store_id: &str,
+        batch_id: &str,
+    ) -> Result<vector_stores::VectorStoreFileBatch> {
+        let url = self.url(&format!("/vector_stores/{}/file_batches/{}", vector_store_id, batch_id));
+
+        let res = self.send(self.req_client.get(url)).await?;
+
+        self.read_json(res).await
+    }
+
+    /// Cancels the processing of the remaining files of a file batch.
+    ///
+    /// See <https://platform.openai.com/docs/api-reference/vector-stores-file-batches/cancelBatch>
+    #[cfg(feature = "vector_stores")]
+    pub async fn cancel_vector_store_file_batch(
+        &self,
+        vector_store_id: &str,
+        batch_id: &str,
+    ) -> Result<vector_stores::VectorStoreFileBatch> {
+        let url = self.url(&format!("/vector_stores/{}/file_batches/{}/cancel", vector_store_id, batch_id));
+
+        let res = self.send(self.req_client.post(url)).await?;
+
+        self.read_json(res).await
+    }
+
+    /// Returns the files of a file batch, optionally only those with the given status
+    /// like [Client::list_vector_store_files].
+    ///
+    /// See <https://platform.openai.com/docs/api-reference/vector-stores-file-batches/listBatchFiles>
+    #[cfg(feature = "vector_stores")]
+    pub fn list_vector_store_file_batch_files(
+        &self,
+        vector_store_id: &str,
+        batch_id: &str,
+        status: Option<&str>,
+        options: pagination::ListOptions,
+    ) -> impl futures_core::Stream<Item = Result<vector_stores::VectorStoreFile>> + Unpin + '_ {
+        let query = status.map(|status| ("filter".to_owned(), status.to_owned())).into_iter().collect();
+        self.paginate(
+            format!("/vector_stores/{}/file_batches/{}/files", vector_store_id, batch_id),
+            query,
+            options,
+        )
+    }
+
+    /// Polls a file batch every `interval` until all of its files were processed, or failed to.
+    #[cfg(feature = "vector_stores")]
+    pub async fn wait_for_vector_store_file_batch(
+        &self,
+        vector_store_id: &str,
+        batch_id: &str,
+        interval: std::time::Duration,
+    ) -> Result<vector_stores::VectorStoreFileBatch> {
+        loop {
+            let batch = self.retrieve_vector_store_file_batch(vector_store_id, batch_id).await?;
+            if !batch.is_in_progress() {
+                return Ok(batch);
+            }
+            tokio::time::sleep(interval).await;
+        }
+    }
+
+    /// Creates a fine-tuning job which begins the process of creating a new model from a given dataset.
+    ///
+    /// See <https://platform.openai.com/docs/api-reference/fine-tuning/create>
     ///
     /// ```no_run
-    /// # let api_key = "";
+    /// # use tokio_test;
     /// # tokio_test::block_on(async {
-    /// let client = openai_rust::Client::new(api_key);
-    /// let models = client.list_models().await.unwrap();
+    /// # let api_key = "";
+    /// let c = openai_rust::Client::new(api_key);
+    /// let args = openai_rust::fine_tuning::FineTuningJobArguments::new("gpt-4o-mini-2024-07-18", "file-abc123");
+    /// let job = c.create_fine_tuning_job(args).await.unwrap();
+    /// println!("{}", job.status);
     /// # })
     /// ```
-    ///
-    /// See <https://platform.openai.com/docs/api-reference/models/list>.
-    pub async fn list_models(&self) -> Result<Vec<models::Model>, anyhow::Error> {
-        let mut url = BASE_URL.clone();
-        url.set_path("/v1/models");
+    #[cfg(feature = "fine_tuning")]
+    pub async fn create_fine_tuning_job(
+        &self,
+        args: fine_tuning::FineTuningJobArguments,
+    ) -> Result<fine_tuning::FineTuningJob> {
+        let url = self.url("/fine_tuning/jobs");
 
-        let res = self
-            .req_client
-            .get(url)
-            .bearer_auth(&self.key)
-            .send()
-            .await?;
+        let res = self.send(self.req_client.post(url).json(&args)).await?;
 
-        if res.status() == 200 {
-            Ok(res.json::<models::ListModelsResponse>().await?.data)
-        } else {
-            Err(anyhow!(res.text().await?))
-        }
+        self.read_json(res).await
     }
 
-    /// Given a list of messages comprising a conversation, the model will return a response.
+    /// Lists the fine-tuning jobs, newest first.
+    ///
+    /// See <https://platform.openai.com/docs/api-reference/fine-tuning/list>
     ///
-    /// See <https://platform.openai.com/docs/api-reference/chat>.
     /// ```no_run
-    /// # use tokio_test;
     /// # tokio_test::block_on(async {
-    /// # use openai_rust;
     /// # let api_key = "";
-    /// let client = openai_rust::Client::new(api_key);
-    /// let args = openai_rust::chat::ChatArguments::new("gpt-3.5-turbo", vec![
-    ///    openai_rust::chat::Message {
-    ///        role: "user".to_owned(),
-    ///        content: "Hello GPT!".to_owned(),
-    ///    }
-    /// ]);
-    /// let res = client.create_chat(args).await.unwrap();
-    /// println!("{}", res.choices[0].message.content);
+    /// use openai_rust::futures_util::StreamExt;
+    /// let c = openai_rust::Client::new(api_key);
+    /// let mut jobs = c.list_fine_tuning_jobs(Default::default());
+    /// while let Some(job) = jobs.next().await {
+    ///     let job = job.unwrap();
+    ///     println!("{}: {}", job.id, job.status);
+    /// }
     /// # })
     /// ```
-    pub async fn create_chat(
+    #[cfg(feature = "fine_tuning")]
+    pub fn list_fine_tuning_jobs(
         &self,
-        args: chat::ChatArguments,
-    ) -> Result<chat::ChatCompletion, anyhow::Error> {
-        let mut url = BASE_URL.clone();
-        url.set_path("/v1/chat/completions");
+        options: pagination::ListOptions,
+    ) -> impl futures_core::Stream<Item = Result<fine_tuning::FineTuningJob>> + Unpin + '_ {
+        self.paginate("/fine_tuning/jobs".to_owned(), vec![], options)
+    }
 
-        let res = self
-            .req_client
-            .post(url)
-            .bearer_auth(&self.key)
-            .json(&args)
-            .send()
-            .await?;
+    /// Upload a file, for use with other endpoints like batches or fine-tuning.
+    ///
+    /// See <https://platform.openai.com/docs/api-reference/files/create>.
+    ///
+    /// ```no_run
+    /// # tokio_test::block_on(async {
+    /// # let api_key = "";
+    /// use openai_rust::files::Upload;
+    /// let c = openai_rust::Client::new(api_key);
+    /// let file = c.upload_file(Upload::file("requests.jsonl").await.unwrap(), "batch").await.unwrap();
+    /// println!("uploaded {} bytes as {}", file.bytes, file.id);
+    /// # })
+    /// ```
+    #[cfg(feature = "files")]
+    pub async fn upload_file(&self, file: files::Upload, purpose: &str) -> Result<files::File> {
+        let url = self.url("/files");
 
-        if res.status() == 200 {
-            Ok(res.json().await?)
-        } else {
-            Err(anyhow!(res.text().await?))
-        }
+        let form = reqwest::multipart::Form::new()
+            .text("purpose", purpose.to_owned())
+            .part("file", file.into_part());
+        let res = self.send(self.req_client.post(url).multipart(form)).await?;
+
+        self.read_json(res).await
     }
 
-    /// Like [Client::create_chat] but with streaming.
+    /// Creates a batch from an uploaded file of requests, see [batches::input_jsonl].
     ///
-    /// See <https://platform.openai.com/docs/api-reference/chat>.
+    /// See <https://platform.openai.com/docs/api-reference/batch/create>.
+    #[cfg(feature = "batches")]
+    pub async fn create_batch(&self, args: batches::BatchArguments) -> Result<batches::Batch> {
+        let url = self.url("/batches");
+
+        let res = self.send(self.req_client.post(url).json(&args)).await?;
+
+        self.read_json(res).await
+    }
+
+    /// Retrieves a batch.
     ///
-    /// This method will return a stream of [chat::stream::ChatCompletionChunk]s. Use with [futures_util::StreamExt::next].
+    /// See <https://platform.openai.com/docs/api-reference/batch/retrieve>.
+    #[cfg(feature = "batches")]
+    pub async fn retrieve_batch(&self, batch_id: &str) -> Result<batches::Batch> {
+        let url = self.url(&format!("/batches/{}", batch_id));
+
+        let res = self.send(self.req_client.get(url)).await?;
+
+        self.read_json(res).await
+    }
+
+    /// Cancels a batch. The batch is `cancelling` for up to 10 minutes, after which it is `cancelled`
+    /// and the results of the finished requests are in its output file.
+    ///
+    /// See <https://platform.openai.com/docs/api-reference/batch/cancel>.
+    #[cfg(feature = "batches")]
+    pub async fn cancel_batch(&self, batch_id: &str) -> Result<batches::Batch> {
+        let url = self.url(&format!("/batches/{}/cancel", batch_id));
+
+        let res = self.send(self.req_client.post(url)).await?;
+
+        self.read_json(res).await
+    }
+
+    /// Lists the batches, newest first.
+    ///
+    /// See <https://platform.openai.com/docs/api-reference/batch/list>.
+    #[cfg(feature = "batches")]
+    pub fn list_batches(
+        &self,
+        options: pagination::ListOptions,
+    ) -> impl futures_core::Stream<Item = Result<batches::Batch>> + Unpin + '_ {
+        self.paginate("/batches".to_owned(), vec![], options)
+    }
+
+    /// Lists the uploaded files, newest first unless set otherwise in the `options`.
+    ///
+    /// See <https://platform.openai.com/docs/api-reference/files/list>.
     ///
     /// ```no_run
-    /// # use tokio_test;
     /// # tokio_test::block_on(async {
-    /// # use openai_rust;
-    /// # use std::io::Write;
-    /// # let client = openai_rust::Client::new("");
-    /// # let args = openai_rust::chat::ChatArguments::new("gpt-3.5-turbo", vec![
-    /// #    openai_rust::chat::Message {
-    /// #        role: "user".to_owned(),
-    /// #        content: "Hello GPT!".to_owned(),
-    /// #    }
-    /// # ]);
+    /// # let api_key = "";
+    /// use openai_rust::files::ListFiles;
     /// use openai_rust::futures_util::StreamExt;
-    /// let mut res = client.create_chat_stream(args).await.unwrap();
-    /// while let Some(chunk) = res.next().await {
-    ///     print!("{}", chunk.unwrap());
-    ///     std::io::stdout().flush().unwrap();
+    /// let c = openai_rust::Client::new(api_key);
+    /// let mut files = c.list_files(ListFiles::new().purpose("batch"), Default::default());
+    /// while let Some(file) = files.next().await {
+    ///     println!("{}", file.unwrap().filename);
     /// }
     /// # })
     /// ```
-    ///
-    pub async fn create_chat_stream(
+    #[cfg(feature = "files")]
+    pub fn list_files(
         &self,
-        args: chat::ChatArguments,
-    ) -> Result<chat::stream::ChatCompletionChunkStream> {
-        let mut url = BASE_URL.clone();
-        url.set_path("/v1/chat/completions");
+        query: files::ListFiles,
+        options: pagination::ListOptions,
+    ) -> impl futures_core::Stream<Item = Result<files::File>> + Unpin + '_ {
+        self.paginate("/files".to_owned(), query.query(), options)
+    }
 
-        // Enable streaming
-        let mut args = args;
-        args.stream = Some(true);
+    /// Returns information about an uploaded file.
+    ///
+    /// See <https://platform.openai.com/docs/api-reference/files/retrieve>.
+    #[cfg(feature = "files")]
+    pub async fn retrieve_file(&self, file_id: &str) -> Result<files::File> {
+        let url = self.url(&format!("/files/{}", file_id));
 
-        let res = self
-            .req_client
-            .post(url)
-            .bearer_auth(&self.key)
-            .json(&args)
-            .send()
-            .await?;
+        let res = self.send(self.req_client.get(url)).await?;
 
-        if res.status() == 200 {
-            Ok(chat::stream::ChatCompletionChunkStream::new(Box::pin(res.bytes_stream())))
-        } else {
-            Err(anyhow!(res.text().await?))
-        }
+        self.read_json(res).await
     }
 
-    /// Given a prompt, the model will return one or more predicted completions, and can also return the probabilities of alternative tokens at each position.
+    /// Deletes an uploaded file.
     ///
-    /// See <https://platform.openai.com/docs/api-reference/completions>
+    /// See <https://platform.openai.com/docs/api-reference/files/delete>.
+    #[cfg(feature = "files")]
+    pub async fn delete_file(&self, file_id: &str) -> Result<files::DeletedFile> {
+        let url = self.url(&format!("/files/{}", file_id));
+
+        let res = self.send(self.req_client.delete(url)).await?;
+
+        self.read_json(res).await
+    }
+
+    /// Returns the contents of the specified file.
+    ///
+    /// See <https://platform.openai.com/docs/api-reference/files/retrieve-contents>.
+    #[cfg(feature = "files")]
+    pub async fn retrieve_file_content(&self, file_id: &str) -> Result<bytes::Bytes> {
+        let url = self.url(&format!("/files/{}/content", file_id));
+
+        let res = self.send(self.req_client.get(url)).await?;
+
+        let res = self.check_stream(res).await?;
+        Ok(res.bytes().await?)
+    }
+
+    /// Wait for a batch to finish, and parse the results of all requests from its output and error files.
+    ///
+    /// Batches which expired or were cancelled resolve to the results of the requests that did finish.
+    /// Batches which failed validation resolve to an error.
     ///
     /// ```no_run
-    /// # use openai_rust::*;
     /// # use tokio_test;
     /// # tokio_test::block_on(async {
     /// # let api_key = "";
+    /// use openai_rust::batches::WaitOptions;
+    /// use openai_rust::chat::ChatCompletion;
     /// let c = openai_rust::Client::new(api_key);
-    /// let args = openai_rust::completions::CompletionArguments::new("text-davinci-003", "The quick brown fox".to_owned());
-    /// println!("{}", c.create_completion(args).await.unwrap().choices[0].text);
+    /// let options = WaitOptions::new().on_progress(|counts| {
+    ///     println!("{}/{} requests done", counts.completed + counts.failed, counts.total)
+    /// });
+    /// for output in c.wait_for_batch::<ChatCompletion>("batch_abc123", options).await.unwrap() {
+    ///     if let Ok(completion) = output.result {
+    ///         println!("{}: {}", output.custom_id, completion);
+    ///     }
+    /// }
     /// # })
     /// ```
-    pub async fn create_completion(
+    #[cfg(feature = "batches")]
+    pub async fn wait_for_batch<T: serde::de::DeserializeOwned>(
         &self,
-        args: completions::CompletionArguments,
-    ) -> Result<completions::CompletionResponse> {
-        let mut url = BASE_URL.clone();
-        url.set_path("/v1/completions");
+        batch_id: &str,
+        options: batches::WaitOptions,
+    ) -> Result<Vec<batches::BatchOutput<T>>> {
+        let deadline = options.deadline();
+        let mut interval = options.first_interval();
+        let batch = loop {
+            let batch = self.retrieve_batch(batch_id).await?;
+            options.progress(&batch.request_counts);
+            if batch.is_finished() {
+                break batch;
+            }
+            if deadline.is_some_and(|deadline| tokio::time::Instant::now() + interval > deadline) {
+                return Err(anyhow!("timed out waiting for batch {} with status {}", batch_id, batch.status).into());
+            }
+            tokio::time::sleep(interval).await;
+            interval = options.next_interval(interval);
+        };
 
-        let res = self
-            .req_client
-            .post(url)
-            .bearer_auth(&self.key)
-            .json(&args)
-            .send()
-            .await?;
+        if batch.status == "failed" {
+            let errors = batch.errors.map(|e| e.data).unwrap_or_default();
+            let messages: Vec<_> = errors.iter().map(|e| e.to_string()).collect();
+            return Err(anyhow!("batch {} failed: {}", batch_id, messages.join(", ")).into());
+        }
 
-        if res.status() == 200 {
-            Ok(res.json().await?)
-        } else {
-            Err(anyhow!(res.text().await?))
+        let mut outputs = vec![];
+        for file_id in [batch.output_file_id, batch.error_file_id].into_iter().flatten() {
+            let jsonl = self.retrieve_file_content(&file_id).await?;
+            outputs.extend(batches::parse_output(&String::from_utf8_lossy(&jsonl))?);
         }
+        Ok(outputs)
     }
 
-    /// Given a prompt and an instruction, the model will return an edited version of the prompt.
-    ///
-    /// See <https://platform.openai.com/docs/api-reference/edits>
+    /// Creates an image given a prompt.
     ///
     /// ```no_run
-    /// # use openai_rust;
-    /// # use tokio_test;
     /// # tokio_test::block_on(async {
     /// # let api_key = "";
+    /// use openai_rust::images::ImageArguments;
     /// let c = openai_rust::Client::new(api_key);
-    /// let args = openai_rust::edits::EditArguments::new("text-davinci-edit-001", "The quick brown fox".to_owned(), "Complete this sentence.".to_owned());
-    /// println!("{}", c.create_edit(args).await.unwrap().to_string());
+    /// let res = c.create_image(ImageArguments::new("A cute baby sea otter")).await.unwrap();
+    /// println!("{}", res.data[0].url.as_deref().unwrap_or_default());
     /// # })
     /// ```
-    ///
-    #[deprecated = "Use the chat api instead"]
-    pub async fn create_edit(&self, args: edits::EditArguments) -> Result<edits::EditResponse> {
-        let mut url = BASE_URL.clone();
-        url.set_path("/v1/edits");
+    #[cfg(feature = "images")]
+    pub async fn create_image(
+        &self,
+        args: images::ImageArguments,
+    ) -> Result<images::ImageResponse> {
+        let url = self.url("/images/generations");
 
-        let res = self
-            .req_client
-            .post(url)
-            .bearer_auth(&self.key)
-            .json(&args)
-            .send()
-            .await?;
+        let res = self.send(self.req_client.post(url).json(&args)).await?;
 
-        if res.status() == 200 {
-            Ok(res.json().await?)
-        } else {
-            Err(anyhow!(res.text().await?))
-        }
+        self.read_json(res).await
     }
 
-    /// Get a vector representation of a given input that can be easily consumed by machine learning models and algorithms.
+    /// Edits an image given a prompt, optionally only in the transparent areas of a mask.
     ///
-    /// See <https://platform.openai.com/docs/api-reference/embeddings>
+    /// See <https://platform.openai.com/docs/api-reference/images/createEdit>.
     ///
     /// ```no_run
-    /// # use openai_rust;
-    /// # use tokio_test;
     /// # tokio_test::block_on(async {
     /// # let api_key = "";
+    /// use openai_rust::files::Upload;
+    /// use openai_rust::images::ImageEditArguments;
     /// let c = openai_rust::Client::new(api_key);
-    /// let args = openai_rust::embeddings::EmbeddingsArguments::new("text-embedding-ada-002", "The food was delicious and the waiter...".to_owned());
-    /// println!("{:?}", c.create_embeddings(args).await.unwrap().data);
+    /// let image = Upload::file("otter.png").await.unwrap();
+    /// let mask = Upload::file("mask.png").await.unwrap();
+    /// let args = ImageEditArguments::new(image, "A sea otter wearing a beret").mask(mask);
+    /// let res = c.create_image_edit(args).await.unwrap();
     /// # })
     /// ```
+    #[cfg(feature = "images")]
+    pub async fn create_image_edit(&self, args: images::ImageEditArguments) -> Result<images::ImageResponse> {
+        let url = self.url("/images/edits");
+
+        let res = self.send(self.req_client.post(url).multipart(args.into_form())).await?;
+
+        self.read_json(res).await
+    }
+
+    /// Creates variations of an image.
     ///
-    pub async fn create_embeddings(
-        &self,
-        args: embeddings::EmbeddingsArguments,
-    ) -> Result<embeddings::EmbeddingsResponse> {
-        let mut url = BASE_URL.clone();
-        url.set_path("/v1/embeddings");
+    /// See <https://platform.openai.com/docs/api-reference/images/createVariation>.
+    #[cfg(feature = "images")]
+    pub async fn create_image_variation(&self, args: images::ImageVariationArguments) -> Result<images::ImageResponse> {
+        let url = self.url("/images/variations");
 
-        let res = self
-            .req_client
-            .post(url)
-            .bearer_auth(&self.key)
-            .json(&args)
-            .send()
-            .await?;
+        let res = self.send(self.req_client.post(url).multipart(args.into_form())).await?;
 
-        if res.status() == 200 {
-            Ok(res.json().await?)
-        } else {
-            Err(anyhow!(res.text().await?))
-        }
+        self.read_json(res).await
     }
+}
 
-    /// Creates an image given a prompt.
-    pub async fn create_image(
+/// Convert a Unix timestamp of the API.
+#[cfg(feature = "chrono")]
+fn timestamp(secs: u64) -> chrono::DateTime<chrono::Utc> {
+    chrono::DateTime::from_timestamp(secs.try_into().unwrap_or(i64::MAX), 0).unwrap_or_default()
+}
+
+/// The chat and embeddings endpoints of [Client] as a trait.
+///
+/// Write the LLM layer of an application against this trait,
+/// so it can be mocked in tests or swapped for another implementation.
+///
+/// ```
+/// use openai_rust::ChatProvider;
+/// use openai_rust::chat::{ChatArguments, ChatCompletion, Message};
+/// use openai_rust::error::Result;
+///
+/// async fn greet(llm: &impl ChatProvider) -> String {
+///     let args = ChatArguments::new("gpt-3.5-turbo", vec![
+///         Message::user("Hello GPT!")
+///     ]);
+///     llm.create_chat(args).await.unwrap().to_string()
+/// }
+///
+/// struct Mock;
+///
+/// impl ChatProvider for Mock {
+///     type ChatStream = openai_rust::futures_util::stream::Empty<Result<openai_rust::chat::stream::ChatCompletionChunk>>;
+///
+///     async fn create_chat(&self, args: ChatArguments) -> Result<ChatCompletion> {
+///         Ok(serde_json::from_str(r#"{
+///             "id": "chatcmpl-123",
+///             "created": 1677652288,
+///             "choices": [{
+///                 "index": 0,
+///                 "message": {"role": "assistant", "content": "Hello there!"},
+///                 "finish_reason": "stop"
+///             }]
+///         }"#)?)
+///     }
+///
+///     async fn create_chat_stream(&self, args: ChatArguments) -> Result<Self::ChatStream> {
+///         Ok(openai_rust::futures_util::stream::empty())
+///     }
+///
+///     async fn create_embeddings(&self, args: openai_rust::embeddings::EmbeddingsArguments) -> Result<openai_rust::embeddings::EmbeddingsResponse> {
+///         Err(anyhow::anyhow!("not implemented").into())
+///     }
+/// }
+///
+/// # tokio_test::block_on(async {
+/// assert_eq!(greet(&Mock).await, "Hello there!");
+/// # })
+/// ```
+#[cfg(all(feature = "chat", feature = "embeddings"))]
+pub trait ChatProvider {
+    /// The stream returned by [ChatProvider::create_chat_stream].
+    type ChatStream: futures_core::Stream<Item = Result<chat::stream::ChatCompletionChunk>>;
+
+    /// See [Client::create_chat].
+    fn create_chat(
         &self,
-        args: images::ImageArguments,
-    ) -> Result<Vec<String>> {
-        let mut url = BASE_URL.clone();
-        url.set_path("/v1/images/generations");
+        args: chat::ChatArguments,
+    ) -> impl std::future::Future<Output = Result<chat::ChatCompletion>> + Send;
 
-        let res = self
-            .req_client
-            .post(url)
-            .bearer_auth(&self.key)
-            .json(&args)
-            .send()
-            .await?;
+    /// See [Client::create_chat_stream].
+    fn create_chat_stream(
+        &self,
+        args: chat::ChatArguments,
+    ) -> impl std::future::Future<Output = Result<Self::ChatStream>> + Send;
 
-        if res.status() == 200 {
-            Ok(res.json::<images::ImageResponse>().await?.data.iter().map(|o|
-                match o {
-                    images::ImageObject::Url(s) => s.to_string(),
-                    images::ImageObject::Base64JSON(s) => s.to_string(),
-                }
-            ).collect())
-        } else {
-            Err(anyhow!(res.text().await?))
-        }
+    /// See [Client::create_embeddings].
+    fn create_embeddings(
+        &self,
+        args: embeddings::EmbeddingsArguments,
+    ) -> impl std::future::Future<Output = Result<embeddings::EmbeddingsResponse>> + Send;
+}
+
+#[cfg(all(feature = "chat", feature = "embeddings"))]
+impl ChatProvider for Client {
+    type ChatStream = chat::stream::ChatCompletionChunkStream;
+
+    async fn create_chat(&self, args: chat::ChatArguments) -> Result<chat::ChatCompletion> {
+        Client::create_chat(self, args).await
+    }
+
+    async fn create_chat_stream(&self, args: chat::ChatArguments) -> Result<Self::ChatStream> {
+        Client::create_chat_stream(self, args).await
+    }
+
+    async fn create_embeddings(&self, args: embeddings::EmbeddingsArguments) -> Result<embeddings::EmbeddingsResponse> {
+        Client::create_embeddings(self, args).await
     }
 }
@@ -0,0 +1,139 @@
+//! An opt-in logger for debugging requests to the API.
+//! Attach with [Client::with_logger](crate::Client::with_logger).
+//!
+//! The logger prints every outgoing request and incoming response, which makes it possible
+//! to see why the API rejected a request without setting up a proxy.
+//! The API key is redacted by default, user content can be redacted as well.
+//!
+//! ```
+//! use std::sync::{Arc, Mutex};
+//! use openai_rust::logger::Logger;
+//! let lines = Arc::new(Mutex::new(Vec::new()));
+//! let sink = lines.clone();
+//! let logger = Logger::new()
+//!     .redact_content(true)
+//!     .sink(move |line| sink.lock().unwrap().push(line.to_owned()));
+//!
+//! logger.log_response(reqwest::StatusCode::OK, r#"{"choices":[{"message":{"role":"assistant","content":"Hello!"}}]}"#);
+//! assert!(lines.lock().unwrap()[0].contains("[REDACTED 6 chars]"));
+//! ```
+use reqwest::header::{HeaderValue, AUTHORIZATION};
+use serde_json::Value;
+
+/// Fields of request and response bodies that contain user content.
+const CONTENT_FIELDS: &[&str] = &["content", "prompt", "input", "instruction", "suffix", "text"];
+
+/// Headers that contain the API key.
+const KEY_HEADERS: &[&str] = &["authorization", "api-key", "x-api-key"];
+
+/// Prints requests and responses.
+pub struct Logger {
+    redact_key: bool,
+    redact_content: bool,
+    sink: Box<dyn Fn(&str) + Send + Sync>,
+}
+
+impl Default for Logger {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Logger {
+    /// Create a logger printing to stderr, which redacts the API key.
+    pub fn new() -> Logger {
+        Logger {
+            redact_key: true,
+            redact_content: false,
+            sink: Box::new(|line| eprintln!("{}", line)),
+        }
+    }
+
+    /// Whether the API key should be redacted. Defaults to `true`.
+    pub fn redact_key(mut self, redact: bool) -> Logger {
+        self.redact_key = redact;
+        self
+    }
+
+    /// Whether user content such as messages and prompts should be redacted. Defaults to `false`.
+    pub fn redact_content(mut self, redact: bool) -> Logger {
+        self.redact_content = redact;
+        self
+    }
+
+    /// Send the log output somewhere else than stderr.
+    pub fn sink(mut self, sink: impl Fn(&str) + Send + Sync + 'static) -> Logger {
+        self.sink = Box::new(sink);
+        self
+    }
+
+    /// Log an outgoing request.
+    pub fn log_request(&self, request: &reqwest::Request) {
+        let mut out = format!("--> {} {}", request.method(), request.url());
+        for (name, value) in request.headers() {
+            out += &format!("\n{}: {}", name, self.header(name.as_str(), value));
+        }
+        if let Some(body) = request.body().and_then(|b| b.as_bytes()) {
+            out += "\n";
+            out += &self.body(&String::from_utf8_lossy(body));
+        }
+        (self.sink)(&out);
+    }
+
+    /// Log an incoming response.
+    pub fn log_response(&self, status: reqwest::StatusCode, body: &str) {
+        let out = format!("<-- {}\n{}", status, self.body(body));
+        (self.sink)(&out);
+    }
+
+    /// Log an incoming response of which the body is streamed.
+    pub fn log_stream(&self, status: reqwest::StatusCode) {
+        (self.sink)(&format!("<-- {} (streaming)", status));
+    }
+
+    fn header(&self, name: &str, value: &HeaderValue) -> String {
+        if self.redact_key && (name == AUTHORIZATION || KEY_HEADERS.contains(&name)) {
+            return "[REDACTED]".to_owned();
+        }
+        String::from_utf8_lossy(value.as_bytes()).into_owned()
+    }
+
+    fn body(&self, body: &str) -> String {
+        match serde_json::from_str::<Value>(body) {
+            Ok(mut json) => {
+                if self.redact_content {
+                    redact(&mut json);
+                }
+                serde_json::to_string_pretty(&json).unwrap_or_default()
+            }
+            Err(_) if self.redact_content => format!("[REDACTED {} bytes]", body.len()),
+            Err(_) => body.to_owned(),
+        }
+    }
+}
+
+/// Replace all strings in content fields, including those nested in arrays and objects.
+fn redact(json: &mut Value) {
+    match json {
+        Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                if CONTENT_FIELDS.contains(&key.as_str()) {
+                    redact_strings(value);
+                } else {
+                    redact(value);
+                }
+            }
+        }
+        Value::Array(values) => values.iter_mut().for_each(redact),
+        _ => {}
+    }
+}
+
+fn redact_strings(json: &mut Value) {
+    match json {
+        Value::String(s) => *s = format!("[REDACTED {} chars]", s.chars().count()),
+        Value::Object(map) => map.values_mut().for_each(redact_strings),
+        Value::Array(values) => values.iter_mut().for_each(redact_strings),
+        _ => {}
+    }
+}
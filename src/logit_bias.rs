@@ -0,0 +1,99 @@
+//! Make words more or less likely to appear, without knowing their token IDs.
+//!
+//! The API biases tokens, not words, and token IDs differ per model.
+//! A [LogitBias] looks up the tokens of a word with the [Tokenizer] of the target model,
+//! like one from the [tiktoken-rs](https://crates.io/crates/tiktoken-rs) crate.
+//!
+//! A word is tokenized differently at the start of a text and after a space,
+//! so both forms, and their capitalized versions, are biased.
+//! Words spanning several tokens bias every token, which also affects other words sharing those tokens.
+//!
+//! ```
+//! use openai_rust::logit_bias::LogitBias;
+//! # let tokenizer = |text: &str| text.bytes().map(u32::from).collect::<Vec<_>>();
+//! // let bpe = tiktoken_rs::o200k_base().unwrap();
+//! // let tokenizer = |text: &str| bpe.encode_ordinary(text);
+//! let bias = LogitBias::new().ban(&tokenizer, "delve").boost(&tokenizer, "!", 5);
+//! assert_eq!(bias.get(u32::from(b'!')), Some(5));
+//! let json = serde_json::to_value(&bias).unwrap();
+//! assert_eq!(json["100"], -100); // 'd'
+//!
+//! let args = openai_rust::chat::ChatArguments::new("gpt-4o", vec![]).logit_bias(bias);
+//! ```
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+/// Turns text into the token IDs of a model.
+pub trait Tokenizer {
+    fn encode(&self, text: &str) -> Vec<u32>;
+}
+
+impl<F: Fn(&str) -> Vec<u32>> Tokenizer for F {
+    fn encode(&self, text: &str) -> Vec<u32> {
+        self(text)
+    }
+}
+
+/// The bias of each token, between -100 (banned) and 100 (always chosen).
+///
+/// Serialized as the `logit_bias` parameter, a map of token IDs to biases.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(transparent)]
+pub struct LogitBias(HashMap<u32, i32>);
+
+impl LogitBias {
+    pub fn new() -> LogitBias {
+        LogitBias::default()
+    }
+
+    /// Bias a single token. The bias is clamped between -100 and 100.
+    pub fn token(mut self, token: u32, bias: i32) -> LogitBias {
+        self.0.insert(token, bias.clamp(-100, 100));
+        self
+    }
+
+    /// Bias the tokens of a word, see the [module documentation](self).
+    pub fn word(self, tokenizer: &impl Tokenizer, word: &str, bias: i32) -> LogitBias {
+        let word = word.trim();
+        let mut capitalized = word.chars();
+        let capitalized = match capitalized.next() {
+            Some(first) => first.to_uppercase().chain(capitalized).collect(),
+            None => String::new(),
+        };
+        let variants = [word.to_owned(), format!(" {}", word), capitalized.clone(), format!(" {}", capitalized)];
+        let mut tokens: Vec<u32> = variants.iter().flat_map(|variant| tokenizer.encode(variant)).collect();
+        tokens.sort_unstable();
+        tokens.dedup();
+        tokens.into_iter().fold(self, |logit_bias, token| logit_bias.token(token, bias))
+    }
+
+    /// Prevent a word from being generated.
+    pub fn ban(self, tokenizer: &impl Tokenizer, word: &str) -> LogitBias {
+        self.word(tokenizer, word, -100)
+    }
+
+    /// Make a word more likely. Values between 1 and 5 nudge the model, larger values can make it repeat the word.
+    pub fn boost(self, tokenizer: &impl Tokenizer, word: &str, bias: i32) -> LogitBias {
+        self.word(tokenizer, word, bias)
+    }
+
+    /// The bias of a token, if it has one.
+    pub fn get(&self, token: u32) -> Option<i32> {
+        self.0.get(&token).copied()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+}
+
+impl From<HashMap<u32, i32>> for LogitBias {
+    fn from(bias: HashMap<u32, i32>) -> Self {
+        LogitBias(bias)
+    }
+}
@@ -0,0 +1,139 @@
+//! Hooks for collecting metrics about the requests of a [Client](crate::Client).
+//! Attach with [Client::with_metrics](crate::Client::with_metrics).
+//!
+//! A [MetricsSink] is told about every request when its response arrives or it fails,
+//! and about the tokens used by every completion. With the `prometheus` feature,
+//! [PrometheusMetrics] records them in a [prometheus::Registry].
+//!
+//! ```
+//! use std::sync::atomic::{AtomicU32, Ordering};
+//! use openai_rust::metrics::{MetricsSink, RequestMetrics};
+//!
+//! #[derive(Default)]
+//! struct TokenCounter(AtomicU32);
+//!
+//! impl MetricsSink for TokenCounter {
+//!     fn request(&self, _request: &RequestMetrics) {}
+//!
+//!     fn usage(&self, _model: &str, prompt_tokens: u32, completion_tokens: u32) {
+//!         self.0.fetch_add(prompt_tokens + completion_tokens, Ordering::Relaxed);
+//!     }
+//! }
+//!
+//! let client = openai_rust::Client::new("").with_metrics(TokenCounter::default());
+//! ```
+use std::time::Duration;
+
+/// Receives the metrics of a [Client](crate::Client).
+pub trait MetricsSink: Send + Sync {
+    /// Called when the response to a request arrives, or the request fails without a response.
+    fn request(&self, request: &RequestMetrics);
+
+    /// Called with the tokens used by a completion, embedding or response.
+    fn usage(&self, model: &str, prompt_tokens: u32, completion_tokens: u32) {
+        let _ = (model, prompt_tokens, completion_tokens);
+    }
+}
+
+/// Shares a sink with the client, to read what it collected.
+impl<T: MetricsSink + ?Sized> MetricsSink for std::sync::Arc<T> {
+    fn request(&self, request: &RequestMetrics) {
+        (**self).request(request)
+    }
+
+    fn usage(&self, model: &str, prompt_tokens: u32, completion_tokens: u32) {
+        (**self).usage(model, prompt_tokens, completion_tokens)
+    }
+}
+
+/// A request sent to the API.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct RequestMetrics {
+    /// The path of the endpoint, with IDs replaced by `{id}`, like `/threads/{id}/runs`.
+    pub endpoint: String,
+    pub method: String,
+    /// The status of the response, or `None` if the request failed without one.
+    pub status: Option<u16>,
+    /// The time until the headers of the response arrived. Time spent waiting in a [queue](crate::queue) is not included.
+    pub duration: Duration,
+}
+
+/// The path of a request with the base path removed, and segments containing digits replaced by `{id}`.
+///
+/// Model names like `gpt-4o` count as IDs as well, which keeps the amount of endpoints small.
+pub(crate) fn endpoint(path: &str, base_path: &str) -> String {
+    let path = path.strip_prefix(base_path.trim_end_matches('/')).unwrap_or(path);
+    path.split('/')
+        .map(|segment| match segment.contains(|c: char| c.is_ascii_digit()) {
+            true => "{id}",
+            false => segment,
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Records requests and tokens as Prometheus metrics. Requires the `prometheus` feature.
+///
+/// Registers these metrics:
+/// - `openai_requests_total`, a counter labeled with the `endpoint` and the `status`, which is `error` without a response
+/// - `openai_request_duration_seconds`, a histogram labeled with the `endpoint`
+/// - `openai_tokens_total`, a counter labeled with the `model` and the `kind` of tokens, `prompt` or `completion`
+///
+/// ```
+/// let registry = prometheus::Registry::new();
+/// let metrics = openai_rust::metrics::PrometheusMetrics::register(&registry).unwrap();
+/// let client = openai_rust::Client::new("").with_metrics(metrics);
+/// ```
+#[cfg(feature = "prometheus")]
+#[derive(Debug, Clone)]
+pub struct PrometheusMetrics {
+    requests: prometheus::IntCounterVec,
+    durations: prometheus::HistogramVec,
+    tokens: prometheus::IntCounterVec,
+}
+
+#[cfg(feature = "prometheus")]
+impl PrometheusMetrics {
+    /// Create the metrics and register them in a registry.
+    pub fn register(registry: &prometheus::Registry) -> prometheus::Result<PrometheusMetrics> {
+        use prometheus::{HistogramOpts, HistogramVec, IntCounterVec, Opts};
+        let requests = IntCounterVec::new(
+            Opts::new("openai_requests_total", "Requests sent to the OpenAI API."),
+            &["endpoint", "status"],
+        )?;
+        let durations = HistogramVec::new(
+            HistogramOpts::new("openai_request_duration_seconds", "Time until the response of the OpenAI API arrived.")
+                .buckets(vec![0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0, 120.0]),
+            &["endpoint"],
+        )?;
+        let tokens = IntCounterVec::new(
+            Opts::new("openai_tokens_total", "Tokens used by the OpenAI API."),
+            &["model", "kind"],
+        )?;
+        registry.register(Box::new(requests.clone()))?;
+        registry.register(Box::new(durations.clone()))?;
+        registry.register(Box::new(tokens.clone()))?;
+        Ok(PrometheusMetrics {
+            requests,
+            durations,
+            tokens,
+        })
+    }
+}
+
+#[cfg(feature = "prometheus")]
+impl MetricsSink for PrometheusMetrics {
+    fn request(&self, request: &RequestMetrics) {
+        let status = request.status.map_or("error".to_owned(), |status| status.to_string());
+        self.requests.with_label_values(&[&request.endpoint, &status]).inc();
+        self.durations
+            .with_label_values(&[&request.endpoint])
+            .observe(request.duration.as_secs_f64());
+    }
+
+    fn usage(&self, model: &str, prompt_tokens: u32, completion_tokens: u32) {
+        self.tokens.with_label_values(&[model, "prompt"]).inc_by(prompt_tokens.into());
+        self.tokens.with_label_values(&[model, "completion"]).inc_by(completion_tokens.into());
+    }
+}
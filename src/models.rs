@@ -1,6 +1,8 @@
 //! See <https://platform.openai.com/docs/api-reference/models>.
 //! Use with [Client::list_models](crate::Client::list_models).
-use serde::Deserialize;
+//!
+//! The API does not list the limits of models, so [info] looks them up in a table of well-known models.
+use serde::{Deserialize, Serialize};
 
 #[derive(Deserialize, Debug)]
 pub(crate) struct ListModelsResponse {
@@ -8,16 +10,105 @@ pub(crate) struct ListModelsResponse {
 }
 
 /// Describes an OpenAI model offering that can be used with the API.
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
 pub struct Model {
     /// The model identifier, which can be referenced in the API endpoints.
     pub id: String,
     /// The organization that owns the model.
+    #[serde(default, deserialize_with = "crate::de::null_default")]
     pub owned_by: String,
     /// The Unix timestamp (in seconds) when the model was created.
+    #[serde(default, deserialize_with = "crate::de::null_default")]
     pub created: u64,
 }
 
+impl Model {
+    /// When this was created, see [created](Self::created).
+    ///
+    /// ```
+    /// # use openai_rust::models::Model;
+    /// let model: Model = serde_json::from_str(r#"{"id":"gpt-4o","created":1715367049,"owned_by":"system"}"#).unwrap();
+    /// assert_eq!(model.created_at().to_rfc3339(), "2024-05-10T18:50:49+00:00");
+    /// ```
+    #[cfg(feature = "chrono")]
+    pub fn created_at(&self) -> chrono::DateTime<chrono::Utc> {
+        crate::timestamp(self.created)
+    }
+}
+
+/// The limits of a model, see [info].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct ModelInfo {
+    /// The maximum amount of tokens of the prompt and the completion together.
+    pub context_window: u32,
+    /// The maximum amount of tokens of the completion.
+    pub max_output_tokens: u32,
+    /// Whether the model reasons before it answers, and takes `max_completion_tokens` instead of `max_tokens`.
+    pub reasoning: bool,
+}
+
+const fn model(context_window: u32, max_output_tokens: u32, reasoning: bool) -> ModelInfo {
+    ModelInfo {
+        context_window,
+        max_output_tokens,
+        reasoning,
+    }
+}
+
+/// Models by the start of their ID, so dated snapshots like `gpt-4o-2024-08-06` are included.
+/// The longest matching prefix wins, so `gpt-4-32k` is not taken for `gpt-4`.
+const MODELS: &[(&str, ModelInfo)] = &[
+    ("gpt-3.5-turbo", model(16_385, 4_096, false)),
+    ("gpt-4", model(8_192, 8_192, false)),
+    ("gpt-4-32k", model(32_768, 32_768, false)),
+    ("gpt-4-turbo", model(128_000, 4_096, false)),
+    ("gpt-4o", model(128_000, 16_384, false)),
+    ("gpt-4.1", model(1_047_576, 32_768, false)),
+    ("gpt-5", model(400_000, 128_000, true)),
+    ("o1", model(200_000, 100_000, true)),
+    ("o1-mini", model(128_000, 65_536, true)),
+    ("o3", model(200_000, 100_000, true)),
+    ("o4-mini", model(200_000, 100_000, true)),
+];
+
+/// The limits of a well-known model, including snapshots and fine-tuned versions of it.
+///
+/// ```
+/// use openai_rust::models::info;
+/// assert_eq!(info("gpt-4o-mini-2024-07-18").unwrap().max_output_tokens, 16_384);
+/// assert_eq!(info("ft:gpt-4o-mini:my-org::abc123").unwrap().context_window, 128_000);
+/// assert_eq!(info("gpt-4-0613").unwrap().context_window, 8_192);
+/// assert_eq!(info("gpt-4-32k-0613").unwrap().context_window, 32_768);
+/// assert!(info("llama3").is_none());
+/// ```
+pub fn info(model: &str) -> Option<ModelInfo> {
+    let model = model.strip_prefix("ft:").unwrap_or(model);
+    MODELS
+        .iter()
+        .filter(|(prefix, _)| model.starts_with(prefix))
+        .max_by_key(|(prefix, _)| prefix.len())
+        .map(|(_, info)| *info)
+}
+
+/// The most tokens a model can generate after a prompt of `prompt_tokens`,
+/// or `None` for unknown models and prompts that leave no room for a completion.
+///
+/// Token estimates are rough, so a quarter of the prompt is kept free as a margin.
+///
+/// ```
+/// use openai_rust::models::max_output_tokens;
+/// assert_eq!(max_output_tokens("gpt-4o", 1_000), Some(16_384));
+/// assert_eq!(max_output_tokens("gpt-4", 6_000), Some(692));
+/// assert_eq!(max_output_tokens("gpt-4", 8_000), None);
+/// ```
+pub fn max_output_tokens(model: &str, prompt_tokens: usize) -> Option<u32> {
+    let info = info(model)?;
+    let prompt = u32::try_from(prompt_tokens + prompt_tokens / 4).unwrap_or(u32::MAX);
+    Some(info.context_window.saturating_sub(prompt).min(info.max_output_tokens)).filter(|max| *max > 0)
+}
+
 // /// Permissions of a model
 // #[derive(Deserialize, Debug)]
 // pub struct ModelPermission {
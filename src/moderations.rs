@@ -0,0 +1,112 @@
+//! See <https://platform.openai.com/docs/api-reference/moderations>.
+//! Use with [Client::create_moderation](crate::Client::create_moderation),
+//! or check every chat request with [Client::with_moderation](crate::Client::with_moderation).
+//!
+//! ```no_run
+//! # tokio_test::block_on(async {
+//! # let api_key = "";
+//! # let args = openai_rust::chat::ChatArguments::new("gpt-4o", vec![]);
+//! use openai_rust::moderations::Flagged;
+//! let client = openai_rust::Client::new(api_key).with_moderation();
+//! match client.create_chat(args).await {
+//!     Ok(completion) => println!("{}", completion),
+//!     Err(e) => match e.downcast_ref::<Flagged>() {
+//!         Some(flagged) => println!("rejected for {}", flagged.categories.join(", ")),
+//!         None => println!("error: {}", e),
+//!     },
+//! }
+//! # })
+//! ```
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+/// The moderation model used by [Client::with_moderation](crate::Client::with_moderation).
+pub const DEFAULT_MODEL: &str = "omni-moderation-latest";
+
+/// Request arguments for classifying text as potentially harmful.
+///
+/// See <https://platform.openai.com/docs/api-reference/moderations/create>.
+///
+/// ```
+/// use openai_rust::moderations::ModerationArguments;
+/// let args = ModerationArguments::new("omni-moderation-latest", vec!["I want to hurt them.".to_owned()]);
+/// ```
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct ModerationArguments {
+    /// ID of the model to use, like `omni-moderation-latest`.
+    pub model: String,
+    /// The texts to classify, each with its own [ModerationResult].
+    pub input: Vec<String>,
+    /// Additional parameters sent in the body, for parameters not yet supported by this crate or specific to a provider.
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    pub extra: Option<serde_json::Map<String, serde_json::Value>>,
+}
+
+impl ModerationArguments {
+    pub fn new(model: impl AsRef<str>, input: Vec<String>) -> ModerationArguments {
+        ModerationArguments {
+            model: model.as_ref().to_owned(),
+            input,
+            extra: None,
+        }
+    }
+}
+
+/// The response of a moderation request.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct ModerationResponse {
+    pub id: String,
+    pub model: String,
+    /// A result for every input, in the same order.
+    pub results: Vec<ModerationResult>,
+}
+
+/// Whether an input is potentially harmful, and in which categories.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[non_exhaustive]
+pub struct ModerationResult {
+    pub flagged: bool,
+    /// Whether the input violates each category, like `harassment` or `violence/graphic`.
+    #[serde(default)]
+    pub categories: HashMap<String, bool>,
+    /// The confidence of the model for each category, between 0 and 1.
+    #[serde(default)]
+    pub category_scores: HashMap<String, f64>,
+}
+
+impl ModerationResult {
+    /// The categories the input was flagged for, sorted by name.
+    pub fn flagged_categories(&self) -> Vec<String> {
+        let mut categories: Vec<String> = self
+            .categories
+            .iter()
+            .filter(|(_, flagged)| **flagged)
+            .map(|(category, _)| category.clone())
+            .collect();
+        categories.sort();
+        categories
+    }
+}
+
+/// A message was flagged by the moderation check of [Client::with_moderation](crate::Client::with_moderation),
+/// so the request was not sent.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Flagged {
+    /// The flagged message.
+    pub input: String,
+    /// The categories the message was flagged for, sorted by name.
+    pub categories: Vec<String>,
+    /// The full result of the moderation model.
+    pub result: ModerationResult,
+}
+
+impl std::fmt::Display for Flagged {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "the message was flagged for {}", self.categories.join(", "))
+    }
+}
+
+impl std::error::Error for Flagged {}
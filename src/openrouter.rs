@@ -0,0 +1,58 @@
+//! Support for [OpenRouter](https://openrouter.ai), which serves many models through an OpenAI compatible API.
+//! Use with [Client::new_openrouter](crate::Client::new_openrouter).
+//!
+//! The same [ChatArguments](crate::chat::ChatArguments) can be sent to OpenRouter,
+//! with the optional `provider`, `route` and `models` fields to control how OpenRouter routes the request.
+//!
+//! ```
+//! use openai_rust::chat::{ChatArguments, Message};
+//! use openai_rust::openrouter::ProviderPreferences;
+//! let mut args = ChatArguments::new("anthropic/claude-3.5-sonnet", vec![
+//!     Message::user("Hello!")
+//! ]);
+//! args.provider = Some(ProviderPreferences {
+//!     order: Some(vec!["Anthropic".to_owned()]),
+//!     allow_fallbacks: Some(false),
+//!     ..Default::default()
+//! });
+//! ```
+//!
+//! See <https://openrouter.ai/docs/api-reference/overview>.
+use serde::{Deserialize, Serialize};
+
+/// The base URL of the OpenRouter API.
+pub const BASE_URL: &str = "https://openrouter.ai/api/v1";
+
+/// Preferences for the providers that OpenRouter routes a request to.
+///
+/// See <https://openrouter.ai/docs/features/provider-routing>.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct ProviderPreferences {
+    /// Providers to try in order, such as `"OpenAI"` or `"Together"`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub order: Option<Vec<String>>,
+
+    /// Whether other providers may be used when the preferred ones are unavailable. Defaults to `true`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_fallbacks: Option<bool>,
+
+    /// Only use providers that support all parameters of the request.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub require_parameters: Option<bool>,
+
+    /// Whether providers that may store or train on the data can be used, either `"allow"` or `"deny"`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data_collection: Option<String>,
+
+    /// Only use these providers.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub only: Option<Vec<String>>,
+
+    /// Never use these providers.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ignore: Option<Vec<String>>,
+
+    /// Sort providers by `"price"`, `"throughput"` or `"latency"`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sort: Option<String>,
+}
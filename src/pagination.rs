@@ -0,0 +1,160 @@
+//! Lists returned by the API are split into pages.
+//! Methods returning a list in this crate return a [Stream](futures_core::Stream) that fetches the next page when needed.
+//!
+//! The pages can be controlled with [ListOptions], like to fetch the newest items first.
+//!
+//! ```no_run
+//! # tokio_test::block_on(async {
+//! # let api_key = "";
+//! use openai_rust::futures_util::TryStreamExt;
+//! use openai_rust::pagination::{ListOptions, Order};
+//! let client = openai_rust::Client::new(api_key);
+//! let items: Vec<_> = client
+//!     .list_response_input_items("resp_123", ListOptions::new().limit(100).order(Order::Desc))
+//!     .try_collect()
+//!     .await
+//!     .unwrap();
+//! # })
+//! ```
+use serde::{Deserialize, Serialize};
+
+/// A single page of a list.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct Page<T> {
+    pub data: Vec<T>,
+    /// The ID of the first item of the page.
+    #[serde(default)]
+    pub first_id: Option<String>,
+    /// The ID of the last item of the page, used to request the next page.
+    #[serde(default)]
+    pub last_id: Option<String>,
+    /// Whether there are more items after this page.
+    #[serde(default)]
+    pub has_more: bool,
+}
+
+impl Page<serde_json::Value> {
+    /// Take the first and last ID from the items, for lists like the fine-tuning jobs that leave them out.
+    pub(crate) fn fill_ids(&mut self) {
+        let id = |item: Option<&serde_json::Value>| item?.get("id")?.as_str().map(str::to_owned);
+        if self.first_id.is_none() {
+            self.first_id = id(self.data.first());
+        }
+        if self.last_id.is_none() {
+            self.last_id = id(self.data.last());
+        }
+    }
+}
+
+/// Options of a list endpoint, sent as query parameters.
+///
+/// ```
+/// use openai_rust::pagination::{ListOptions, Order};
+/// let options = ListOptions::new().limit(20).after("msg_abc123").order(Order::Asc);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ListOptions {
+    /// The amount of items of each page, between 1 and 100. Defaults to 20.
+    pub limit: Option<u32>,
+    /// Start with the items after this ID.
+    pub after: Option<String>,
+    /// Start with the items before this ID, and continue towards the start of the list.
+    ///
+    /// Together with [after](ListOptions::after), only the items between the two IDs are listed.
+    pub before: Option<String>,
+    /// The order of the items by creation time.
+    pub order: Option<Order>,
+}
+
+impl ListOptions {
+    pub fn new() -> ListOptions {
+        ListOptions::default()
+    }
+
+    pub fn limit(mut self, limit: u32) -> ListOptions {
+        self.limit = Some(limit);
+        self
+    }
+
+    pub fn after(mut self, after: impl AsRef<str>) -> ListOptions {
+        self.after = Some(after.as_ref().to_owned());
+        self
+    }
+
+    pub fn before(mut self, before: impl AsRef<str>) -> ListOptions {
+        self.before = Some(before.as_ref().to_owned());
+        self
+    }
+
+    pub fn order(mut self, order: Order) -> ListOptions {
+        self.order = Some(order);
+        self
+    }
+
+    /// The query parameters of the first page.
+    pub(crate) fn query(&self) -> Vec<(&'static str, String)> {
+        let mut query = vec![];
+        if let Some(limit) = self.limit {
+            query.push(("limit", limit.to_string()));
+        }
+        if let Some(order) = self.order {
+            query.push(("order", order.as_str().to_owned()));
+        }
+        // The pages move from before towards after, which stays the same for every page
+        if let (Some(after), Some(_)) = (&self.after, &self.before) {
+            query.push(("after", after.clone()));
+        }
+        query
+    }
+
+    /// Where the first page starts.
+    pub(crate) fn cursor(&self) -> Cursor {
+        match (&self.after, &self.before) {
+            (_, Some(before)) => Cursor::Before(before.clone()),
+            (Some(after), None) => Cursor::After(after.clone()),
+            (None, None) => Cursor::Start,
+        }
+    }
+}
+
+/// The order of the items of a list.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum Order {
+    /// Oldest first.
+    Asc,
+    /// Newest first.
+    Desc,
+}
+
+impl Order {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Order::Asc => "asc",
+            Order::Desc => "desc",
+        }
+    }
+}
+
+/// Where the next page of a list starts.
+pub(crate) enum Cursor {
+    Start,
+    After(String),
+    Before(String),
+    Done,
+}
+
+impl Cursor {
+    /// The page after `page`, in the direction of `self`.
+    pub(crate) fn next<T>(&self, page: &Page<T>) -> Cursor {
+        if !page.has_more {
+            return Cursor::Done;
+        }
+        let next = match self {
+            Cursor::Before(_) => page.first_id.clone().map(Cursor::Before),
+            _ => page.last_id.clone().map(Cursor::After),
+        };
+        next.unwrap_or(Cursor::Done)
+    }
+}
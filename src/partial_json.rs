@@ -0,0 +1,293 @@
+//! Parse the arguments of a function call while they are being streamed.
+//!
+//! The arguments of a function call arrive as chunks of a JSON string, which is only valid JSON once complete.
+//! [PartialJson] collects the chunks and parses them into the value that is complete so far,
+//! so the arguments can be shown while they are generated.
+//!
+//! Unfinished strings are included, so text appears as it is written.
+//! Unfinished numbers, `true`, `false` and `null` are left out until they are complete, as are object keys without a value.
+//!
+//! ```
+//! use openai_rust::partial_json::PartialJson;
+//! use openai_rust::realtime::ServerEvent;
+//! # let events: Vec<ServerEvent> = [r#"{"query": "rust "#, r#"async"#, r#"", "limit": 1"#, r#"0}"#]
+//! #     .into_iter()
+//! #     .map(|delta| ServerEvent::FunctionCallArgumentsDelta {
+//! #         response_id: "resp_001".to_owned(),
+//! #         item_id: "fc_001".to_owned(),
+//! #         call_id: "call_001".to_owned(),
+//! #         delta: delta.to_owned(),
+//! #     })
+//! #     .collect();
+//! let mut arguments = PartialJson::new();
+//! let mut queries = vec![];
+//! for event in events {
+//!     if let ServerEvent::FunctionCallArgumentsDelta { delta, .. } = event {
+//!         if let Some(value) = arguments.push(&delta) {
+//!             queries.push(value["query"].as_str().unwrap_or_default().to_owned());
+//!         }
+//!     }
+//! }
+//! assert_eq!(queries, ["rust ", "rust async", "rust async", "rust async"]);
+//! assert!(arguments.is_complete());
+//! assert_eq!(arguments.value().unwrap()["limit"], 10);
+//! ```
+use serde_json::{Map, Value};
+
+/// The chunks of a JSON text received so far.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PartialJson {
+    text: String,
+}
+
+impl PartialJson {
+    pub fn new() -> PartialJson {
+        PartialJson::default()
+    }
+
+    /// Add a chunk and return the value that is complete so far, see [parse].
+    pub fn push(&mut self, delta: &str) -> Option<Value> {
+        self.text.push_str(delta);
+        self.value()
+    }
+
+    /// The value that is complete so far, see [parse].
+    pub fn value(&self) -> Option<Value> {
+        parse(&self.text)
+    }
+
+    /// Whether the text received so far is a complete JSON value.
+    pub fn is_complete(&self) -> bool {
+        serde_json::from_str::<Value>(&self.text).is_ok()
+    }
+
+    /// The text received so far.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+}
+
+/// Parse the start of a JSON text, closing the strings, arrays and objects that are still open.
+///
+/// Returns `None` if no value is complete yet, or if the text is not the start of valid JSON.
+///
+/// ```
+/// use openai_rust::partial_json::parse;
+/// use serde_json::json;
+/// assert_eq!(parse(r#"{"location": "San Fr"#), Some(json!({"location": "San Fr"})));
+/// assert_eq!(parse(r#"{"location": "Paris", "days": 1"#), Some(json!({"location": "Paris"})));
+/// assert_eq!(parse(r#"{"tags": ["a", "b\u00"#), Some(json!({"tags": ["a", "b"]})));
+/// assert_eq!(parse(r#"{"units"#), Some(json!({})));
+/// assert_eq!(parse(r#"[1, tr"#), Some(json!([1])));
+/// assert_eq!(parse("42"), Some(json!(42)));
+/// assert_eq!(parse(""), None);
+/// assert_eq!(parse(r#"{"a": 1}}"#), None);
+/// ```
+pub fn parse(text: &str) -> Option<Value> {
+    if let Ok(value) = serde_json::from_str(text) {
+        return Some(value);
+    }
+    let mut parser = Parser { text, pos: 0 };
+    let value = parser.value().ok()??;
+    parser.whitespace();
+    match parser.pos == text.len() {
+        true => Some(value),
+        false => None,
+    }
+}
+
+/// The text is not the start of valid JSON.
+struct Invalid;
+
+/// Parses values until the end of the text. `Ok(None)` means the text ended before a value was complete.
+struct Parser<'a> {
+    text: &'a str,
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<u8> {
+        self.text.as_bytes().get(self.pos).copied()
+    }
+
+    fn whitespace(&mut self) {
+        while matches!(self.peek(), Some(b' ' | b'\t' | b'\n' | b'\r')) {
+            self.pos += 1;
+        }
+    }
+
+    fn value(&mut self) -> Result<Option<Value>, Invalid> {
+        self.whitespace();
+        match self.peek() {
+            None => Ok(None),
+            Some(b'{') => self.object().map(Some),
+            Some(b'[') => self.array().map(Some),
+            Some(b'"') => Ok(Some(Value::String(self.string()?.0))),
+            Some(b'-' | b'0'..=b'9') => self.number(),
+            Some(_) => self.literal(),
+        }
+    }
+
+    fn object(&mut self) -> Result<Value, Invalid> {
+        self.pos += 1;
+        let mut map = Map::new();
+        loop {
+            self.whitespace();
+            match self.peek() {
+                None => break,
+                Some(b'}') => {
+                    self.pos += 1;
+                    break;
+                }
+                Some(b',') if !map.is_empty() => {
+                    self.pos += 1;
+                    self.whitespace();
+                }
+                Some(_) if map.is_empty() => {}
+                Some(_) => return Err(Invalid),
+            }
+            match self.peek() {
+                None => break,
+                Some(b'"') => {}
+                Some(_) => return Err(Invalid),
+            }
+            let (key, complete) = self.string()?;
+            self.whitespace();
+            if !complete || self.peek().is_none() {
+                break;
+            }
+            if self.peek() != Some(b':') {
+                return Err(Invalid);
+            }
+            self.pos += 1;
+            match self.value()? {
+                Some(value) => map.insert(key, value),
+                None => break,
+            };
+        }
+        Ok(Value::Object(map))
+    }
+
+    fn array(&mut self) -> Result<Value, Invalid> {
+        self.pos += 1;
+        let mut items = vec![];
+        loop {
+            self.whitespace();
+            match self.peek() {
+                None => break,
+                Some(b']') => {
+                    self.pos += 1;
+                    break;
+                }
+                Some(b',') if !items.is_empty() => self.pos += 1,
+                Some(_) if items.is_empty() => {}
+                Some(_) => return Err(Invalid),
+            }
+            match self.value()? {
+                Some(value) => items.push(value),
+                None => break,
+            }
+        }
+        Ok(Value::Array(items))
+    }
+
+    /// A string, and whether its closing quote was reached. An unfinished escape at the end is left out.
+    fn string(&mut self) -> Result<(String, bool), Invalid> {
+        self.pos += 1;
+        let mut string = String::new();
+        let mut chars = self.text[self.pos..].char_indices();
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '"' => {
+                    self.pos += i + 1;
+                    return Ok((string, true));
+                }
+                '\\' => {
+                    let escape = match chars.next() {
+                        Some((_, escape)) => escape,
+                        None => break,
+                    };
+                    let c = match escape {
+                        '"' | '\\' | '/' => escape,
+                        'b' => '\u{8}',
+                        'f' => '\u{c}',
+                        'n' => '\n',
+                        'r' => '\r',
+                        't' => '\t',
+                        'u' => match unicode_escape(&mut chars)? {
+                            Some(c) => c,
+                            None => break,
+                        },
+                        _ => return Err(Invalid),
+                    };
+                    string.push(c);
+                }
+                c if c < ' ' => return Err(Invalid),
+                c => string.push(c),
+            }
+        }
+        self.pos = self.text.len();
+        Ok((string, false))
+    }
+
+    /// A number, which is only complete once something follows it.
+    fn number(&mut self) -> Result<Option<Value>, Invalid> {
+        let start = self.pos;
+        while matches!(self.peek(), Some(b'-' | b'+' | b'.' | b'e' | b'E' | b'0'..=b'9')) {
+            self.pos += 1;
+        }
+        if self.peek().is_none() {
+            return Ok(None);
+        }
+        serde_json::from_str(&self.text[start..self.pos]).map(Some).map_err(|_| Invalid)
+    }
+
+    fn literal(&mut self) -> Result<Option<Value>, Invalid> {
+        let rest = &self.text[self.pos..];
+        for (literal, value) in [("true", Value::Bool(true)), ("false", Value::Bool(false)), ("null", Value::Null)] {
+            if rest.starts_with(literal) {
+                self.pos += literal.len();
+                return Ok(Some(value));
+            }
+            if literal.starts_with(rest) {
+                self.pos = self.text.len();
+                return Ok(None);
+            }
+        }
+        Err(Invalid)
+    }
+}
+
+/// The character of a `\uXXXX` escape, after the `\u`. `Ok(None)` if the text ends before it is complete.
+fn unicode_escape(chars: &mut std::str::CharIndices) -> Result<Option<char>, Invalid> {
+    let Some(high) = hex4(chars)? else {
+        return Ok(None);
+    };
+    if !(0xD800..0xDC00).contains(&high) {
+        return char::from_u32(high).map(Some).ok_or(Invalid);
+    }
+    // A surrogate pair, the low half follows as another escape.
+    match (chars.next(), chars.next()) {
+        (Some((_, '\\')), Some((_, 'u'))) => {}
+        (None, _) | (Some((_, '\\')), None) => return Ok(None),
+        _ => return Err(Invalid),
+    }
+    let Some(low) = hex4(chars)? else {
+        return Ok(None);
+    };
+    if !(0xDC00..0xE000).contains(&low) {
+        return Err(Invalid);
+    }
+    char::from_u32(0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)).map(Some).ok_or(Invalid)
+}
+
+fn hex4(chars: &mut std::str::CharIndices) -> Result<Option<u32>, Invalid> {
+    let mut code = 0;
+    for _ in 0..4 {
+        let Some((_, c)) = chars.next() else {
+            return Ok(None);
+        };
+        code = code * 16 + c.to_digit(16).ok_or(Invalid)?;
+    }
+    Ok(Some(code))
+}
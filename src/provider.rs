@@ -0,0 +1,139 @@
+//! Profiles for vendors offering an OpenAI compatible API.
+//! Use with [Client::with_provider](crate::Client::with_provider).
+//!
+//! Every [Provider] knows its base URL, how it expects the API key to be sent,
+//! and which parameters it rejects. Those parameters are stripped from requests before they are sent,
+//! so the same arguments can be used for every provider.
+//!
+//! ```
+//! use openai_rust::provider::Provider;
+//! # let api_key = "";
+//! let client = openai_rust::Client::new(api_key).with_provider(Provider::Groq).unwrap();
+//! assert!(Provider::Groq.unsupported_parameters().contains(&"logit_bias"));
+//! ```
+use anyhow::anyhow;
+use reqwest::Url;
+
+/// A vendor of an OpenAI compatible API.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum Provider {
+    /// The OpenAI API itself.
+    #[default]
+    OpenAI,
+    /// [Azure OpenAI](https://learn.microsoft.com/en-us/azure/ai-services/openai/reference).
+    ///
+    /// Requests are sent to a deployment, so the `model` of the arguments is ignored.
+    Azure {
+        /// The name of the Azure OpenAI resource, the subdomain of `openai.azure.com`.
+        resource: String,
+        /// The name of the deployed model.
+        deployment: String,
+        /// The API version, such as `2024-06-01`.
+        api_version: String,
+    },
+    /// [Groq](https://console.groq.com/docs/openai).
+    Groq,
+    /// [Mistral](https://docs.mistral.ai/api/).
+    Mistral,
+    /// [DeepSeek](https://api-docs.deepseek.com/).
+    DeepSeek,
+    /// [Together AI](https://docs.together.ai/docs/openai-api-compatibility).
+    Together,
+}
+
+/// How the API key is sent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Auth {
+    /// `Authorization: Bearer <key>`
+    Bearer,
+    /// The key as the value of a header with the given name.
+    Header(&'static str),
+}
+
+impl Provider {
+    /// The URL that endpoint paths are appended to.
+    ///
+    /// Fails if the resource of [Provider::Azure] is not a valid subdomain, or the deployment is empty.
+    /// The deployment is percent-encoded.
+    pub fn base_url(&self) -> anyhow::Result<Url> {
+        let url = match self {
+            Provider::OpenAI => "https://api.openai.com/v1",
+            Provider::Azure {
+                resource,
+                deployment,
+                ..
+            } => {
+                if resource.is_empty() || !resource.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+                    return Err(anyhow!("invalid name of Azure resource {:?}", resource));
+                }
+                if deployment.is_empty() || deployment == "." || deployment == ".." {
+                    return Err(anyhow!("invalid name of Azure deployment {:?}", deployment));
+                }
+                let mut url = Url::parse(&format!("https://{}.openai.azure.com/openai/deployments", resource))?;
+                url.path_segments_mut().unwrap().push(deployment);
+                return Ok(url);
+            }
+            Provider::Groq => "https://api.groq.com/openai/v1",
+            Provider::Mistral => "https://api.mistral.ai/v1",
+            Provider::DeepSeek => "https://api.deepseek.com/v1",
+            Provider::Together => "https://api.together.xyz/v1",
+        };
+        Ok(Url::parse(url).unwrap())
+    }
+
+    pub fn auth(&self) -> Auth {
+        match self {
+            Provider::Azure { .. } => Auth::Header("api-key"),
+            _ => Auth::Bearer,
+        }
+    }
+
+    /// Query parameters that have to be added to every request.
+    pub fn query(&self) -> Vec<(&'static str, String)> {
+        match self {
+            Provider::Azure { api_version, .. } => vec![("api-version", api_version.clone())],
+            _ => vec![],
+        }
+    }
+
+    /// Parameters of request bodies which the provider rejects.
+    pub fn unsupported_parameters(&self) -> &'static [&'static str] {
+        match self {
+            Provider::OpenAI | Provider::Azure { .. } => &[],
+            Provider::Groq => &["logprobs", "top_logprobs", "logit_bias", "n"],
+            Provider::Mistral => &["user", "logit_bias", "logprobs", "top_logprobs", "n"],
+            Provider::DeepSeek => &["n", "logit_bias", "user"],
+            Provider::Together => &["user"],
+        }
+    }
+
+    /// Authenticate a request with the given key.
+    pub(crate) fn authenticate(&self, req: &mut reqwest::Request, key: &str) -> anyhow::Result<()> {
+        use reqwest::header::{HeaderName, HeaderValue, AUTHORIZATION};
+        let (name, mut value) = match self.auth() {
+            Auth::Bearer => (AUTHORIZATION, HeaderValue::from_str(&format!("Bearer {}", key))?),
+            Auth::Header(name) => (HeaderName::from_static(name), HeaderValue::from_str(key)?),
+        };
+        value.set_sensitive(true);
+        req.headers_mut().insert(name, value);
+        Ok(())
+    }
+
+    /// Remove the unsupported parameters from a JSON request body.
+    pub(crate) fn strip_unsupported(&self, req: &mut reqwest::Request) {
+        let unsupported = self.unsupported_parameters();
+        if unsupported.is_empty() {
+            return;
+        }
+        let Some(body) = req.body().and_then(|b| b.as_bytes()) else {
+            return;
+        };
+        let Ok(serde_json::Value::Object(mut map)) = serde_json::from_slice(body) else {
+            return;
+        };
+        for param in unsupported {
+            map.remove(*param);
+        }
+        *req.body_mut() = Some(serde_json::to_vec(&map).unwrap().into());
+    }
+}
@@ -0,0 +1,148 @@
+//! A priority queue limiting the amount of concurrent requests of a [Client](crate::Client).
+//! Attach with [Client::with_queue](crate::Client::with_queue).
+//!
+//! Once the limit is reached, new requests wait in the queue. Whenever a request finishes,
+//! the oldest request of the highest [Priority] is sent next, so user-facing requests
+//! jump ahead of background jobs. A queue can be shared between clients by cloning it.
+//! The [rate limiter](crate::rate_limit) of the client serves waiting requests by the same priority.
+//!
+//! ```
+//! # tokio_test::block_on(async {
+//! use openai_rust::queue::{Priority, RequestQueue};
+//! let queue = RequestQueue::new(1);
+//! let running = queue.acquire(Priority::Background).await;
+//!
+//! let background = tokio::spawn({
+//!     let queue = queue.clone();
+//!     async move { queue.acquire(Priority::Background).await }
+//! });
+//! tokio::task::yield_now().await;
+//! let interactive = tokio::spawn({
+//!     let queue = queue.clone();
+//!     async move { queue.acquire(Priority::Interactive).await }
+//! });
+//! tokio::task::yield_now().await;
+//! assert_eq!(queue.queued(), 2);
+//!
+//! drop(running);
+//! let _permit = interactive.await.unwrap();
+//! assert_eq!(queue.queued(), 1);
+//! # drop(_permit);
+//! # background.await.unwrap();
+//! # })
+//! ```
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use tokio::sync::oneshot;
+
+/// How urgent a request is. Requests with a higher priority are sent first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum Priority {
+    /// Jobs nobody is waiting on, like batch summarization.
+    Background,
+    /// Requests a user is waiting on, like chat.
+    #[default]
+    Interactive,
+}
+
+impl Priority {
+    pub(crate) const ALL: [Priority; 2] = [Priority::Interactive, Priority::Background];
+}
+
+/// Limits the amount of concurrent requests, sending queued requests by [Priority].
+#[derive(Debug, Clone)]
+pub struct RequestQueue {
+    inner: Arc<Inner>,
+}
+
+#[derive(Debug)]
+struct Inner {
+    max_concurrent: usize,
+    state: Mutex<State>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    running: usize,
+    /// The waiting requests, indexed like [Priority::ALL].
+    waiting: [VecDeque<oneshot::Sender<Permit>>; 2],
+}
+
+impl State {
+    fn queued(&self) -> usize {
+        self.waiting.iter().flatten().filter(|tx| !tx.is_closed()).count()
+    }
+}
+
+impl RequestQueue {
+    /// Allow at most `max_concurrent` requests at the same time.
+    pub fn new(max_concurrent: usize) -> RequestQueue {
+        RequestQueue {
+            inner: Arc::new(Inner {
+                max_concurrent: max_concurrent.max(1),
+                state: Mutex::new(State::default()),
+            }),
+        }
+    }
+
+    pub fn max_concurrent(&self) -> usize {
+        self.inner.max_concurrent
+    }
+
+    /// The amount of requests currently being sent.
+    pub fn running(&self) -> usize {
+        self.inner.state.lock().unwrap().running
+    }
+
+    /// The amount of requests waiting for their turn.
+    pub fn queued(&self) -> usize {
+        self.inner.state.lock().unwrap().queued()
+    }
+
+    /// Wait for a turn to send a request, which lasts until the returned [Permit] is dropped.
+    ///
+    /// The [Client](crate::Client) does this automatically for every request.
+    pub async fn acquire(&self, priority: Priority) -> Permit {
+        let rx = {
+            let mut state = self.inner.state.lock().unwrap();
+            if state.running < self.inner.max_concurrent && state.queued() == 0 {
+                state.running += 1;
+                return Permit {
+                    queue: self.inner.clone(),
+                };
+            }
+            let (tx, rx) = oneshot::channel();
+            state.waiting[Priority::ALL.iter().position(|p| *p == priority).unwrap()].push_back(tx);
+            rx
+        };
+        // The sender is only dropped together with the queue, which this holds on to
+        rx.await.unwrap()
+    }
+}
+
+/// A turn to send a request, handed out by [RequestQueue::acquire].
+/// The next request in the queue is started when this is dropped.
+#[derive(Debug)]
+pub struct Permit {
+    queue: Arc<Inner>,
+}
+
+impl Drop for Permit {
+    fn drop(&mut self) {
+        let next = {
+            let mut state = self.queue.state.lock().unwrap();
+            match state.waiting.iter_mut().find_map(|waiting| waiting.pop_front()) {
+                Some(next) => next,
+                None => {
+                    state.running -= 1;
+                    return;
+                }
+            }
+        };
+        // Hand the turn over. If the waiting request was cancelled the permit comes back
+        // and is dropped here, passing the turn on to the request after it.
+        let _ = next.send(Permit {
+            queue: self.queue.clone(),
+        });
+    }
+}
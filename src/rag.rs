@@ -0,0 +1,224 @@
+//! Retrieval-augmented generation: find the parts of your documents relevant to a question, to add them to the prompt.
+//!
+//! A [Rag] splits documents into chunks with a [TextSplitter], embeds the chunks,
+//! and keeps them in an [Index](crate::embeddings::Index).
+//! [Rag::retrieve] embeds a query and returns the most similar chunks, which [context] turns into text for a prompt.
+//!
+//! ```no_run
+//! # tokio_test::block_on(async {
+//! # let api_key = "";
+//! use openai_rust::chat::{ChatArguments, Message};
+//! use openai_rust::rag::{context, Rag};
+//! let client = openai_rust::Client::new(api_key);
+//! let mut rag = Rag::new("text-embedding-3-small");
+//! let handbook = tokio::fs::read_to_string("handbook.md").await.unwrap();
+//! rag.add_document(&client, "handbook.md", &handbook).await.unwrap();
+//!
+//! let question = "How many vacation days do I get?";
+//! let chunks = rag.retrieve(&client, question, 3).await.unwrap();
+//! let args = ChatArguments::new("gpt-4o", vec![Message::user(format!("Answer using these sources:\n\n{}\n\nQuestion: {}", context(&chunks), question))]);
+//! println!("{}", client.create_chat(args).await.unwrap());
+//! # })
+//! ```
+use std::collections::HashMap;
+
+use anyhow::{anyhow, Result};
+use futures_util::StreamExt;
+
+use crate::embeddings::{EmbeddingsArguments, Index};
+
+/// How many chunks are embedded at the same time.
+const CONCURRENCY: usize = 8;
+
+/// Splits a document into chunks that are embedded separately.
+pub trait TextSplitter: Send + Sync {
+    fn split(&self, text: &str) -> Vec<String>;
+}
+
+impl<F: Fn(&str) -> Vec<String> + Send + Sync> TextSplitter for F {
+    fn split(&self, text: &str) -> Vec<String> {
+        self(text)
+    }
+}
+
+/// Splits text into chunks of a number of words, where each chunk repeats the last words of the previous one.
+///
+/// Whitespace between words is replaced by a single space.
+///
+/// ```
+/// use openai_rust::rag::{TextSplitter, WordSplitter};
+/// let chunks = WordSplitter::new(4, 1).split("one two three four five six seven");
+/// assert_eq!(chunks, ["one two three four", "four five six seven"]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WordSplitter {
+    words: usize,
+    overlap: usize,
+}
+
+impl WordSplitter {
+    /// Chunks of `words` words, overlapping by `overlap` words. The overlap is limited to half a chunk.
+    pub fn new(words: usize, overlap: usize) -> WordSplitter {
+        let words = words.max(1);
+        WordSplitter {
+            words,
+            overlap: overlap.min(words / 2),
+        }
+    }
+}
+
+impl Default for WordSplitter {
+    /// Chunks of 200 words, overlapping by 40 words.
+    fn default() -> Self {
+        WordSplitter::new(200, 40)
+    }
+}
+
+impl TextSplitter for WordSplitter {
+    fn split(&self, text: &str) -> Vec<String> {
+        let words: Vec<&str> = text.split_whitespace().collect();
+        let mut chunks = vec![];
+        let mut start = 0;
+        while start < words.len() {
+            let end = (start + self.words).min(words.len());
+            chunks.push(words[start..end].join(" "));
+            if end == words.len() {
+                break;
+            }
+            start = end - self.overlap;
+        }
+        chunks
+    }
+}
+
+/// A chunk of a document found by [Rag::retrieve].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Retrieved {
+    /// The ID of the document the chunk is from.
+    pub document: String,
+    pub text: String,
+    /// The cosine similarity with the query, between -1 and 1.
+    pub score: f32,
+}
+
+/// The chunks as numbered sources with the documents they are from, to add to a prompt.
+///
+/// ```
+/// use openai_rust::rag::{context, Retrieved};
+/// let chunks = [Retrieved { document: "handbook.md".to_owned(), text: "You get 25 vacation days.".to_owned(), score: 0.8 }];
+/// assert_eq!(context(&chunks), "[1] handbook.md\nYou get 25 vacation days.");
+/// ```
+pub fn context(chunks: &[Retrieved]) -> String {
+    let sources: Vec<String> = chunks
+        .iter()
+        .enumerate()
+        .map(|(i, chunk)| format!("[{}] {}\n{}", i + 1, chunk.document, chunk.text))
+        .collect();
+    sources.join("\n\n")
+}
+
+/// Documents split into chunks and embedded, to retrieve the chunks relevant to a query.
+pub struct Rag {
+    model: String,
+    splitter: Box<dyn TextSplitter>,
+    index: Index,
+    /// The document and text of each chunk in the index, by the ID of the chunk.
+    chunks: HashMap<String, (String, String)>,
+}
+
+impl Rag {
+    /// Embed documents with an embeddings model, split with the default [WordSplitter].
+    pub fn new(model: impl AsRef<str>) -> Rag {
+        Rag {
+            model: model.as_ref().to_owned(),
+            splitter: Box::new(WordSplitter::default()),
+            index: Index::new(),
+            chunks: HashMap::new(),
+        }
+    }
+
+    /// Split documents with another [TextSplitter].
+    pub fn splitter(mut self, splitter: impl TextSplitter + 'static) -> Rag {
+        self.splitter = Box::new(splitter);
+        self
+    }
+
+    /// Split and embed a document, replacing the document with the same ID.
+    ///
+    /// Returns the amount of chunks.
+    pub async fn add_document(&mut self, client: &crate::Client, id: &str, text: &str) -> Result<usize> {
+        let chunks = self.splitter.split(text);
+        let vectors: Vec<Vec<f32>> = futures_util::stream::iter(chunks.iter())
+            .map(|chunk| self.embed(client, chunk))
+            .buffered(CONCURRENCY)
+            .collect::<Vec<_>>()
+            .await
+            .into_iter()
+            .collect::<Result<_>>()?;
+
+        self.remove_document(id);
+        for (n, (chunk, vector)) in chunks.into_iter().zip(vectors).enumerate() {
+            let chunk_id = format!("{}#{}", id, n);
+            self.index.add(chunk_id.clone(), vector)?;
+            self.chunks.insert(chunk_id, (id.to_owned(), chunk));
+        }
+        Ok(self.chunks.values().filter(|(document, _)| document == id).count())
+    }
+
+    /// Remove the chunks of a document.
+    pub fn remove_document(&mut self, id: &str) {
+        let index = &mut self.index;
+        self.chunks.retain(|chunk_id, (document, _)| {
+            if document == id {
+                index.remove(chunk_id);
+            }
+            document != id
+        });
+    }
+
+    /// The `k` chunks most similar to the query, most similar first.
+    pub async fn retrieve(&self, client: &crate::Client, query: &str, k: usize) -> Result<Vec<Retrieved>> {
+        let query = self.embed(client, query).await?;
+        Ok(self
+            .index
+            .search(&query, k)?
+            .into_iter()
+            .map(|result| {
+                let (document, text) = self.chunks[&result.id].clone();
+                Retrieved {
+                    document,
+                    text,
+                    score: result.score,
+                }
+            })
+            .collect())
+    }
+
+    /// The amount of chunks of all documents.
+    pub fn len(&self) -> usize {
+        self.chunks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.chunks.is_empty()
+    }
+
+    async fn embed(&self, client: &crate::Client, text: &str) -> Result<Vec<f32>> {
+        let args = EmbeddingsArguments::new(&self.model, text.to_owned());
+        let res = client.create_embeddings(args).await?;
+        res.data
+            .into_iter()
+            .next()
+            .map(|data| data.embedding)
+            .ok_or_else(|| anyhow!("the embeddings response is empty"))
+    }
+}
+
+impl std::fmt::Debug for Rag {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Rag")
+            .field("model", &self.model)
+            .field("chunks", &self.chunks.len())
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,294 @@
+//! A client-side rate limiter for the [Client](crate::Client), to stay under the limits of your account.
+//! Attach with [Client::with_rate_limiter](crate::Client::with_rate_limiter).
+//!
+//! Every model has a bucket of requests and a bucket of tokens, which refill continuously up to
+//! the per-minute limits. Requests wait until both buckets have room, instead of being rejected by the API.
+//! The tokens of a request are counted from its input before it is sent,
+//! plus the maximum amount of output tokens if set, like the API does.
+//! Input is counted with the [Tokenizer] of the limiter if it has one, and [estimated](crate::tokens) otherwise.
+//!
+//! Waiting requests take their turn by [Priority], and in the order they arrived within a priority,
+//! so user-facing requests jump ahead of background jobs and large requests aren't starved by small ones.
+//!
+//! ```
+//! # tokio_test::block_on(async {
+//! use openai_rust::queue::Priority;
+//! use openai_rust::rate_limit::{Limits, RateLimiter};
+//! let limiter = RateLimiter::new()
+//!     .limit("gpt-4o", Limits::new(500, 30_000))
+//!     .limit("gpt-4o-mini", Limits::new(500, 200_000));
+//!
+//! limiter.acquire("gpt-4o-2024-08-06", 10_000, Priority::Interactive).await.unwrap();
+//! assert_eq!(limiter.wait_time("gpt-4o", 20_000), std::time::Duration::ZERO);
+//! assert!(limiter.wait_time("gpt-4o", 30_000) > std::time::Duration::ZERO);
+//! # })
+//! ```
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use tokio::sync::Notify;
+
+use crate::logit_bias::Tokenizer;
+use crate::queue::Priority;
+
+/// The limits of a model, per minute.
+///
+/// A limit of 0 blocks the model, requests to it fail with [Blocked].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Limits {
+    /// Requests per minute.
+    pub rpm: Option<u32>,
+    /// Tokens per minute.
+    pub tpm: Option<u32>,
+}
+
+impl Limits {
+    pub fn new(rpm: u32, tpm: u32) -> Limits {
+        Limits {
+            rpm: Some(rpm),
+            tpm: Some(tpm),
+        }
+    }
+
+    pub fn requests_per_minute(rpm: u32) -> Limits {
+        Limits { rpm: Some(rpm), tpm: None }
+    }
+
+    pub fn tokens_per_minute(tpm: u32) -> Limits {
+        Limits { rpm: None, tpm: Some(tpm) }
+    }
+}
+
+/// Delays requests to stay under the [Limits] of each model.
+#[derive(Default)]
+pub struct RateLimiter {
+    limits: HashMap<String, Limits>,
+    default_limits: Option<Limits>,
+    tokenizer: Option<Box<dyn Tokenizer + Send + Sync>>,
+    buckets: Mutex<HashMap<String, Buckets>>,
+    /// The id of the next waiting request.
+    next_ticket: AtomicU64,
+    /// Wakes the waiting requests whenever the first in line changes.
+    turn: Notify,
+}
+
+/// The room left for requests and tokens, as of `updated`.
+#[derive(Debug)]
+struct Buckets {
+    requests: f64,
+    tokens: f64,
+    updated: Instant,
+    /// The tickets of the waiting requests, indexed like [Priority::ALL].
+    waiting: [VecDeque<u64>; 2],
+}
+
+impl Buckets {
+    /// The ticket of the request whose turn it is.
+    fn first(&self) -> Option<u64> {
+        self.waiting.iter().find_map(|waiting| waiting.front().copied())
+    }
+}
+
+impl RateLimiter {
+    /// Create a rate limiter without any limits.
+    pub fn new() -> RateLimiter {
+        RateLimiter::default()
+    }
+
+    /// Set the limits of a model.
+    ///
+    /// Limits are matched on the longest prefix of the model name,
+    /// so a limit for `gpt-4o` also applies to `gpt-4o-2024-08-06`, and both share the same buckets.
+    pub fn limit(mut self, model: impl AsRef<str>, limits: Limits) -> RateLimiter {
+        self.limits.insert(model.as_ref().to_owned(), limits);
+        self
+    }
+
+    /// The limits used for models without limits of their own.
+    /// Every such model gets buckets of its own.
+    ///
+    /// Without default limits, requests to unknown models are not limited.
+    pub fn default_limits(mut self, limits: Limits) -> RateLimiter {
+        self.default_limits = Some(limits);
+        self
+    }
+
+    /// Count the tokens of requests with a tokenizer, like one from the
+    /// [tiktoken-rs](https://crates.io/crates/tiktoken-rs) crate, instead of estimating them.
+    ///
+    /// ```
+    /// use openai_rust::rate_limit::RateLimiter;
+    /// # let tokenizer = |text: &str| text.split_whitespace().map(|_| 0).collect::<Vec<u32>>();
+    /// // let bpe = tiktoken_rs::o200k_base().unwrap();
+    /// // let tokenizer = move |text: &str| bpe.encode_ordinary(text);
+    /// let limiter = RateLimiter::new().tokenizer(tokenizer);
+    /// assert_eq!(limiter.count_tokens("Hello GPT, how are you?"), 5);
+    /// ```
+    pub fn tokenizer(mut self, tokenizer: impl Tokenizer + Send + Sync + 'static) -> RateLimiter {
+        self.tokenizer = Some(Box::new(tokenizer));
+        self
+    }
+
+    /// The amount of tokens in a text, counted with the [tokenizer](RateLimiter::tokenizer)
+    /// or [estimated](crate::tokens::estimate_tokens) without one.
+    pub fn count_tokens(&self, text: &str) -> usize {
+        match &self.tokenizer {
+            Some(tokenizer) => tokenizer.encode(text).len(),
+            None => crate::tokens::estimate_tokens(text),
+        }
+    }
+
+    /// How long a request of `tokens` to `model` would have to wait.
+    pub fn wait_time(&self, model: &str, tokens: u32) -> Duration {
+        let Some((key, limits)) = self.limits_of(model) else {
+            return Duration::ZERO;
+        };
+        let mut buckets = self.buckets.lock().unwrap();
+        let buckets = Self::refill(&mut buckets, key, limits);
+        Self::wait(buckets, limits, tokens)
+    }
+
+    /// Wait until there is room for a request of `tokens` to `model`, and take it.
+    /// Requests of a higher [Priority] go first, and requests of the same priority go in order.
+    ///
+    /// Fails with [Blocked] if a limit of the model is 0.
+    /// The [Client](crate::Client) does this automatically for every request that has a model.
+    pub async fn acquire(&self, model: &str, tokens: u32, priority: Priority) -> Result<(), Blocked> {
+        let Some((key, limits)) = self.limits_of(model) else {
+            return Ok(());
+        };
+        if limits.rpm == Some(0) || limits.tpm == Some(0) {
+            return Err(Blocked { model: model.to_owned() });
+        }
+        let ticket = Ticket::new(self, key, limits, priority);
+        loop {
+            let turn = self.turn.notified();
+            let wait = {
+                let mut buckets = self.buckets.lock().unwrap();
+                let buckets = Self::refill(&mut buckets, key, limits);
+                if buckets.first() == Some(ticket.id) {
+                    let wait = Self::wait(buckets, limits, tokens);
+                    if wait.is_zero() {
+                        buckets.requests -= 1.0;
+                        if let Some(tpm) = limits.tpm {
+                            buckets.tokens -= tokens.min(tpm) as f64;
+                        }
+                    }
+                    Some(wait)
+                } else {
+                    None
+                }
+            };
+            match wait {
+                // Dropping the ticket passes the turn on
+                Some(wait) if wait.is_zero() => return Ok(()),
+                Some(wait) => tokio::time::sleep(wait).await,
+                None => turn.await,
+            }
+        }
+    }
+
+    /// The key of the buckets and the limits of a model.
+    fn limits_of<'a>(&'a self, model: &'a str) -> Option<(&'a str, Limits)> {
+        self.limits
+            .iter()
+            .filter(|(prefix, _)| model.starts_with(prefix.as_str()))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(prefix, limits)| (prefix.as_str(), *limits))
+            .or(self.default_limits.map(|limits| (model, limits)))
+    }
+
+    /// Get the buckets of a model, refilled for the time passed since they were last used.
+    fn refill<'b>(buckets: &'b mut HashMap<String, Buckets>, key: &str, limits: Limits) -> &'b mut Buckets {
+        let capacity = |limit: Option<u32>| limit.map_or(f64::INFINITY, |limit| limit as f64);
+        let buckets = buckets.entry(key.to_owned()).or_insert_with(|| Buckets {
+            requests: capacity(limits.rpm),
+            tokens: capacity(limits.tpm),
+            updated: Instant::now(),
+            waiting: Default::default(),
+        });
+        let minutes = buckets.updated.elapsed().as_secs_f64() / 60.0;
+        if let Some(rpm) = limits.rpm {
+            buckets.requests = (buckets.requests + minutes * rpm as f64).min(rpm as f64);
+        }
+        if let Some(tpm) = limits.tpm {
+            buckets.tokens = (buckets.tokens + minutes * tpm as f64).min(tpm as f64);
+        }
+        buckets.updated = Instant::now();
+        buckets
+    }
+
+    /// How long until the buckets have room for a request of `tokens`.
+    ///
+    /// Requests of more tokens than the limit wait for a full bucket, and requests to a blocked model wait forever.
+    fn wait(buckets: &Buckets, limits: Limits, tokens: u32) -> Duration {
+        let until = |available: f64, needed: f64, limit: Option<u32>| match limit {
+            Some(0) => Duration::MAX,
+            Some(limit) if available < needed => Duration::from_secs_f64((needed - available) * 60.0 / limit as f64),
+            _ => Duration::ZERO,
+        };
+        let tokens = limits.tpm.map_or(0, |tpm| tokens.min(tpm)) as f64;
+        until(buckets.requests, 1.0, limits.rpm).max(until(buckets.tokens, tokens, limits.tpm))
+    }
+}
+
+impl std::fmt::Debug for RateLimiter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RateLimiter")
+            .field("limits", &self.limits)
+            .field("default_limits", &self.default_limits)
+            .field("tokenizer", &self.tokenizer.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+/// A place in line for the buckets of a model, left when dropped.
+struct Ticket<'a> {
+    limiter: &'a RateLimiter,
+    key: &'a str,
+    limits: Limits,
+    id: u64,
+}
+
+impl<'a> Ticket<'a> {
+    fn new(limiter: &'a RateLimiter, key: &'a str, limits: Limits, priority: Priority) -> Ticket<'a> {
+        let id = limiter.next_ticket.fetch_add(1, Ordering::Relaxed);
+        let mut buckets = limiter.buckets.lock().unwrap();
+        let buckets = RateLimiter::refill(&mut buckets, key, limits);
+        buckets.waiting[Priority::ALL.iter().position(|p| *p == priority).unwrap()].push_back(id);
+        Ticket {
+            limiter,
+            key,
+            limits,
+            id,
+        }
+    }
+}
+
+impl Drop for Ticket<'_> {
+    fn drop(&mut self) {
+        let mut buckets = self.limiter.buckets.lock().unwrap();
+        let buckets = RateLimiter::refill(&mut buckets, self.key, self.limits);
+        for waiting in &mut buckets.waiting {
+            waiting.retain(|id| *id != self.id);
+        }
+        self.limiter.turn.notify_waiters();
+    }
+}
+
+/// The error returned for requests to a model with a limit of 0.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Blocked {
+    /// The model of the request.
+    pub model: String,
+}
+
+impl std::fmt::Display for Blocked {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "requests to {} are blocked by a rate limit of 0", self.model)
+    }
+}
+
+impl std::error::Error for Blocked {}
@@ -0,0 +1,394 @@
+//! Events of the [Realtime API](https://platform.openai.com/docs/guides/realtime).
+//!
+//! The Realtime API is used over a WebSocket (or WebRTC) connection, which this crate does not provide.
+//! Instead it models the events sent over the connection, so they can be used with any WebSocket library:
+//! [ClientEvent]s are serialized to JSON text messages, and text messages received are deserialized into [ServerEvent]s.
+//!
+//! Audio is sent and received as base64 encoded 16-bit PCM at 24kHz, mono and little-endian.
+//!
+//! ```
+//! use openai_rust::realtime::*;
+//! // configure the session once connected
+//! let update = ClientEvent::SessionUpdate {
+//!     session: Session {
+//!         turn_detection: Some(TurnDetection::server_vad()),
+//!         ..Default::default()
+//!     },
+//! };
+//! let text = serde_json::to_string(&update).unwrap();
+//!
+//! // send a second of silence from the microphone
+//! let samples = vec![0i16; SAMPLE_RATE as usize];
+//! for event in append_audio(&samples) {
+//!     let text = serde_json::to_string(&event).unwrap();
+//! }
+//!
+//! // collect the audio of the answer
+//! let mut audio = AudioBuffer::new();
+//! # let text = r#"{"type": "response.audio.delta", "event_id": "event_4950", "response_id": "resp_001", "item_id": "msg_008", "output_index": 0, "content_index": 0, "delta": "AAABAA=="}"#;
+//! let event: ServerEvent = serde_json::from_str(text).unwrap();
+//! audio.push(&event).unwrap();
+//! let wav = audio.to_wav();
+//! # assert_eq!(audio.samples(), vec![0, 1]);
+//! ```
+use anyhow::Result;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+
+/// The sample rate of `pcm16` audio.
+pub const SAMPLE_RATE: u32 = 24_000;
+
+/// The maximum amount of PCM bytes sent in a single [ClientEvent::InputAudioBufferAppend].
+///
+/// Events are limited to 15 MiB, and base64 makes audio a third larger.
+/// Chunks are kept far below that, so audio is streamed as it is recorded.
+pub const MAX_AUDIO_CHUNK_BYTES: usize = 2 * SAMPLE_RATE as usize; // one second
+
+/// An event sent by the client.
+///
+/// See <https://platform.openai.com/docs/api-reference/realtime-client-events>.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type")]
+pub enum ClientEvent {
+    /// Update the configuration of the session.
+    #[serde(rename = "session.update")]
+    SessionUpdate { session: Session },
+    /// Append base64 encoded audio to the input audio buffer. See [append_audio].
+    #[serde(rename = "input_audio_buffer.append")]
+    InputAudioBufferAppend { audio: String },
+    /// Commit the input audio buffer as a user message. Not needed when using [TurnDetection].
+    #[serde(rename = "input_audio_buffer.commit")]
+    InputAudioBufferCommit,
+    /// Clear the input audio buffer.
+    #[serde(rename = "input_audio_buffer.clear")]
+    InputAudioBufferClear,
+    /// Ask the model to respond. Not needed when using [TurnDetection] with `create_response`.
+    #[serde(rename = "response.create")]
+    ResponseCreate,
+    /// Cancel the response in progress, for example when the user interrupts the model.
+    #[serde(rename = "response.cancel")]
+    ResponseCancel,
+    /// Add an item to the conversation, such as the output of a function. See [function_output].
+    #[serde(rename = "conversation.item.create")]
+    ConversationItemCreate { item: Item },
+}
+
+/// Submit the output of a function call to the session, and ask the model to respond to it.
+///
+/// ```
+/// use openai_rust::realtime::*;
+/// # let text = r#"{"type": "response.function_call_arguments.done", "event_id": "event_5556", "response_id": "resp_002", "item_id": "fc_001", "output_index": 0, "call_id": "call_001", "name": "get_weather", "arguments": "{\"location\": \"San Francisco\"}"}"#;
+/// let event: ServerEvent = serde_json::from_str(text).unwrap();
+/// if let ServerEvent::FunctionCallArgumentsDone { call_id, name, arguments, .. } = event {
+///     # assert_eq!(name, "get_weather");
+///     let output = r#"{"temperature": 18}"#; // call the function
+///     for event in function_output(&call_id, output) {
+///         let text = serde_json::to_string(&event).unwrap();
+///     }
+/// }
+/// ```
+pub fn function_output(call_id: &str, output: &str) -> [ClientEvent; 2] {
+    [
+        ClientEvent::ConversationItemCreate {
+            item: Item::FunctionCallOutput {
+                id: None,
+                call_id: call_id.to_owned(),
+                output: output.to_owned(),
+            },
+        },
+        ClientEvent::ResponseCreate,
+    ]
+}
+
+/// An item of a realtime conversation.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Item {
+    /// A call of a function by the model.
+    FunctionCall {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        id: Option<String>,
+        call_id: String,
+        name: String,
+        /// The arguments as a JSON string.
+        arguments: String,
+    },
+    /// The output of a function, sent by the client.
+    FunctionCallOutput {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        id: Option<String>,
+        call_id: String,
+        output: String,
+    },
+    /// An item not yet supported by this crate, such as a message.
+    #[serde(other)]
+    Other,
+}
+
+/// A function the model can call, set in [Session::tools].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Function {
+    /// Always `function`.
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub name: String,
+    pub description: String,
+    /// The parameters of the function, as a JSON schema.
+    pub parameters: serde_json::Value,
+}
+
+impl Function {
+    pub fn new(name: impl AsRef<str>, description: impl AsRef<str>, parameters: serde_json::Value) -> Function {
+        Function {
+            kind: "function".to_owned(),
+            name: name.as_ref().to_owned(),
+            description: description.as_ref().to_owned(),
+            parameters,
+        }
+    }
+}
+
+/// The configuration of a realtime session. Unset fields are left unchanged.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct Session {
+    /// The modalities the model responds with, `["text"]` or `["text", "audio"]`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub modalities: Option<Vec<String>>,
+
+    /// The system instructions for the model.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instructions: Option<String>,
+
+    /// The voice the model responds with, like `alloy` or `verse`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub voice: Option<String>,
+
+    /// The format of input audio, `pcm16`, `g711_ulaw`, or `g711_alaw`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input_audio_format: Option<String>,
+
+    /// The format of output audio, `pcm16`, `g711_ulaw`, or `g711_alaw`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_audio_format: Option<String>,
+
+    /// How the end of a turn of the user is detected.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub turn_detection: Option<TurnDetection>,
+
+    /// Functions the model can call.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tools: Option<Vec<Function>>,
+
+    /// How the model chooses tools, `auto`, `none`, or `required`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_choice: Option<String>,
+}
+
+/// Voice activity detection, which commits the input audio buffer when the user stops speaking.
+///
+/// See <https://platform.openai.com/docs/guides/realtime-vad>.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TurnDetection {
+    /// Detects the end of a turn based on the volume of the audio.
+    ServerVad {
+        /// Activation threshold between 0 and 1. A higher threshold requires louder audio. Defaults to 0.5.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        threshold: Option<f32>,
+        /// Audio included before the speech started, in milliseconds. Defaults to 300.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        prefix_padding_ms: Option<u32>,
+        /// Duration of silence that ends the turn, in milliseconds. Defaults to 500.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        silence_duration_ms: Option<u32>,
+        /// Whether to create a response when the turn ends. Defaults to `true`.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        create_response: Option<bool>,
+    },
+    /// Detects the end of a turn based on the words spoken.
+    SemanticVad {
+        /// How quickly the model responds, `low`, `medium`, `high`, or `auto`.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        eagerness: Option<String>,
+        /// Whether to create a response when the turn ends. Defaults to `true`.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        create_response: Option<bool>,
+    },
+}
+
+impl TurnDetection {
+    /// Server VAD with the default settings.
+    pub fn server_vad() -> TurnDetection {
+        TurnDetection::ServerVad {
+            threshold: None,
+            prefix_padding_ms: None,
+            silence_duration_ms: None,
+            create_response: None,
+        }
+    }
+}
+
+/// Turn audio samples into [ClientEvent::InputAudioBufferAppend] events of at most [MAX_AUDIO_CHUNK_BYTES].
+pub fn append_audio(samples: &[i16]) -> Vec<ClientEvent> {
+    let bytes: Vec<u8> = samples.iter().flat_map(|s| s.to_le_bytes()).collect();
+    append_audio_bytes(&bytes)
+}
+
+/// Turn little-endian PCM16 bytes into [ClientEvent::InputAudioBufferAppend] events of at most [MAX_AUDIO_CHUNK_BYTES].
+///
+/// A trailing odd byte, which is not a whole sample, is dropped.
+pub fn append_audio_bytes(pcm: &[u8]) -> Vec<ClientEvent> {
+    let pcm = &pcm[..pcm.len() - pcm.len() % 2];
+    pcm.chunks(MAX_AUDIO_CHUNK_BYTES)
+        .map(|chunk| ClientEvent::InputAudioBufferAppend {
+            audio: BASE64.encode(chunk),
+        })
+        .collect()
+}
+
+/// An event sent by the server.
+///
+/// See <https://platform.openai.com/docs/api-reference/realtime-server-events>.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type")]
+pub enum ServerEvent {
+    /// A chunk of the audio of a response, base64 encoded.
+    #[serde(rename = "response.audio.delta", alias = "response.output_audio.delta")]
+    ResponseAudioDelta {
+        response_id: String,
+        item_id: String,
+        delta: String,
+    },
+    /// All audio of an item of a response has been sent.
+    #[serde(rename = "response.audio.done", alias = "response.output_audio.done")]
+    ResponseAudioDone { response_id: String, item_id: String },
+    /// The user started speaking, detected by [TurnDetection].
+    #[serde(rename = "input_audio_buffer.speech_started")]
+    SpeechStarted { item_id: String },
+    /// The user stopped speaking, detected by [TurnDetection].
+    #[serde(rename = "input_audio_buffer.speech_stopped")]
+    SpeechStopped { item_id: String },
+    /// A chunk of the arguments of a function call.
+    #[serde(rename = "response.function_call_arguments.delta")]
+    FunctionCallArgumentsDelta {
+        response_id: String,
+        item_id: String,
+        call_id: String,
+        delta: String,
+    },
+    /// The model finished a function call. Respond with [function_output].
+    #[serde(rename = "response.function_call_arguments.done")]
+    FunctionCallArgumentsDone {
+        response_id: String,
+        item_id: String,
+        call_id: String,
+        #[serde(default)]
+        name: String,
+        /// The arguments as a JSON string.
+        arguments: String,
+    },
+    /// An item was added to the output of a response, such as an [Item::FunctionCall].
+    #[serde(rename = "response.output_item.added")]
+    OutputItemAdded { response_id: String, item: Item },
+    /// An item of the output of a response is complete.
+    #[serde(rename = "response.output_item.done")]
+    OutputItemDone { response_id: String, item: Item },
+    /// A response is done.
+    #[serde(rename = "response.done")]
+    ResponseDone,
+    #[serde(rename = "error")]
+    Error { error: RealtimeError },
+    /// An event not yet supported by this crate.
+    #[serde(other)]
+    Other,
+}
+
+/// An error sent by the server.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct RealtimeError {
+    #[serde(default)]
+    pub code: Option<String>,
+    pub message: String,
+}
+
+/// Reassembles the audio deltas of a response into playable audio.
+#[derive(Debug, Clone, Default)]
+pub struct AudioBuffer {
+    pcm: Vec<u8>,
+}
+
+impl AudioBuffer {
+    pub fn new() -> AudioBuffer {
+        AudioBuffer::default()
+    }
+
+    /// Add the audio of a [ServerEvent::ResponseAudioDelta], other events are ignored.
+    ///
+    /// Returns whether the event contained audio.
+    pub fn push(&mut self, event: &ServerEvent) -> Result<bool> {
+        match event {
+            ServerEvent::ResponseAudioDelta { delta, .. } => {
+                self.push_delta(delta)?;
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    /// Add base64 encoded audio.
+    pub fn push_delta(&mut self, delta: &str) -> Result<()> {
+        BASE64.decode_vec(delta, &mut self.pcm)?;
+        Ok(())
+    }
+
+    /// The audio as little-endian PCM16 bytes.
+    pub fn pcm(&self) -> &[u8] {
+        &self.pcm
+    }
+
+    /// The audio as samples.
+    pub fn samples(&self) -> Vec<i16> {
+        self.pcm
+            .chunks_exact(2)
+            .map(|b| i16::from_le_bytes([b[0], b[1]]))
+            .collect()
+    }
+
+    /// The length of the audio.
+    pub fn duration(&self) -> std::time::Duration {
+        std::time::Duration::from_secs_f64(self.pcm.len() as f64 / 2.0 / SAMPLE_RATE as f64)
+    }
+
+    /// Take the audio received so far, for example to play it while more is being received.
+    pub fn take(&mut self) -> Vec<u8> {
+        let whole = self.pcm.len() - self.pcm.len() % 2;
+        let rest = self.pcm.split_off(whole);
+        std::mem::replace(&mut self.pcm, rest)
+    }
+
+    pub fn clear(&mut self) {
+        self.pcm.clear();
+    }
+
+    /// The audio as a WAV file.
+    pub fn to_wav(&self) -> Vec<u8> {
+        let len = self.pcm.len() as u32;
+        let mut wav = Vec::with_capacity(44 + self.pcm.len());
+        wav.extend_from_slice(b"RIFF");
+        wav.extend_from_slice(&(36 + len).to_le_bytes());
+        wav.extend_from_slice(b"WAVEfmt ");
+        wav.extend_from_slice(&16u32.to_le_bytes()); // size of the fmt chunk
+        wav.extend_from_slice(&1u16.to_le_bytes()); // PCM
+        wav.extend_from_slice(&1u16.to_le_bytes()); // mono
+        wav.extend_from_slice(&SAMPLE_RATE.to_le_bytes());
+        wav.extend_from_slice(&(SAMPLE_RATE * 2).to_le_bytes()); // bytes per second
+        wav.extend_from_slice(&2u16.to_le_bytes()); // bytes per sample
+        wav.extend_from_slice(&16u16.to_le_bytes()); // bits per sample
+        wav.extend_from_slice(b"data");
+        wav.extend_from_slice(&len.to_le_bytes());
+        wav.extend_from_slice(&self.pcm);
+        wav
+    }
+}
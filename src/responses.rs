@@ -0,0 +1,590 @@
+//! See <https://platform.openai.com/docs/api-reference/responses>.
+//! Use with [Client::create_response](crate::Client::create_response).
+
+use serde::{Deserialize, Serialize};
+
+/// Request arguments for creating a response.
+///
+/// See <https://platform.openai.com/docs/api-reference/responses/create>.
+///
+/// ```
+/// use openai_rust::responses::{ResponseArguments, Reasoning, ReasoningEffort, ReasoningSummary};
+/// let mut args = ResponseArguments::new("o4-mini", "How much wood would a woodchuck chuck?");
+/// args.reasoning = Some(Reasoning {
+///     effort: Some(ReasoningEffort::Low),
+///     summary: Some(ReasoningSummary::Auto),
+/// });
+/// ```
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct ResponseArguments {
+    /// ID of the model to use, like `gpt-4o` or `o3`.
+    pub model: String,
+
+    /// Text or messages used to generate the response.
+    pub input: Input,
+
+    /// A system (or developer) message inserted into the model's context.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instructions: Option<String>,
+
+    /// Configuration options for [reasoning models](https://platform.openai.com/docs/guides/reasoning).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reasoning: Option<Reasoning>,
+
+    /// An upper bound for the number of tokens that can be generated for a response, including visible output tokens and reasoning tokens.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_output_tokens: Option<u32>,
+
+    /// What sampling temperature to use, between 0 and 2. Higher values like 0.8 will make the output more random, while lower values like 0.2 will make it more focused and deterministic.
+    ///
+    /// We generally recommend altering this or `top_p` but not both.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f32>,
+
+    /// An alternative to sampling with temperature, called nucleus sampling, where the model considers the results of the tokens with top_p probability mass. So 0.1 means only the tokens comprising the top 10% probability mass are considered.
+    ///
+    /// We generally recommend altering this or `temperature` but not both.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f32>,
+
+    /// Tools the model may call, like searching files.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tools: Option<Vec<Tool>>,
+
+    /// Additional output data to include in the response, like the results of the file search tool.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub include: Option<Vec<Include>>,
+
+    /// The ID of the previous response, used to create multi-turn conversations.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub previous_response_id: Option<String>,
+
+    /// Whether to store the generated response for later retrieval. Defaults to `true`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub store: Option<bool>,
+
+    /// Whether to run the model response in the background.
+    /// Poll it with [Client::get_response](crate::Client::get_response) and stop it with [Client::cancel_response](crate::Client::cancel_response).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub background: Option<bool>,
+
+    /// A unique identifier representing your end-user, which can help OpenAI to monitor and detect abuse.
+    /// [Learn more](https://platform.openai.com/docs/guides/safety-best-practices/end-user-ids).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+
+    /// Additional parameters sent in the body, for parameters not yet supported by this crate or specific to a provider.
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    pub extra: Option<serde_json::Map<String, serde_json::Value>>,
+}
+
+impl ResponseArguments {
+    pub fn new(model: impl AsRef<str>, input: impl Into<Input>) -> ResponseArguments {
+        ResponseArguments {
+            model: model.as_ref().to_owned(),
+            input: input.into(),
+            instructions: None,
+            reasoning: None,
+            max_output_tokens: None,
+            temperature: None,
+            top_p: None,
+            tools: None,
+            include: None,
+            previous_response_id: None,
+            store: None,
+            background: None,
+            user: None,
+            extra: None,
+        }
+    }
+}
+
+/// The input of a response.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum Input {
+    /// A text input, equivalent to a single user message.
+    Text(String),
+    /// A list of messages.
+    Messages(Vec<crate::chat::Message>),
+}
+
+impl Input {
+    /// Count the amount of tokens of the input, counting the tokens of each text with `count`.
+    pub(crate) fn count_tokens(&self, count: impl Fn(&str) -> usize) -> usize {
+        match self {
+            Input::Text(text) => count(text),
+            Input::Messages(messages) => crate::tokens::count_message_tokens(messages, count),
+        }
+    }
+}
+
+impl From<&str> for Input {
+    fn from(text: &str) -> Self {
+        Input::Text(text.to_owned())
+    }
+}
+
+impl From<String> for Input {
+    fn from(text: String) -> Self {
+        Input::Text(text)
+    }
+}
+
+impl From<Vec<crate::chat::Message>> for Input {
+    fn from(messages: Vec<crate::chat::Message>) -> Self {
+        Input::Messages(messages)
+    }
+}
+
+/// Configuration options for reasoning models.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct Reasoning {
+    /// Constrains the effort on reasoning. Reducing it can result in faster responses and fewer reasoning tokens.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub effort: Option<ReasoningEffort>,
+
+    /// Request a summary of the reasoning performed by the model,
+    /// which is returned as a [OutputItem::Reasoning].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<ReasoningSummary>,
+}
+
+/// The effort a model spends on reasoning.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ReasoningEffort {
+    Minimal,
+    Low,
+    Medium,
+    High,
+}
+
+/// The level of detail of a reasoning summary.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ReasoningSummary {
+    Auto,
+    Concise,
+    Detailed,
+}
+
+/// A tool the model may call while generating a response.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Tool {
+    /// Search the files in vector stores.
+    FileSearch {
+        vector_store_ids: Vec<String>,
+        /// The maximum number of results to return, between 1 and 50.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        max_num_results: Option<u32>,
+    },
+}
+
+impl Tool {
+    /// Search the files in the vector stores with these IDs.
+    pub fn file_search(vector_store_ids: &[&str]) -> Tool {
+        Tool::FileSearch {
+            vector_store_ids: vector_store_ids.iter().map(|id| (*id).to_owned()).collect(),
+            max_num_results: None,
+        }
+    }
+}
+
+/// Additional output data to include in a [Response].
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Include {
+    /// The [results](OutputItem::FileSearchCall::results) of the file search tool.
+    #[serde(rename = "file_search_call.results")]
+    FileSearchCallResults,
+    /// The log probabilities of the output text.
+    #[serde(rename = "message.output_text.logprobs")]
+    MessageOutputTextLogprobs,
+    /// An encrypted version of the reasoning, to use the reasoning in later requests when [store](ResponseArguments::store) is `false`.
+    #[serde(rename = "reasoning.encrypted_content")]
+    ReasoningEncryptedContent,
+}
+
+/// A response generated by the model.
+///
+/// It implements [Display](std::fmt::Display) as a shortcut to easily extract the output text.
+/// ```
+/// # use serde_json;
+/// # let json = r#"{
+/// #   "id": "resp_67ccd3a9da748190baa7f1570fe91ac604becb25c45c1d41",
+/// #   "object": "response",
+/// #   "created_at": 1741476777,
+/// #   "status": "completed",
+/// #   "model": "o4-mini-2025-04-16",
+/// #   "output": [
+/// #     {
+/// #       "type": "reasoning",
+/// #       "id": "rs_6876cf02e0bc8192b74af0fb64b715ff06fa2fcced15a5ac",
+/// #       "summary": [
+/// #         {
+/// #           "type": "summary_text",
+/// #           "text": "**Estimating woodchuck capacity**\n\nThe question is a classic tongue twister."
+/// #         }
+/// #       ]
+/// #     },
+/// #     {
+/// #       "type": "message",
+/// #       "id": "msg_67ccd3acc8d48190a77525dc6de64b4104becb25c45c1d41",
+/// #       "status": "completed",
+/// #       "role": "assistant",
+/// #       "content": [
+/// #         {
+/// #           "type": "output_text",
+/// #           "text": "About 700 pounds, according to one wildlife expert.",
+/// #           "annotations": []
+/// #         }
+/// #       ]
+/// #     }
+/// #   ],
+/// #   "usage": {
+/// #     "input_tokens": 81,
+/// #     "output_tokens": 1035,
+/// #     "output_tokens_details": {
+/// #       "reasoning_tokens": 832
+/// #     },
+/// #     "total_tokens": 1116
+/// #   }
+/// # }"#;
+/// # let res = serde_json::from_str::<openai_rust::responses::Response>(json).unwrap();
+/// let text = res.output_text();
+/// // or
+/// let text = res.to_string();
+/// // and to see what the model was thinking
+/// let summary = res.reasoning_summary();
+/// # assert_eq!(summary, "**Estimating woodchuck capacity**\n\nThe question is a classic tongue twister.");
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct Response {
+    pub id: String,
+    pub created_at: u64,
+    /// One of `completed`, `failed`, `in_progress`, `cancelled`, `queued`, or `incomplete`.
+    pub status: String,
+    pub model: String,
+    pub output: Vec<OutputItem>,
+    pub usage: Option<Usage>,
+}
+
+impl Response {
+    /// The text of all output messages.
+    pub fn output_text(&self) -> String {
+        self.output
+            .iter()
+            .filter_map(|item| match item {
+                OutputItem::Message { content, .. } => Some(content),
+                _ => None,
+            })
+            .flatten()
+            .filter_map(|content| match content {
+                OutputContent::OutputText { text, .. } => Some(text.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// The sources cited in the output messages, like web pages and files.
+    pub fn annotations(&self) -> Vec<&Annotation> {
+        self.output
+            .iter()
+            .filter_map(|item| match item {
+                OutputItem::Message { content, .. } => Some(content),
+                _ => None,
+            })
+            .flatten()
+            .filter_map(|content| match content {
+                OutputContent::OutputText { annotations, .. } => Some(annotations),
+                _ => None,
+            })
+            .flatten()
+            .collect()
+    }
+
+    /// The file chunks found by the file search tool.
+    ///
+    /// This is empty unless the results were requested with [Include::FileSearchCallResults].
+    ///
+    /// ```
+    /// # let json = r#"{
+    /// #   "id": "resp_123",
+    /// #   "created_at": 1741476777,
+    /// #   "status": "completed",
+    /// #   "model": "gpt-4o",
+    /// #   "output": [
+    /// #     {
+    /// #       "type": "file_search_call",
+    /// #       "id": "fs_123",
+    /// #       "status": "completed",
+    /// #       "queries": ["deep research"],
+    /// #       "results": [
+    /// #         {"file_id": "file-123", "filename": "research.pdf", "score": 0.92, "text": "Deep research is...", "attributes": {}}
+    /// #       ]
+    /// #     }
+    /// #   ],
+    /// #   "usage": null
+    /// # }"#;
+    /// use openai_rust::responses::{Include, ResponseArguments, Tool};
+    /// let mut args = ResponseArguments::new("gpt-4o", "What is deep research?");
+    /// args.tools = Some(vec![Tool::file_search(&["vs_123"])]);
+    /// args.include = Some(vec![Include::FileSearchCallResults]);
+    /// # let res = serde_json::from_str::<openai_rust::responses::Response>(json).unwrap();
+    /// for result in res.file_search_results() {
+    ///     println!("{} ({}): {}", result.filename, result.score, result.text);
+    /// }
+    /// # assert_eq!(res.file_search_results().len(), 1);
+    /// ```
+    pub fn file_search_results(&self) -> Vec<&FileSearchResult> {
+        self.output
+            .iter()
+            .filter_map(|item| match item {
+                OutputItem::FileSearchCall { results, .. } => results.as_ref(),
+                _ => None,
+            })
+            .flatten()
+            .collect()
+    }
+
+    /// The reasoning summaries, separated by blank lines.
+    ///
+    /// This is empty unless a summary was requested using [Reasoning::summary].
+    pub fn reasoning_summary(&self) -> String {
+        self.output
+            .iter()
+            .filter_map(|item| match item {
+                OutputItem::Reasoning { summary, .. } => Some(summary),
+                _ => None,
+            })
+            .flatten()
+            .map(|part| part.text.as_str())
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+}
+
+impl std::fmt::Display for Response {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.output_text())
+    }
+}
+
+/// The result of [Client::delete_response](crate::Client::delete_response).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct DeletedResponse {
+    pub id: String,
+    pub deleted: bool,
+}
+
+/// An item in the output of a [Response].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum OutputItem {
+    /// A message from the model.
+    Message {
+        id: String,
+        role: String,
+        content: Vec<OutputContent>,
+    },
+    /// A call of the file search tool.
+    FileSearchCall {
+        id: String,
+        /// One of `in_progress`, `searching`, `incomplete`, `failed` or `completed`.
+        status: String,
+        /// The queries used to search the files.
+        #[serde(default)]
+        queries: Vec<String>,
+        /// The chunks of files that were found, only present when requested with [Include::FileSearchCallResults].
+        #[serde(default)]
+        results: Option<Vec<FileSearchResult>>,
+    },
+    /// The reasoning of a reasoning model.
+    Reasoning {
+        id: String,
+        /// Summaries of the reasoning, only present when requested.
+        #[serde(default)]
+        summary: Vec<ReasoningSummaryPart>,
+    },
+    /// An output item not yet supported by this crate, as its JSON.
+    #[serde(untagged)]
+    Other(serde_json::Value),
+}
+
+/// The content of an [OutputItem::Message].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum OutputContent {
+    OutputText {
+        text: String,
+        /// The sources cited in the text.
+        #[serde(default)]
+        annotations: Vec<Annotation>,
+    },
+    Refusal { refusal: String },
+    #[serde(untagged)]
+    Other(serde_json::Value),
+}
+
+/// A source cited in an [OutputContent::OutputText].
+///
+/// ```
+/// # let json = r#"{
+/// #   "type": "output_text",
+/// #   "text": "The deep research report covers cats.",
+/// #   "annotations": [
+/// #     {"type": "url_citation", "start_index": 0, "end_index": 37, "title": "Cats", "url": "https://example.com/cats"},
+/// #     {"type": "file_citation", "index": 37, "file_id": "file-2dtbBZdjtDKS8eqWxqbgDi", "filename": "cats.pdf"}
+/// #   ]
+/// # }"#;
+/// # let content = serde_json::from_str::<openai_rust::responses::OutputContent>(json).unwrap();
+/// use openai_rust::responses::{Annotation, OutputContent};
+/// if let OutputContent::OutputText { annotations, .. } = content {
+///     for annotation in annotations {
+///         match annotation {
+///             Annotation::UrlCitation { url, title, .. } => println!("[{}]({})", title, url),
+///             Annotation::FileCitation { filename, .. } => println!("{}", filename),
+///             Annotation::Other(_) => {}
+///         }
+///     }
+/// }
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Annotation {
+    /// A web page found by the web search tool.
+    UrlCitation {
+        url: String,
+        title: String,
+        /// The index of the first character of the citation in the text.
+        start_index: usize,
+        /// The index after the last character of the citation in the text.
+        end_index: usize,
+    },
+    /// A file found by the file search tool.
+    FileCitation {
+        file_id: String,
+        #[serde(default)]
+        filename: String,
+        /// The index of the character in the text the citation belongs to.
+        index: usize,
+    },
+    /// An annotation not yet supported by this crate, as its JSON.
+    #[serde(untagged)]
+    Other(serde_json::Value),
+}
+
+/// A chunk of a file found by the file search tool.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct FileSearchResult {
+    pub file_id: String,
+    pub filename: String,
+    /// The relevance of the chunk, between 0 and 1.
+    pub score: f32,
+    /// The text of the chunk.
+    pub text: String,
+    /// The attributes of the file in the vector store.
+    #[serde(default)]
+    pub attributes: serde_json::Map<String, serde_json::Value>,
+}
+
+/// A part of the summary of an [OutputItem::Reasoning].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct ReasoningSummaryPart {
+    pub text: String,
+}
+
+/// An item of the input of a [Response].
+///
+/// Returned by [Client::list_response_input_items](crate::Client::list_response_input_items).
+/// ```
+/// # use serde_json;
+/// # let json = r#"{
+/// #   "id": "msg_abc123",
+/// #   "type": "message",
+/// #   "role": "user",
+/// #   "content": [
+/// #     {
+/// #       "type": "input_text",
+/// #       "text": "Tell me a three sentence bedtime story about a unicorn."
+/// #     }
+/// #   ]
+/// # }"#;
+/// # let item = serde_json::from_str::<openai_rust::responses::InputItem>(json).unwrap();
+/// use openai_rust::responses::{InputItem, InputContent};
+/// if let InputItem::Message { role, content, .. } = item {
+///     for part in content {
+///         if let InputContent::InputText { text } = part {
+///             println!("{}: {}", role, text);
+///         }
+///     }
+/// }
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum InputItem {
+    /// A message from the user, system, developer or a previous assistant turn.
+    Message {
+        id: String,
+        role: String,
+        content: Vec<InputContent>,
+    },
+    /// An input item not yet supported by this crate, as its JSON.
+    #[serde(untagged)]
+    Other(serde_json::Value),
+}
+
+/// The content of an [InputItem::Message].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum InputContent {
+    InputText {
+        text: String,
+    },
+    InputImage {
+        #[serde(default)]
+        image_url: Option<String>,
+        #[serde(default)]
+        file_id: Option<String>,
+        #[serde(default)]
+        detail: Option<String>,
+    },
+    InputFile {
+        #[serde(default)]
+        file_id: Option<String>,
+        #[serde(default)]
+        filename: Option<String>,
+    },
+    /// The text of an assistant message from an earlier turn.
+    OutputText {
+        text: String,
+    },
+    #[serde(untagged)]
+    Other(serde_json::Value),
+}
+
+/// Information about the tokens used by a [Response].
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[non_exhaustive]
+#[serde(default)]
+pub struct Usage {
+    pub input_tokens: u32,
+    pub output_tokens: u32,
+    pub total_tokens: u32,
+    pub output_tokens_details: OutputTokensDetails,
+}
+
+/// A breakdown of the output tokens of a [Response].
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[non_exhaustive]
+#[serde(default)]
+pub struct OutputTokensDetails {
+    /// The tokens spent on reasoning, which are not part of the output but are billed.
+    pub reasoning_tokens: u32,
+}
@@ -0,0 +1,103 @@
+//! Retrying requests that hit a rate limit, a server error or a connection error.
+//! Enable with [Client::with_retries](crate::Client::with_retries).
+//!
+//! A failed request is sent again after an exponential backoff with jitter, or after the time
+//! the API asked to wait with the `Retry-After` header. Streamed requests are retried until the stream starts,
+//! a stream that breaks off halfway is not retried. Requests with a body that can't be cloned, like
+//! [uploads](crate::files::Upload) streamed from a file, are sent once.
+//!
+//! ```
+//! use std::time::Duration;
+//! use openai_rust::retry::RetryPolicy;
+//! let client = openai_rust::Client::new("").with_retries(
+//!     RetryPolicy::new()
+//!         .max_attempts(5)
+//!         .base_delay(Duration::from_secs(1))
+//!         .max_delay(Duration::from_secs(60)),
+//! );
+//! ```
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+
+/// How often and how long to wait before retrying a request.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    base_delay: Duration,
+    max_delay: Duration,
+    jitter: f64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RetryPolicy {
+    /// Send a request up to 3 times, waiting 500 milliseconds and then 1 second, with a jitter of 50%.
+    pub fn new() -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            jitter: 0.5,
+        }
+    }
+
+    /// How often a request is sent, including the first attempt. Defaults to 3.
+    pub fn max_attempts(mut self, max_attempts: u32) -> RetryPolicy {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// The time to wait before the first retry, which doubles after every retry. Defaults to 500 milliseconds.
+    pub fn base_delay(mut self, base_delay: Duration) -> RetryPolicy {
+        self.base_delay = base_delay;
+        self
+    }
+
+    /// The maximum time to wait between attempts, also for the `Retry-After` header. Defaults to 30 seconds.
+    pub fn max_delay(mut self, max_delay: Duration) -> RetryPolicy {
+        self.max_delay = max_delay;
+        self
+    }
+
+    /// The part of the backoff that is random, between 0 and 1, so clients that failed together
+    /// don't retry together. Defaults to 0.5. The `Retry-After` header is used as is.
+    pub fn jitter(mut self, jitter: f64) -> RetryPolicy {
+        self.jitter = jitter.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Whether another attempt may follow the given attempt, counting from 1.
+    pub(crate) fn retries_after(&self, attempt: u32) -> bool {
+        attempt < self.max_attempts
+    }
+
+    /// The time to wait after the given attempt, counting from 1, or the time the API asked to wait.
+    pub(crate) fn delay(&self, attempt: u32, retry_after: Option<Duration>) -> Duration {
+        if let Some(retry_after) = retry_after {
+            return retry_after.min(self.max_delay);
+        }
+        let backoff = self
+            .base_delay
+            .saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)))
+            .min(self.max_delay);
+        backoff.mul_f64(1.0 - self.jitter * random())
+    }
+}
+
+/// Whether a response may be an error worth retrying, before reading its body.
+pub(crate) fn retryable_status(status: reqwest::StatusCode) -> bool {
+    use reqwest::StatusCode;
+    status.is_server_error()
+        || [StatusCode::TOO_MANY_REQUESTS, StatusCode::REQUEST_TIMEOUT, StatusCode::CONFLICT].contains(&status)
+}
+
+/// A random number between 0 and 1, good enough to spread out retries.
+fn random() -> f64 {
+    let mut hasher = std::collections::hash_map::RandomState::new().build_hasher();
+    hasher.write_u128(std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_nanos());
+    (hasher.finish() >> 11) as f64 / (1u64 << 53) as f64
+}
@@ -0,0 +1,126 @@
+//! Graceful shutdown of a [Client](crate::Client), see [Client::shutdown](crate::Client::shutdown).
+//!
+//! A request is in flight from the moment it is sent until its response, or the stream of a streamed response,
+//! has been read or dropped. Clones of a client share their requests in flight, so shutting down one clone shuts down all.
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use bytes::Bytes;
+use futures_core::Stream;
+use tokio::sync::Notify;
+
+/// The request was not sent or was aborted, because the client is shutting down.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShuttingDown;
+
+impl std::fmt::Display for ShuttingDown {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "the client is shutting down")
+    }
+}
+
+impl std::error::Error for ShuttingDown {}
+
+/// Counts the requests in flight, and tells them when to abort.
+#[derive(Debug, Default)]
+pub(crate) struct Tracker {
+    closed: AtomicBool,
+    aborted: AtomicBool,
+    in_flight: AtomicUsize,
+    /// Notified when a request finishes or the requests are aborted.
+    changed: Notify,
+}
+
+impl Tracker {
+    /// Start a request, unless the client is shutting down.
+    pub(crate) fn enter(self: &Arc<Self>) -> Result<Guard, ShuttingDown> {
+        // Counted before checking, so a shutdown starting in between waits for this request.
+        // When closed, dropping the guard uncounts it again.
+        self.in_flight.fetch_add(1, Ordering::SeqCst);
+        let guard = Guard(self.clone());
+        if self.closed.load(Ordering::SeqCst) {
+            return Err(ShuttingDown);
+        }
+        Ok(guard)
+    }
+
+    /// Resolves when the requests in flight are aborted.
+    pub(crate) async fn aborted(&self) {
+        self.wait_for(|tracker| tracker.aborted.load(Ordering::SeqCst)).await
+    }
+
+    /// Stop new requests and wait for the requests in flight, aborting them after the timeout.
+    ///
+    /// Returns whether all requests finished in time.
+    pub(crate) async fn shutdown(&self, timeout: Duration) -> bool {
+        self.closed.store(true, Ordering::SeqCst);
+        let idle = self.wait_for(|tracker| tracker.in_flight.load(Ordering::SeqCst) == 0);
+        if tokio::time::timeout(timeout, idle).await.is_ok() {
+            return true;
+        }
+        self.aborted.store(true, Ordering::SeqCst);
+        self.changed.notify_waiters();
+        false
+    }
+
+    async fn wait_for(&self, condition: impl Fn(&Tracker) -> bool) {
+        loop {
+            let mut notified = std::pin::pin!(self.changed.notified());
+            notified.as_mut().enable();
+            if condition(self) {
+                return;
+            }
+            notified.await;
+        }
+    }
+}
+
+/// A request in flight, which finishes when dropped.
+#[derive(Debug)]
+pub(crate) struct Guard(Arc<Tracker>);
+
+impl Drop for Guard {
+    fn drop(&mut self) {
+        self.0.in_flight.fetch_sub(1, Ordering::SeqCst);
+        self.0.changed.notify_waiters();
+    }
+}
+
+/// The body of a response, which keeps its request in flight until it is read or dropped, and fails when aborted.
+pub(crate) struct TrackedBody {
+    body: Pin<Box<dyn Stream<Item = reqwest::Result<Bytes>> + Send>>,
+    aborted: Pin<Box<dyn Future<Output = ()> + Send>>,
+    done: bool,
+    _guard: Guard,
+}
+
+impl TrackedBody {
+    pub(crate) fn new(res: reqwest::Response, guard: Guard) -> TrackedBody {
+        let tracker = guard.0.clone();
+        TrackedBody {
+            body: Box::pin(res.bytes_stream()),
+            aborted: Box::pin(async move { tracker.aborted().await }),
+            done: false,
+            _guard: guard,
+        }
+    }
+}
+
+impl Stream for TrackedBody {
+    type Item = Result<Bytes, Box<dyn std::error::Error + Send + Sync>>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.done {
+            return Poll::Ready(None);
+        }
+        if self.aborted.as_mut().poll(cx).is_ready() {
+            self.done = true;
+            return Poll::Ready(Some(Err(Box::new(ShuttingDown))));
+        }
+        self.body.as_mut().poll_next(cx).map(|chunk| chunk.map(|chunk| chunk.map_err(Into::into)))
+    }
+}
@@ -0,0 +1,113 @@
+//! Parsing of [server-sent events](https://html.spec.whatwg.org/multipage/server-sent-events.html),
+//! the format of streamed responses.
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use bytes::Bytes;
+use futures_core::Stream;
+
+/// A server-sent event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Event {
+    /// The type of the event, from the `event:` field.
+    pub(crate) event: Option<String>,
+    /// The `data:` lines of the event, joined by newlines.
+    pub(crate) data: String,
+}
+
+/// The events of a response body.
+///
+/// Lines may end with `\n`, `\r\n` or `\r`, and may be split across chunks anywhere, even inside a character.
+/// Comments, `id:` and `retry:` lines are skipped, and an event the body ends in the middle of is dropped.
+pub(crate) struct EventStream {
+    bytes: Pin<Box<dyn Stream<Item = reqwest::Result<Bytes>> + Send>>,
+    // bytes received after the last complete line
+    buf: Vec<u8>,
+    event: Option<String>,
+    data: Option<String>,
+    // set once the body ended
+    done: bool,
+}
+
+impl EventStream {
+    pub(crate) fn new(bytes: Pin<Box<dyn Stream<Item = reqwest::Result<Bytes>> + Send>>) -> EventStream {
+        EventStream {
+            bytes,
+            buf: Vec::new(),
+            event: None,
+            data: None,
+            done: false,
+        }
+    }
+
+    /// The next complete event in the buffer.
+    fn next_event(&mut self) -> Option<Event> {
+        while let Some(line) = self.next_line() {
+            if line.is_empty() {
+                match self.data.take() {
+                    Some(data) => {
+                        return Some(Event {
+                            event: self.event.take(),
+                            data,
+                        })
+                    }
+                    None => self.event = None,
+                }
+                continue;
+            }
+            let (field, value) = match line.split_once(':') {
+                Some((field, value)) => (field, value.strip_prefix(' ').unwrap_or(value)),
+                None => (line.as_str(), ""),
+            };
+            match field {
+                // A comment, like the keep-alives of some servers
+                "" => {}
+                "event" => self.event = Some(value.to_owned()),
+                "data" => match &mut self.data {
+                    Some(data) => {
+                        data.push('\n');
+                        data.push_str(value);
+                    }
+                    None => self.data = Some(value.to_owned()),
+                },
+                _ => {}
+            }
+        }
+        None
+    }
+
+    /// The next complete line in the buffer, without its line ending.
+    fn next_line(&mut self) -> Option<String> {
+        let end = self.buf.iter().position(|&b| b == b'\n' || b == b'\r')?;
+        let ending = match (self.buf[end], self.buf.get(end + 1)) {
+            (b'\r', Some(b'\n')) => 2,
+            // The `\n` of a `\r\n` may still arrive
+            (b'\r', None) if !self.done => return None,
+            _ => 1,
+        };
+        let line = String::from_utf8_lossy(&self.buf[..end]).into_owned();
+        self.buf.drain(..end + ending);
+        Some(line)
+    }
+}
+
+impl Stream for EventStream {
+    type Item = reqwest::Result<Event>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            if let Some(event) = self.next_event() {
+                return Poll::Ready(Some(Ok(event)));
+            }
+            if self.done {
+                return Poll::Ready(None);
+            }
+            match self.bytes.as_mut().poll_next(cx) {
+                Poll::Ready(Some(Ok(bytes))) => self.buf.extend_from_slice(&bytes),
+                Poll::Ready(Some(Err(e))) => return Poll::Ready(Some(Err(e))),
+                Poll::Ready(None) => self.done = true,
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
@@ -0,0 +1,68 @@
+//! The sequences where the API stops generating, the `stop` parameter of
+//! chat completions and completions.
+//!
+//! ```
+//! use openai_rust::stop::StopSequences;
+//! let stop = StopSequences::from(["\n\n", "User:"]);
+//! assert_eq!(serde_json::to_string(&stop).unwrap(), r#"["\n\n","User:"]"#);
+//! assert_eq!(serde_json::to_string(&StopSequences::from("END")).unwrap(), r#""END""#);
+//! ```
+use serde::{Deserialize, Serialize};
+
+/// Sequences where the API will stop generating further tokens.
+/// The returned text will not contain the stop sequence.
+///
+/// The amount of sequences is not checked here. The API rejects an empty list,
+/// and OpenAI accepts at most 4 sequences, while other providers may accept more.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(untagged)]
+pub enum StopSequences {
+    Single(String),
+    Multiple(Vec<String>),
+}
+
+impl StopSequences {
+    /// The sequences, one for [StopSequences::Single].
+    pub fn as_slice(&self) -> &[String] {
+        match self {
+            StopSequences::Single(stop) => std::slice::from_ref(stop),
+            StopSequences::Multiple(stops) => stops,
+        }
+    }
+}
+
+impl From<&str> for StopSequences {
+    fn from(stop: &str) -> Self {
+        StopSequences::Single(stop.to_owned())
+    }
+}
+
+impl From<String> for StopSequences {
+    fn from(stop: String) -> Self {
+        StopSequences::Single(stop)
+    }
+}
+
+impl From<&String> for StopSequences {
+    fn from(stop: &String) -> Self {
+        StopSequences::Single(stop.clone())
+    }
+}
+
+impl From<Vec<String>> for StopSequences {
+    fn from(stops: Vec<String>) -> Self {
+        StopSequences::Multiple(stops)
+    }
+}
+
+impl From<Vec<&str>> for StopSequences {
+    fn from(stops: Vec<&str>) -> Self {
+        StopSequences::Multiple(stops.into_iter().map(str::to_owned).collect())
+    }
+}
+
+impl<const N: usize> From<[&str; N]> for StopSequences {
+    fn from(stops: [&str; N]) -> Self {
+        StopSequences::Multiple(stops.into_iter().map(str::to_owned).collect())
+    }
+}
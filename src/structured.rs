@@ -0,0 +1,380 @@
+//! Chat completions parsed into Rust types, with [Structured Outputs](https://platform.openai.com/docs/guides/structured-outputs).
+//! Use with [Client::create_structured_chat](crate::Client::create_structured_chat).
+//!
+//! The schema of a type is given by implementing [JsonSchema], by hand or by forwarding to a crate like
+//! [schemars](https://crates.io/crates/schemars). The schema has to meet the constraints of strict mode,
+//! see [validate_strict_schema](crate::tools::validate_strict_schema).
+//!
+//! ```no_run
+//! # tokio_test::block_on(async {
+//! # let api_key = "";
+//! use openai_rust::chat::{ChatArguments, Message};
+//! use openai_rust::structured::{JsonSchema, Refusal};
+//!
+//! #[derive(serde::Deserialize)]
+//! struct Event {
+//!     name: String,
+//!     date: String,
+//! }
+//!
+//! impl JsonSchema for Event {
+//!     fn schema_name() -> String {
+//!         "event".to_owned()
+//!     }
+//!
+//!     fn json_schema() -> serde_json::Value {
+//!         serde_json::json!({
+//!             "type": "object",
+//!             "properties": { "name": { "type": "string" }, "date": { "type": "string" } },
+//!             "required": ["name", "date"],
+//!             "additionalProperties": false
+//!         })
+//!     }
+//! }
+//!
+//! let client = openai_rust::Client::new(api_key);
+//! let args = ChatArguments::new("gpt-4o", vec![Message::user("Alice and Bob are going to a science fair on Friday.")]);
+//! match client.create_structured_chat::<Event>(args).await {
+//!     Ok(event) => println!("{} on {}", event.name, event.date),
+//!     Err(e) => match e.downcast_ref::<Refusal>() {
+//!         Some(refusal) => println!("refused: {}", refusal.message),
+//!         None => println!("error: {}", e),
+//!     },
+//! }
+//! # })
+//! ```
+//!
+//! The model can still produce invalid JSON, like in JSON mode or when the output was cut off.
+//! With [Client::with_json_repair](crate::Client::with_json_repair), the output is [validate]d
+//! and the model is asked to correct it, failing with [InvalidOutput] when it keeps producing invalid JSON.
+use serde_json::Value;
+
+use crate::chat::ResponseFormat;
+
+/// A type with a JSON schema, which the model can be asked to follow.
+pub trait JsonSchema {
+    /// The name of the schema, containing only a-z, A-Z, 0-9, underscores and dashes.
+    fn schema_name() -> String;
+
+    /// The JSON schema of the serialized type.
+    fn json_schema() -> serde_json::Value;
+}
+
+/// The strict [ResponseFormat::JsonSchema] of `T`, to stream structured output or send it in a [batch](crate::batches).
+///
+/// Fails when the schema does not meet the constraints of strict mode.
+///
+/// ```
+/// use openai_rust::chat::ChatArguments;
+/// use openai_rust::structured::{response_format, JsonSchema};
+///
+/// struct Answer;
+///
+/// impl JsonSchema for Answer {
+///     fn schema_name() -> String {
+///         "answer".to_owned()
+///     }
+///
+///     fn json_schema() -> serde_json::Value {
+///         serde_json::json!({
+///             "type": "object",
+///             "properties": { "answer": { "type": "string" } },
+///             "required": ["answer"],
+///             "additionalProperties": false
+///         })
+///     }
+/// }
+///
+/// let args = ChatArguments::new("gpt-4o", vec![]).response_format(response_format::<Answer>().unwrap());
+/// let body = serde_json::to_value(&args).unwrap();
+/// assert_eq!(body["response_format"]["json_schema"]["strict"], true);
+/// ```
+pub fn response_format<T: JsonSchema>() -> anyhow::Result<ResponseFormat> {
+    let schema = T::json_schema();
+    let errors = crate::tools::validate_strict_schema(&schema);
+    if !errors.is_empty() {
+        let errors: Vec<String> = errors.iter().map(ToString::to_string).collect();
+        return Err(anyhow::anyhow!("the schema of {} is not valid in strict mode: {}", T::schema_name(), errors.join(", ")));
+    }
+    Ok(ResponseFormat::JsonSchema {
+        json_schema: crate::chat::JsonSchema::new(T::schema_name(), schema).strict(true),
+    })
+}
+
+/// The model refused to answer, such as for safety reasons.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Refusal {
+    /// The explanation of the model.
+    pub message: String,
+}
+
+impl std::fmt::Display for Refusal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "the model refused: {}", self.message)
+    }
+}
+
+impl std::error::Error for Refusal {}
+
+/// The model kept producing JSON that is not valid, see [Client::with_json_repair](crate::Client::with_json_repair).
+#[derive(Debug, Clone, PartialEq)]
+pub struct InvalidOutput {
+    /// Every output of the model, in order.
+    pub attempts: Vec<Attempt>,
+}
+
+/// An output of the model and why it is not valid.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Attempt {
+    pub content: String,
+    pub errors: Vec<Violation>,
+}
+
+impl std::fmt::Display for InvalidOutput {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "the output was not valid after {} attempts", self.attempts.len())?;
+        if let Some(last) = self.attempts.last() {
+            let errors: Vec<String> = last.errors.iter().map(ToString::to_string).collect();
+            write!(f, ": {}", errors.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for InvalidOutput {}
+
+/// A reason JSON does not follow a schema.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    /// Where in the JSON, like `#/events/0/date`.
+    pub path: String,
+    pub message: String,
+}
+
+impl std::fmt::Display for Violation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.path, self.message)
+    }
+}
+
+/// The message asking the model to correct its output, listing what is wrong with it.
+pub(crate) fn repair_prompt(errors: &[Violation]) -> String {
+    let errors: Vec<String> = errors.iter().map(|e| format!("- {}", e)).collect();
+    format!(
+        "Your reply is not valid:\n{}\nReply again with only the corrected JSON.",
+        errors.join("\n")
+    )
+}
+
+/// Check the content of a reply against a response format. Text is always valid.
+pub(crate) fn check(content: &str, format: &ResponseFormat) -> Vec<Violation> {
+    if let ResponseFormat::Text = format {
+        return vec![];
+    }
+    let value: Value = match serde_json::from_str(content) {
+        Ok(value) => value,
+        Err(e) => {
+            return vec![violation("#", format!("is not valid JSON: {}", e))]
+        }
+    };
+    match format {
+        ResponseFormat::JsonSchema { json_schema } => validate(&value, &json_schema.schema),
+        _ => vec![],
+    }
+}
+
+/// Validate JSON against a JSON schema.
+///
+/// This supports the keywords of [Structured Outputs](https://platform.openai.com/docs/guides/structured-outputs#supported-schemas):
+/// `type`, `enum`, `const`, `properties`, `required`, `additionalProperties`, `items`, `anyOf`, `allOf`, `oneOf`,
+/// local `$ref`s, and the limits on lengths, items and numbers. Other keywords are ignored.
+///
+/// ```
+/// use serde_json::json;
+/// let schema = json!({
+///     "type": "object",
+///     "properties": { "name": { "type": "string" }, "age": { "type": "integer", "minimum": 0 } },
+///     "required": ["name", "age"],
+///     "additionalProperties": false
+/// });
+/// assert!(openai_rust::structured::validate(&json!({ "name": "Alice", "age": 30 }), &schema).is_empty());
+///
+/// let errors = openai_rust::structured::validate(&json!({ "age": -1 }), &schema);
+/// let errors: Vec<String> = errors.iter().map(ToString::to_string).collect();
+/// assert_eq!(errors, ["# is missing property \"name\"", "#/age is less than the minimum of 0"]);
+/// ```
+pub fn validate(value: &Value, schema: &Value) -> Vec<Violation> {
+    let mut errors = vec![];
+    validate_node(value, schema, schema, "#".to_owned(), &[], &mut errors);
+    errors
+}
+
+/// `refs` are the `$ref`s followed without descending into the value, to stop at cycles.
+fn validate_node<'a>(
+    value: &Value,
+    schema: &'a Value,
+    root: &'a Value,
+    path: String,
+    refs: &[&'a str],
+    errors: &mut Vec<Violation>,
+) {
+    let Some(node) = schema.as_object() else {
+        if schema == &Value::Bool(false) {
+            errors.push(violation(&path, "is not allowed".to_owned()));
+        }
+        return;
+    };
+    if let Some(reference) = node.get("$ref").and_then(Value::as_str) {
+        if refs.contains(&reference) {
+            errors.push(violation(&path, format!("refers to {:?} in a cycle", reference)));
+            return;
+        }
+        let refs = [refs, &[reference]].concat();
+        match reference.strip_prefix('#').and_then(|pointer| root.pointer(pointer)) {
+            Some(target) => validate_node(value, target, root, path.clone(), &refs, errors),
+            None => errors.push(violation(&path, format!("refers to {:?}, which does not exist", reference))),
+        }
+        return;
+    }
+
+    if let Some(kinds) = node.get("type") {
+        let kinds: Vec<&str> = match kinds {
+            Value::String(kind) => vec![kind],
+            Value::Array(kinds) => kinds.iter().filter_map(Value::as_str).collect(),
+            _ => vec![],
+        };
+        if !kinds.iter().any(|kind| is_type(value, kind)) {
+            errors.push(violation(&path, format!("is {}, not {}", type_name(value), kinds.join(" or "))));
+            return;
+        }
+    }
+    if let Some(options) = node.get("enum").and_then(Value::as_array) {
+        if !options.contains(value) {
+            errors.push(violation(&path, format!("is {}, not one of {}", value, Value::Array(options.clone()))));
+        }
+    }
+    if let Some(constant) = node.get("const") {
+        if constant != value {
+            errors.push(violation(&path, format!("is {}, not {}", value, constant)));
+        }
+    }
+
+    match value {
+        Value::Object(object) => {
+            let properties = node.get("properties").and_then(Value::as_object);
+            for required in node.get("required").and_then(Value::as_array).into_iter().flatten() {
+                if let Some(required) = required.as_str().filter(|required| !object.contains_key(*required)) {
+                    errors.push(violation(&path, format!("is missing property {:?}", required)));
+                }
+            }
+            for (key, item) in object {
+                let item_path = format!("{}/{}", path, key);
+                match (properties.and_then(|properties| properties.get(key)), node.get("additionalProperties")) {
+                    (Some(property), _) => validate_node(item, property, root, item_path, &[], errors),
+                    (None, Some(Value::Bool(false))) => {
+                        errors.push(violation(&item_path, "is not an allowed property".to_owned()))
+                    }
+                    (None, Some(additional)) => validate_node(item, additional, root, item_path, &[], errors),
+                    (None, None) => {}
+                }
+            }
+        }
+        Value::Array(items) => {
+            if let Some(min) = node.get("minItems").and_then(Value::as_u64).filter(|min| (items.len() as u64) < *min) {
+                errors.push(violation(&path, format!("has fewer than {} items", min)));
+            }
+            if let Some(max) = node.get("maxItems").and_then(Value::as_u64).filter(|max| (items.len() as u64) > *max) {
+                errors.push(violation(&path, format!("has more than {} items", max)));
+            }
+            if let Some(item_schema) = node.get("items") {
+                for (i, item) in items.iter().enumerate() {
+                    validate_node(item, item_schema, root, format!("{}/{}", path, i), &[], errors);
+                }
+            }
+        }
+        Value::String(string) => {
+            let length = string.chars().count() as u64;
+            if let Some(min) = node.get("minLength").and_then(Value::as_u64).filter(|min| length < *min) {
+                errors.push(violation(&path, format!("is shorter than {} characters", min)));
+            }
+            if let Some(max) = node.get("maxLength").and_then(Value::as_u64).filter(|max| length > *max) {
+                errors.push(violation(&path, format!("is longer than {} characters", max)));
+            }
+        }
+        Value::Number(number) => {
+            let number = number.as_f64().unwrap_or_default();
+            let limits = [
+                ("minimum", "less than the minimum of"),
+                ("maximum", "more than the maximum of"),
+                ("exclusiveMinimum", "not more than"),
+                ("exclusiveMaximum", "not less than"),
+            ];
+            for (keyword, description) in limits {
+                let Some(limit) = node.get(keyword).filter(|limit| limit.is_number()) else {
+                    continue;
+                };
+                let bound = limit.as_f64().unwrap_or_default();
+                let violated = match keyword {
+                    "minimum" => number < bound,
+                    "maximum" => number > bound,
+                    "exclusiveMinimum" => number <= bound,
+                    _ => number >= bound,
+                };
+                if violated {
+                    errors.push(violation(&path, format!("is {} {}", description, limit)));
+                }
+            }
+        }
+        _ => {}
+    }
+
+    if let Some(all) = node.get("allOf").and_then(Value::as_array) {
+        for schema in all {
+            validate_node(value, schema, root, path.clone(), refs, errors);
+        }
+    }
+    for (keyword, exactly_one) in [("anyOf", false), ("oneOf", true)] {
+        let Some(options) = node.get(keyword).and_then(Value::as_array) else {
+            continue;
+        };
+        let matching = options
+            .iter()
+            .filter(|schema| {
+                let mut option_errors = vec![];
+                validate_node(value, schema, root, path.clone(), refs, &mut option_errors);
+                option_errors.is_empty()
+            })
+            .count();
+        if matching == 0 || (exactly_one && matching > 1) {
+            let expected = if exactly_one { "exactly one" } else { "any" };
+            errors.push(violation(&path, format!("does not match {} of the schemas of {}", expected, keyword)));
+        }
+    }
+}
+
+fn violation(path: &str, message: String) -> Violation {
+    Violation {
+        path: path.to_owned(),
+        message,
+    }
+}
+
+/// Whether a value is of a JSON schema type. Integers are numbers without a fraction.
+fn is_type(value: &Value, kind: &str) -> bool {
+    match kind {
+        "integer" => value.as_f64().is_some_and(|n| n.fract() == 0.0),
+        "number" => value.is_number(),
+        kind => type_name(value) == kind,
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
@@ -0,0 +1,345 @@
+//! Builders for fake responses, for use in the unit tests of applications using this crate.
+//! Requires the `test_utils` feature.
+//!
+//! The builders start out with realistic values, so only the fields a test cares about have to be set.
+//!
+//! ```
+//! use openai_rust::test_utils::ChatCompletionBuilder;
+//! let completion = ChatCompletionBuilder::new()
+//!     .content("Hello there, how may I assist you today?")
+//!     .usage(9, 12)
+//!     .build();
+//! assert_eq!(completion.to_string(), "Hello there, how may I assist you today?");
+//! assert_eq!(completion.usage.unwrap().total_tokens, 21);
+//! ```
+#[cfg(feature = "chat")]
+use crate::chat::{self, stream};
+#[cfg(feature = "embeddings")]
+use crate::embeddings;
+
+/// Builds a [chat::ChatCompletion].
+#[cfg(feature = "chat")]
+#[derive(Debug, Clone)]
+pub struct ChatCompletionBuilder {
+    id: String,
+    created: u64,
+    choices: Vec<chat::Choice>,
+    finish_reason: String,
+    usage: Option<chat::Usage>,
+}
+
+#[cfg(feature = "chat")]
+impl Default for ChatCompletionBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "chat")]
+impl ChatCompletionBuilder {
+    pub fn new() -> ChatCompletionBuilder {
+        ChatCompletionBuilder {
+            id: "chatcmpl-123".to_owned(),
+            created: 1677652288,
+            choices: vec![],
+            finish_reason: "stop".to_owned(),
+            usage: Some(chat::Usage {
+                prompt_tokens: 9,
+                completion_tokens: 12,
+                total_tokens: 21,
+            }),
+        }
+    }
+
+    pub fn id(mut self, id: impl AsRef<str>) -> Self {
+        self.id = id.as_ref().to_owned();
+        self
+    }
+
+    pub fn created(mut self, created: u64) -> Self {
+        self.created = created;
+        self
+    }
+
+    /// Add a choice with a message from the assistant.
+    /// Call this multiple times to emulate `n` being larger than 1.
+    pub fn content(self, content: impl AsRef<str>) -> Self {
+        self.message(chat::Message::assistant(content.as_ref()))
+    }
+
+    /// Add a choice with any message.
+    pub fn message(mut self, message: chat::Message) -> Self {
+        self.choices.push(chat::Choice {
+            index: self.choices.len() as u32,
+            message,
+            // Set in build
+            finish_reason: String::new(),
+            logprobs: None,
+        });
+        self
+    }
+
+    /// The finish reason of all choices. Defaults to `stop`.
+    pub fn finish_reason(mut self, finish_reason: impl AsRef<str>) -> Self {
+        self.finish_reason = finish_reason.as_ref().to_owned();
+        self
+    }
+
+    pub fn usage(mut self, prompt_tokens: u32, completion_tokens: u32) -> Self {
+        self.usage = Some(chat::Usage {
+            prompt_tokens,
+            completion_tokens,
+            total_tokens: prompt_tokens + completion_tokens,
+        });
+        self
+    }
+
+    /// Leave out the usage, like some OpenAI compatible servers do.
+    pub fn without_usage(mut self) -> Self {
+        self.usage = None;
+        self
+    }
+
+    /// Build the completion. Without any choices added, the completion has a single empty message.
+    pub fn build(self) -> chat::ChatCompletion {
+        let finish_reason = self.finish_reason;
+        let mut choices = self.choices;
+        if choices.is_empty() {
+            choices.push(chat::Choice {
+                index: 0,
+                message: chat::Message::assistant(""),
+                finish_reason: String::new(),
+                logprobs: None,
+            });
+        }
+        for choice in choices.iter_mut() {
+            choice.finish_reason = finish_reason.clone();
+        }
+        chat::ChatCompletion {
+            id: self.id,
+            created: self.created,
+            choices,
+            usage: self.usage,
+            metadata: None,
+        }
+    }
+}
+
+/// Builds a [stream::ChatCompletionChunk].
+///
+/// ```
+/// use openai_rust::test_utils::ChatCompletionChunkBuilder;
+/// let chunks = ChatCompletionChunkBuilder::new().split("Hello there!");
+/// assert_eq!(chunks.len(), 2);
+/// let text: String = chunks.iter().map(|c| c.to_string()).collect();
+/// assert_eq!(text, "Hello there!");
+/// assert_eq!(chunks[1].choices[0].finish_reason.as_deref(), Some("stop"));
+/// ```
+#[cfg(feature = "chat")]
+#[derive(Debug, Clone)]
+pub struct ChatCompletionChunkBuilder {
+    id: String,
+    created: u64,
+    model: String,
+    content: Option<String>,
+    finish_reason: Option<String>,
+    system_fingerprint: Option<String>,
+}
+
+#[cfg(feature = "chat")]
+impl Default for ChatCompletionChunkBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "chat")]
+impl ChatCompletionChunkBuilder {
+    pub fn new() -> ChatCompletionChunkBuilder {
+        ChatCompletionChunkBuilder {
+            id: "chatcmpl-6yX67cSCIAm4nrNLQUPOtJu9JUoLG".to_owned(),
+            created: 1679884927,
+            model: "gpt-3.5-turbo-0301".to_owned(),
+            content: None,
+            finish_reason: None,
+            system_fingerprint: None,
+        }
+    }
+
+    pub fn id(mut self, id: impl AsRef<str>) -> Self {
+        self.id = id.as_ref().to_owned();
+        self
+    }
+
+    pub fn created(mut self, created: u64) -> Self {
+        self.created = created;
+        self
+    }
+
+    pub fn model(mut self, model: impl AsRef<str>) -> Self {
+        self.model = model.as_ref().to_owned();
+        self
+    }
+
+    /// The content of the delta.
+    pub fn content(mut self, content: impl AsRef<str>) -> Self {
+        self.content = Some(content.as_ref().to_owned());
+        self
+    }
+
+    pub fn finish_reason(mut self, finish_reason: impl AsRef<str>) -> Self {
+        self.finish_reason = Some(finish_reason.as_ref().to_owned());
+        self
+    }
+
+    pub fn system_fingerprint(mut self, system_fingerprint: impl AsRef<str>) -> Self {
+        self.system_fingerprint = Some(system_fingerprint.as_ref().to_owned());
+        self
+    }
+
+    pub fn build(self) -> stream::ChatCompletionChunk {
+        stream::ChatCompletionChunk {
+            id: self.id,
+            created: self.created,
+            model: self.model,
+            choices: vec![stream::Choice {
+                delta: stream::ChoiceDelta {
+                    content: self.content,
+                    tool_calls: vec![],
+                },
+                index: 0,
+                finish_reason: self.finish_reason,
+                logprobs: None,
+            }],
+            system_fingerprint: self.system_fingerprint,
+            usage: None,
+        }
+    }
+
+    /// Split a text into chunks the way the API would stream it, one word per chunk.
+    /// The last chunk has the finish reason `stop`, unless another finish reason was set.
+    pub fn split(self, text: &str) -> Vec<stream::ChatCompletionChunk> {
+        let mut words: Vec<String> = vec![];
+        for (i, word) in text.split(' ').enumerate() {
+            if i == 0 {
+                words.push(word.to_owned());
+            } else {
+                words.push(format!(" {}", word));
+            }
+        }
+        let last = words.len() - 1;
+        let finish_reason = self.finish_reason.clone().unwrap_or("stop".to_owned());
+        words
+            .into_iter()
+            .enumerate()
+            .map(|(i, word)| {
+                let mut chunk = self.clone().content(word);
+                chunk.finish_reason = None;
+                if i == last {
+                    chunk = chunk.finish_reason(&finish_reason);
+                }
+                chunk.build()
+            })
+            .collect()
+    }
+}
+
+/// A [stream::ChatCompletionChunkStream] of the given chunks, ended like the API does.
+///
+/// ```
+/// # tokio_test::block_on(async {
+/// use openai_rust::futures_util::StreamExt;
+/// use openai_rust::test_utils::{chunk_stream, ChatCompletionChunkBuilder};
+/// let mut stream = chunk_stream(ChatCompletionChunkBuilder::new().split("Hello there!"));
+/// let mut text = String::new();
+/// while let Some(chunk) = stream.next().await {
+///     text += &chunk.unwrap().to_string();
+/// }
+/// assert_eq!(text, "Hello there!");
+/// assert_eq!(stream.end().unwrap().finish_reason.as_deref(), Some("stop"));
+/// # })
+/// ```
+#[cfg(feature = "chat")]
+pub fn chunk_stream(chunks: Vec<stream::ChatCompletionChunk>) -> stream::ChatCompletionChunkStream {
+    let mut body = String::new();
+    for chunk in chunks {
+        body += &format!("data: {}\n\n", serde_json::to_string(&chunk).unwrap());
+    }
+    body += "data: [DONE]\n\n";
+    let bytes = futures_util::stream::iter([Ok(bytes::Bytes::from(body))]);
+    stream::ChatCompletionChunkStream::new(Box::pin(bytes))
+}
+
+/// Builds an [embeddings::EmbeddingsResponse].
+///
+/// ```
+/// use openai_rust::test_utils::EmbeddingsResponseBuilder;
+/// let res = EmbeddingsResponseBuilder::new()
+///     .embedding(vec![0.1, 0.2, 0.3])
+///     .build();
+/// assert_eq!(res.data[0].embedding.len(), 3);
+/// ```
+#[cfg(feature = "embeddings")]
+#[derive(Debug, Clone)]
+pub struct EmbeddingsResponseBuilder {
+    model: String,
+    data: Vec<embeddings::EmbeddingsData>,
+    usage: Option<embeddings::Usage>,
+}
+
+#[cfg(feature = "embeddings")]
+impl Default for EmbeddingsResponseBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "embeddings")]
+impl EmbeddingsResponseBuilder {
+    pub fn new() -> EmbeddingsResponseBuilder {
+        EmbeddingsResponseBuilder {
+            model: "text-embedding-ada-002".to_owned(),
+            data: vec![],
+            usage: Some(embeddings::Usage {
+                prompt_tokens: 8,
+                total_tokens: 8,
+            }),
+        }
+    }
+
+    pub fn model(mut self, model: impl AsRef<str>) -> Self {
+        self.model = model.as_ref().to_owned();
+        self
+    }
+
+    /// Add an embedding. Call this multiple times for multiple inputs.
+    pub fn embedding(mut self, embedding: Vec<f32>) -> Self {
+        self.data.push(embeddings::EmbeddingsData {
+            embedding,
+            index: self.data.len(),
+        });
+        self
+    }
+
+    pub fn usage(mut self, prompt_tokens: u32) -> Self {
+        self.usage = Some(embeddings::Usage {
+            prompt_tokens,
+            total_tokens: prompt_tokens,
+        });
+        self
+    }
+
+    /// Leave out the usage, like some OpenAI compatible servers do.
+    pub fn without_usage(mut self) -> Self {
+        self.usage = None;
+        self
+    }
+
+    pub fn build(self) -> embeddings::EmbeddingsResponse {
+        embeddings::EmbeddingsResponse {
+            data: self.data,
+            model: self.model,
+            usage: self.usage,
+        }
+    }
+}
@@ -0,0 +1,809 @@
+//! See <https://platform.openai.com/docs/api-reference/threads>.
+//! Use with [Client::create_thread](crate::Client::create_thread), [Client::create_message](crate::Client::create_message),
+//! [Client::create_run](crate::Client::create_run) and [Client::create_run_stream](crate::Client::create_run_stream).
+//!
+//! A thread is a conversation with an assistant. Messages are added to the thread, and a run
+//! lets the assistant respond to them:
+//!
+//! ```no_run
+//! # tokio_test::block_on(async {
+//! # let api_key = "";
+//! use std::time::Duration;
+//! use openai_rust::threads::{MessageArguments, RunArguments, ThreadArguments};
+//! let c = openai_rust::Client::new(api_key);
+//! let mut args = ThreadArguments::new();
+//! args.messages.push(MessageArguments::user("How many moons does Mars have?"));
+//! let thread = c.create_thread(args).await.unwrap();
+//! let run = c.create_run(&thread.id, RunArguments::new("asst_abc123")).await.unwrap();
+//! let run = c.wait_for_run(&thread.id, &run.id, Duration::from_secs(1)).await.unwrap();
+//! assert_eq!(run.status, "completed");
+//! let messages: Vec<_> = openai_rust::futures_util::StreamExt::collect(c.list_messages(&thread.id, Default::default())).await;
+//! println!("{}", messages[0].as_ref().unwrap().text());
+//! # })
+//! ```
+
+use std::collections::HashMap;
+use std::pin::Pin;
+use std::task::Poll;
+
+use bytes::Bytes;
+use futures_core::Stream;
+use serde::{Deserialize, Serialize};
+
+use crate::assistants::{Tool, ToolResources};
+use crate::sse::EventStream;
+use crate::vision::ImageUrl;
+
+/// Request arguments for creating a thread.
+///
+/// See <https://platform.openai.com/docs/api-reference/threads/createThread>.
+#[derive(Serialize, Debug, Clone, Default, PartialEq)]
+#[non_exhaustive]
+pub struct ThreadArguments {
+    /// Messages to start the thread with.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub messages: Vec<MessageArguments>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_resources: Option<ToolResources>,
+
+    /// Up to 16 key-value pairs.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<HashMap<String, String>>,
+}
+
+impl ThreadArguments {
+    pub fn new() -> ThreadArguments {
+        ThreadArguments::default()
+    }
+}
+
+/// Request arguments for modifying a thread. Only the given fields are changed.
+///
+/// See <https://platform.openai.com/docs/api-reference/threads/modifyThread>.
+#[derive(Serialize, Debug, Clone, Default, PartialEq)]
+#[non_exhaustive]
+pub struct ModifyThreadArguments {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_resources: Option<ToolResources>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<HashMap<String, String>>,
+}
+
+impl ModifyThreadArguments {
+    pub fn new() -> ModifyThreadArguments {
+        ModifyThreadArguments::default()
+    }
+}
+
+/// A conversation with an assistant.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct Thread {
+    pub id: String,
+    pub created_at: u64,
+    #[serde(default)]
+    pub tool_resources: Option<ToolResources>,
+    #[serde(default)]
+    pub metadata: HashMap<String, String>,
+}
+
+/// The response of [Client::delete_thread](crate::Client::delete_thread).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct DeletedThread {
+    pub id: String,
+    pub deleted: bool,
+}
+
+/// Request arguments for adding a message to a thread.
+///
+/// See <https://platform.openai.com/docs/api-reference/messages/createMessage>.
+///
+/// ```
+/// use openai_rust::assistants::Tool;
+/// use openai_rust::threads::{Attachment, MessageArguments};
+/// let mut message = MessageArguments::user("Summarize the attached report.");
+/// message.attachments.push(Attachment::new("file-abc123", vec![Tool::file_search(Default::default())]));
+/// ```
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct MessageArguments {
+    /// Either `user` or `assistant`.
+    pub role: String,
+
+    pub content: String,
+
+    /// Files made available to the tools of the run.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub attachments: Vec<Attachment>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<HashMap<String, String>>,
+}
+
+impl MessageArguments {
+    pub fn new(role: impl AsRef<str>, content: impl AsRef<str>) -> MessageArguments {
+        MessageArguments {
+            role: role.as_ref().to_owned(),
+            content: content.as_ref().to_owned(),
+            attachments: vec![],
+            metadata: None,
+        }
+    }
+
+    /// A message from the user.
+    pub fn user(content: impl AsRef<str>) -> MessageArguments {
+        MessageArguments::new("user", content)
+    }
+
+    /// A message inserted as if the assistant wrote it.
+    pub fn assistant(content: impl AsRef<str>) -> MessageArguments {
+        MessageArguments::new("assistant", content)
+    }
+}
+
+/// A file attached to a message, and the tools it is added to.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct Attachment {
+    pub file_id: String,
+    #[serde(default)]
+    pub tools: Vec<Tool>,
+}
+
+impl Attachment {
+    pub fn new(file_id: impl AsRef<str>, tools: Vec<Tool>) -> Attachment {
+        Attachment {
+            file_id: file_id.as_ref().to_owned(),
+            tools,
+        }
+    }
+}
+
+/// A message in a thread.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct Message {
+    pub id: String,
+    pub created_at: u64,
+    pub thread_id: String,
+    /// Either `user` or `assistant`.
+    pub role: String,
+    #[serde(default)]
+    pub content: Vec<MessageContent>,
+    /// The assistant that wrote the message.
+    #[serde(default)]
+    pub assistant_id: Option<String>,
+    /// The run that wrote the message.
+    #[serde(default)]
+    pub run_id: Option<String>,
+    #[serde(default)]
+    pub attachments: Vec<Attachment>,
+    #[serde(default)]
+    pub metadata: HashMap<String, String>,
+}
+
+impl Message {
+    /// The text parts of the message, joined together.
+    ///
+    /// ```
+    /// # let json = r#"{"id": "msg_abc123", "created_at": 1699017614, "thread_id": "thread_abc123", "role": "assistant",
+    /// #   "content": [{"type": "text", "text": {"value": "Mars has two moons.", "annotations": []}}]}"#;
+    /// let message: openai_rust::threads::Message = serde_json::from_str(json).unwrap();
+    /// assert_eq!(message.text(), "Mars has two moons.");
+    /// ```
+    pub fn text(&self) -> String {
+        self.content
+            .iter()
+            .filter_map(|content| match content {
+                MessageContent::Text { text } => Some(text.value.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+/// A part of the content of a [Message].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MessageContent {
+    Text { text: Text },
+    /// An image uploaded through the Files API.
+    ImageFile { image_file: ImageFile },
+    ImageUrl { image_url: ImageUrl },
+    Refusal { refusal: String },
+}
+
+/// The text of a [MessageContent::Text] part.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct Text {
+    pub value: String,
+    /// Citations of the files the text is based on.
+    #[serde(default)]
+    pub annotations: Vec<serde_json::Value>,
+}
+
+/// An image uploaded through the Files API.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct ImageFile {
+    pub file_id: String,
+}
+
+/// Request arguments for creating a run of an assistant on a thread.
+///
+/// See <https://platform.openai.com/docs/api-reference/runs/createRun>.
+///
+/// The tools of the run override the tools of the assistant.
+/// The vector stores searched by the file search tool are configured in the
+/// [tool_resources](crate::assistants::AssistantArguments::tool_resources) of the assistant.
+///
+/// ```
+/// use openai_rust::assistants::{FileSearch, Tool};
+/// let mut args = openai_rust::threads::RunArguments::new("asst_abc123");
+/// args.tools = Some(vec![Tool::file_search(FileSearch {
+///     max_num_results: Some(10),
+///     ..Default::default()
+/// })]);
+/// ```
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct RunArguments {
+    /// The ID of the assistant to use to execute this run.
+    pub assistant_id: String,
+
+    /// Overrides the model of the assistant.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+
+    /// Overrides the instructions of the assistant.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instructions: Option<String>,
+
+    /// Appends additional instructions to the instructions of the assistant.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub additional_instructions: Option<String>,
+
+    /// Overrides the tools of the assistant.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tools: Option<Vec<Tool>>,
+
+    /// What sampling temperature to use, between 0 and 2. Higher values like 0.8 will make the output more random, while lower values like 0.2 will make it more focused and deterministic.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f32>,
+
+    /// An alternative to sampling with temperature, called nucleus sampling, where the model considers the results of the tokens with top_p probability mass. So 0.1 means only the tokens comprising the top 10% probability mass are considered.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f32>,
+
+    /// Additional parameters sent in the body, for parameters not yet supported by this crate or specific to a provider.
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    pub extra: Option<serde_json::Map<String, serde_json::Value>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) stream: Option<bool>,
+}
+
+impl RunArguments {
+    pub fn new(assistant_id: impl AsRef<str>) -> RunArguments {
+        RunArguments {
+            assistant_id: assistant_id.as_ref().to_owned(),
+            model: None,
+            instructions: None,
+            additional_instructions: None,
+            tools: None,
+            temperature: None,
+            top_p: None,
+            extra: None,
+            stream: None,
+        }
+    }
+}
+
+/// An execution of an assistant on a thread.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct Run {
+    pub id: String,
+    pub created_at: u64,
+    pub thread_id: String,
+    pub assistant_id: String,
+    /// One of `queued`, `in_progress`, `requires_action`, `cancelling`, `cancelled`, `failed`, `completed`, `incomplete`, or `expired`.
+    pub status: String,
+    pub model: String,
+    pub instructions: String,
+    #[serde(default)]
+    pub tools: Vec<Tool>,
+    /// What is needed to continue the run, when the status is `requires_action`.
+    #[serde(default)]
+    pub required_action: Option<RequiredAction>,
+}
+
+impl Run {
+    /// Whether the run is still going, so its status will change without any action.
+    pub fn is_active(&self) -> bool {
+        ["queued", "in_progress", "cancelling"].contains(&self.status.as_str())
+    }
+}
+
+/// The action required to continue a [Run].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct RequiredAction {
+    pub submit_tool_outputs: SubmitToolOutputs,
+}
+
+/// The tool calls whose outputs have to be submitted with [Client::submit_tool_outputs](crate::Client::submit_tool_outputs).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct SubmitToolOutputs {
+    pub tool_calls: Vec<ToolCall>,
+}
+
+/// A step of a [Run], which is either the creation of a message or a list of tool calls.
+///
+/// ```
+/// # use serde_json;
+/// # let json = r#"{
+/// #   "id": "step_abc123",
+/// #   "object": "thread.run.step",
+/// #   "created_at": 1699063291,
+/// #   "run_id": "run_abc123",
+/// #   "assistant_id": "asst_abc123",
+/// #   "thread_id": "thread_abc123",
+/// #   "type": "message_creation",
+/// #   "status": "completed",
+/// #   "cancelled_at": null,
+/// #   "completed_at": 1699063291,
+/// #   "expired_at": null,
+/// #   "failed_at": null,
+/// #   "last_error": null,
+/// #   "step_details": {
+/// #     "type": "message_creation",
+/// #     "message_creation": {
+/// #       "message_id": "msg_abc123"
+/// #     }
+/// #   },
+/// #   "usage": {
+/// #     "prompt_tokens": 123,
+/// #     "completion_tokens": 456,
+/// #     "total_tokens": 579
+/// #   }
+/// # }"#;
+/// # let step = serde_json::from_str::<openai_rust::threads::RunStep>(json).unwrap();
+/// use openai_rust::threads::StepDetails;
+/// match step.step_details {
+///     StepDetails::MessageCreation { message_creation } => println!("wrote {}", message_creation.message_id),
+///     StepDetails::ToolCalls { tool_calls } => println!("called {} tools", tool_calls.len()),
+/// }
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct RunStep {
+    pub id: String,
+    pub created_at: u64,
+    pub run_id: String,
+    pub thread_id: String,
+    pub assistant_id: String,
+    /// One of `in_progress`, `cancelled`, `failed`, `completed`, or `expired`.
+    pub status: String,
+    pub step_details: StepDetails,
+    pub completed_at: Option<u64>,
+    pub usage: Option<RunStepUsage>,
+}
+
+/// What happened during a [RunStep].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum StepDetails {
+    /// The assistant wrote a message.
+    MessageCreation { message_creation: MessageCreation },
+    /// The assistant called one or more tools.
+    ToolCalls { tool_calls: Vec<ToolCall> },
+}
+
+/// The message written in a [StepDetails::MessageCreation] step.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct MessageCreation {
+    pub message_id: String,
+}
+
+/// Information about the tokens used by a [RunStep].
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[non_exhaustive]
+#[serde(default)]
+pub struct RunStepUsage {
+    pub prompt_tokens: u32,
+    pub completion_tokens: u32,
+    pub total_tokens: u32,
+}
+
+/// A tool call made by an assistant during a run.
+///
+/// ```
+/// # use serde_json;
+/// # let json = r#"{
+/// #   "id": "call_a4F2kXkvdpqHTQMomf3d8ZYb",
+/// #   "type": "code_interpreter",
+/// #   "code_interpreter": {
+/// #     "input": "import matplotlib.pyplot as plt\nplt.plot([1, 2, 3])\nplt.savefig('plot.png')\nprint('done')",
+/// #     "outputs": [
+/// #       {
+/// #         "type": "logs",
+/// #         "logs": "done\n"
+/// #       },
+/// #       {
+/// #         "type": "image",
+/// #         "image": {
+/// #           "file_id": "file-Lpd6NoXvbTL4WYFjdY5cktwn"
+/// #         }
+/// #       }
+/// #     ]
+/// #   }
+/// # }"#;
+/// # let call = serde_json::from_str::<openai_rust::threads::ToolCall>(json).unwrap();
+/// use openai_rust::threads::{ToolCall, CodeInterpreterOutput};
+/// if let ToolCall::CodeInterpreter { code_interpreter, .. } = call {
+///     println!("{}", code_interpreter.input);
+///     for output in code_interpreter.outputs {
+///         match output {
+///             CodeInterpreterOutput::Logs { logs } => println!("{}", logs),
+///             CodeInterpreterOutput::Image { image } => println!("generated {}", image.file_id),
+///         }
+///     }
+/// }
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ToolCall {
+    CodeInterpreter {
+        id: String,
+        code_interpreter: CodeInterpreterCall,
+    },
+    FileSearch {
+        id: String,
+        #[serde(default)]
+        file_search: FileSearchCall,
+    },
+    Function {
+        id: String,
+        function: FunctionCall,
+    },
+}
+
+impl ToolCall {
+    pub fn id(&self) -> &str {
+        match self {
+            ToolCall::CodeInterpreter { id, .. } | ToolCall::FileSearch { id, .. } | ToolCall::Function { id, .. } => id,
+        }
+    }
+}
+
+/// The output of a [ToolCall], submitted with [Client::submit_tool_outputs](crate::Client::submit_tool_outputs).
+///
+/// ```
+/// # let json = r#"{"id": "call_abc123", "type": "function", "function": {"name": "get_weather", "arguments": "{\"location\": \"Paris\"}"}}"#;
+/// # let call = serde_json::from_str::<openai_rust::threads::ToolCall>(json).unwrap();
+/// use openai_rust::threads::ToolOutput;
+/// #[derive(serde::Serialize)]
+/// struct Weather {
+///     temperature: i32,
+/// }
+/// let output = ToolOutput::new(&call, &Weather { temperature: 18 }).unwrap();
+/// assert_eq!(output.tool_call_id, "call_abc123");
+/// assert_eq!(output.output, r#"{"temperature":18}"#);
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ToolOutput {
+    pub tool_call_id: String,
+    pub output: String,
+}
+
+impl ToolOutput {
+    /// The output of `call`, with `value` serialized as JSON.
+    pub fn new(call: &ToolCall, value: &impl Serialize) -> serde_json::Result<ToolOutput> {
+        Ok(ToolOutput::text(call, serde_json::to_string(value)?))
+    }
+
+    /// The output of `call` as text, sent as is.
+    pub fn text(call: &ToolCall, output: impl Into<String>) -> ToolOutput {
+        ToolOutput {
+            tool_call_id: call.id().to_owned(),
+            output: output.into(),
+        }
+    }
+}
+
+/// The code run by the code interpreter tool and its outputs.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct CodeInterpreterCall {
+    /// The Python code that was run.
+    pub input: String,
+    #[serde(default)]
+    pub outputs: Vec<CodeInterpreterOutput>,
+}
+
+/// An output of the code interpreter tool.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CodeInterpreterOutput {
+    /// Text printed by the code.
+    Logs { logs: String },
+    /// An image generated by the code, which can be downloaded through the Files API.
+    Image { image: CodeInterpreterImage },
+}
+
+/// An image generated by the code interpreter tool.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct CodeInterpreterImage {
+    pub file_id: String,
+}
+
+/// The results of the file search tool.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[non_exhaustive]
+pub struct FileSearchCall {
+    /// Only included when requested.
+    #[serde(default)]
+    pub results: Vec<FileSearchResult>,
+}
+
+/// A chunk of a file found by the file search tool.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct FileSearchResult {
+    pub file_id: String,
+    pub file_name: String,
+    pub score: f32,
+}
+
+/// A function called by the assistant.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct FunctionCall {
+    pub name: String,
+    /// The arguments as a JSON string.
+    pub arguments: String,
+    /// The output of the function, once it has been submitted.
+    pub output: Option<String>,
+}
+
+/// A change in the content of a message, streamed in a [RunEvent::MessageDelta].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct MessageDelta {
+    /// The ID of the message.
+    pub id: String,
+    pub delta: MessageDeltaContent,
+}
+
+impl MessageDelta {
+    /// The text added to the message.
+    pub fn text(&self) -> String {
+        self.delta
+            .content
+            .iter()
+            .filter_map(|content| match content {
+                MessageContentDelta::Text { text, .. } => text.value.as_deref(),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+/// The fields of a [MessageDelta].
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[non_exhaustive]
+#[serde(default)]
+pub struct MessageDeltaContent {
+    pub role: Option<String>,
+    pub content: Vec<MessageContentDelta>,
+}
+
+/// A change in a part of the content of a message.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MessageContentDelta {
+    Text {
+        /// The index of the part in the [content](Message::content) of the message.
+        index: usize,
+        text: TextDelta,
+    },
+    ImageFile {
+        index: usize,
+        image_file: ImageFile,
+    },
+    ImageUrl {
+        index: usize,
+        image_url: ImageUrl,
+    },
+    Refusal {
+        index: usize,
+        #[serde(default)]
+        refusal: Option<String>,
+    },
+}
+
+/// The text added to a [MessageContent::Text] part.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[non_exhaustive]
+#[serde(default)]
+pub struct TextDelta {
+    pub value: Option<String>,
+    pub annotations: Vec<serde_json::Value>,
+}
+
+/// A change in a [RunStep], streamed in a [RunEvent::RunStepDelta].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct RunStepDelta {
+    /// The ID of the run step.
+    pub id: String,
+    pub delta: RunStepDeltaDetails,
+}
+
+/// The fields of a [RunStepDelta].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct RunStepDeltaDetails {
+    pub step_details: StepDetailsDelta,
+}
+
+/// A change in the [StepDetails] of a run step.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum StepDetailsDelta {
+    MessageCreation { message_creation: MessageCreation },
+    ToolCalls {
+        #[serde(default)]
+        tool_calls: Vec<ToolCallDelta>,
+    },
+}
+
+/// A part of a [ToolCall], streamed in a [RunStepDelta]. The `id` and `name` come in the first delta of a call,
+/// the arguments and the code are appended to by later deltas with the same `index`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct ToolCallDelta {
+    pub index: usize,
+    #[serde(default)]
+    pub id: Option<String>,
+    /// One of `code_interpreter`, `file_search` or `function`.
+    #[serde(rename = "type")]
+    pub kind: String,
+    #[serde(default)]
+    pub code_interpreter: Option<CodeInterpreterCallDelta>,
+    #[serde(default)]
+    pub function: Option<FunctionCallDelta>,
+}
+
+/// A part of a [CodeInterpreterCall].
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[non_exhaustive]
+#[serde(default)]
+pub struct CodeInterpreterCallDelta {
+    pub input: Option<String>,
+    pub outputs: Vec<CodeInterpreterOutput>,
+}
+
+/// A part of a [FunctionCall].
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[non_exhaustive]
+#[serde(default)]
+pub struct FunctionCallDelta {
+    pub name: Option<String>,
+    pub arguments: Option<String>,
+    pub output: Option<String>,
+}
+
+/// An event of a streamed run, returned by [Client::create_run_stream](crate::Client::create_run_stream)
+/// and [Client::submit_tool_outputs_stream](crate::Client::submit_tool_outputs_stream).
+///
+/// The objects are sent whenever they change, like a [Run] when its status changes.
+/// See <https://platform.openai.com/docs/api-reference/assistants-streaming/events>.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum RunEvent {
+    /// `thread.created`
+    Thread(Thread),
+    /// `thread.run.*`
+    Run(Run),
+    /// `thread.run.step.*`, except deltas
+    RunStep(RunStep),
+    /// `thread.run.step.delta`
+    RunStepDelta(RunStepDelta),
+    /// `thread.message.*`, except deltas
+    Message(Message),
+    /// `thread.message.delta`
+    MessageDelta(MessageDelta),
+    /// An event not known to this crate.
+    Other { event: String, data: serde_json::Value },
+}
+
+impl RunEvent {
+    fn parse(event: &str, data: &str) -> serde_json::Result<RunEvent> {
+        Ok(match event {
+            "thread.created" => RunEvent::Thread(serde_json::from_str(data)?),
+            "thread.run.step.delta" => RunEvent::RunStepDelta(serde_json::from_str(data)?),
+            "thread.message.delta" => RunEvent::MessageDelta(serde_json::from_str(data)?),
+            e if e.starts_with("thread.run.step.") => RunEvent::RunStep(serde_json::from_str(data)?),
+            e if e.starts_with("thread.run.") => RunEvent::Run(serde_json::from_str(data)?),
+            e if e.starts_with("thread.message.") => RunEvent::Message(serde_json::from_str(data)?),
+            _ => RunEvent::Other {
+                event: event.to_owned(),
+                data: serde_json::from_str(data)?,
+            },
+        })
+    }
+}
+
+/// A stream of [RunEvent]s.
+///
+/// The stream ends after the `done` event, and an `error` event is returned as an error.
+///
+/// ```no_run
+/// # tokio_test::block_on(async {
+/// # let client = openai_rust::Client::new("");
+/// use openai_rust::futures_util::StreamExt;
+/// use openai_rust::threads::{RunArguments, RunEvent};
+/// let mut events = client.create_run_stream("thread_abc123", RunArguments::new("asst_abc123")).await.unwrap();
+/// while let Some(event) = events.next().await {
+///     match event.unwrap() {
+///         RunEvent::MessageDelta(delta) => print!("{}", delta.text()),
+///         RunEvent::Run(run) if run.status == "requires_action" => println!("\ntools needed"),
+///         _ => {}
+///     }
+/// }
+/// assert!(events.is_done());
+/// # })
+/// ```
+pub struct RunEventStream {
+    events: EventStream,
+    // set once the done event was received
+    done: bool,
+}
+
+impl RunEventStream {
+    pub(crate) fn new(stream: Pin<Box<dyn Stream<Item = reqwest::Result<Bytes>> + Send>>) -> Self {
+        Self {
+            events: EventStream::new(stream),
+            done: false,
+        }
+    }
+
+    /// Whether the API signaled the end of the stream, rather than the connection closing early.
+    pub fn is_done(&self) -> bool {
+        self.done
+    }
+}
+
+impl Stream for RunEventStream {
+    type Item = crate::error::Result<RunEvent>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.done {
+            return Poll::Ready(None);
+        }
+        let event = match Pin::new(&mut self.events).poll_next(cx) {
+            Poll::Ready(Some(Ok(event))) => event,
+            Poll::Ready(Some(Err(e))) => return Poll::Ready(Some(Err(e.into()))),
+            Poll::Ready(None) => return Poll::Ready(None),
+            Poll::Pending => return Poll::Pending,
+        };
+        let name = event.event.unwrap_or_default();
+        match name.as_str() {
+            "done" => {
+                self.done = true;
+                Poll::Ready(None)
+            }
+            "error" => Poll::Ready(Some(Err(anyhow::anyhow!("run failed: {}", event.data).into()))),
+            _ => Poll::Ready(Some(RunEvent::parse(&name, &event.data).map_err(Into::into))),
+        }
+    }
+}
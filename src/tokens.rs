@@ -0,0 +1,35 @@
+//! Rough token counting, for when an estimate is good enough.
+//!
+//! The estimates assume about four characters per token, which holds for English text
+//! with the tokenizers of the GPT models. Use a real tokenizer when exact counts matter.
+//!
+//! ```
+//! use openai_rust::tokens::estimate_tokens;
+//! assert_eq!(estimate_tokens("Hello GPT!"), 3);
+//! ```
+#[cfg(feature = "chat")]
+use crate::chat::Message;
+
+/// Estimate the amount of tokens in a text.
+pub fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(4)
+}
+
+/// Estimate the amount of tokens a list of messages takes up in the prompt of a chat completion.
+///
+/// Every message has an overhead of a few tokens for its role and delimiters,
+/// and the reply of the assistant is primed with a few more.
+#[cfg(feature = "chat")]
+pub fn estimate_message_tokens(messages: &[Message]) -> usize {
+    count_message_tokens(messages, estimate_tokens)
+}
+
+/// Like [estimate_message_tokens], counting the tokens of each message with `count`.
+#[cfg(feature = "chat")]
+pub(crate) fn count_message_tokens(messages: &[Message], count: impl Fn(&str) -> usize) -> usize {
+    messages
+        .iter()
+        .map(|m| 4 + count(&m.content.text()))
+        .sum::<usize>()
+        + 3
+}
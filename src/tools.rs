@@ -0,0 +1,410 @@
+//! Functions the model can call, and the checks of [Structured Outputs](https://platform.openai.com/docs/guides/structured-outputs) for their parameters.
+//!
+//! With [strict](FunctionDefinition::strict) mode the arguments generated by the model always match the schema,
+//! but the API only accepts a subset of JSON Schema. [FunctionDefinition::validate] checks a schema
+//! before it is sent, so mistakes are caught without a request.
+//!
+//! ```
+//! use openai_rust::tools::{FunctionDefinition, StrictSchemaError};
+//! let function = FunctionDefinition::new(
+//!     "get_weather",
+//!     "Get the current weather in a location",
+//!     serde_json::json!({
+//!         "type": "object",
+//!         "properties": {
+//!             "location": { "type": "string" },
+//!             "unit": { "type": ["string", "null"], "enum": ["celsius", "fahrenheit", null] }
+//!         },
+//!         "required": ["location"],
+//!         "additionalProperties": false
+//!     }),
+//! )
+//! .strict(true);
+//! assert_eq!(
+//!     function.validate(),
+//!     vec![StrictSchemaError::NotRequired {
+//!         path: "#".to_owned(),
+//!         property: "unit".to_owned(),
+//!     }]
+//! );
+//! ```
+//!
+//! Functions implemented in Rust can be collected in a [ToolRegistry],
+//! which lists them for the request and calls them when the model asks for it.
+use std::future::Future;
+use std::pin::Pin;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// The maximum depth of nested objects in a strict schema.
+pub const MAX_DEPTH: usize = 10;
+/// The maximum amount of properties in a strict schema.
+pub const MAX_PROPERTIES: usize = 5000;
+
+/// A function the model can call.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct FunctionDefinition {
+    /// The name of the function, containing only a-z, A-Z, 0-9, underscores and dashes.
+    pub name: String,
+    /// What the function does, used by the model to choose when and how to call it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// The parameters of the function, as a JSON schema object.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parameters: Option<Value>,
+    /// Whether the arguments always follow the schema exactly.
+    /// This requires the schema to meet the constraints checked by [FunctionDefinition::validate].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub strict: Option<bool>,
+}
+
+impl FunctionDefinition {
+    pub fn new(name: impl AsRef<str>, description: impl AsRef<str>, parameters: Value) -> FunctionDefinition {
+        FunctionDefinition {
+            name: name.as_ref().to_owned(),
+            description: Some(description.as_ref().to_owned()),
+            parameters: Some(parameters),
+            strict: None,
+        }
+    }
+
+    pub fn strict(mut self, strict: bool) -> FunctionDefinition {
+        self.strict = Some(strict);
+        self
+    }
+
+    /// Check the parameters against the constraints of strict mode.
+    /// Without strict mode, any schema is accepted.
+    pub fn validate(&self) -> Vec<StrictSchemaError> {
+        if self.strict != Some(true) {
+            return vec![];
+        }
+        match &self.parameters {
+            Some(parameters) => validate_strict_schema(parameters),
+            None => vec![StrictSchemaError::RootNotObject],
+        }
+    }
+}
+
+/// Check a JSON schema against the constraints of strict mode, for functions and the `json_schema` response format.
+///
+/// See <https://platform.openai.com/docs/guides/structured-outputs#supported-schemas>.
+///
+/// ```
+/// use openai_rust::tools::{validate_strict_schema, StrictSchemaError};
+/// let schema = serde_json::json!({
+///     "type": "object",
+///     "properties": {"unit": {"oneOf": [{"const": "celsius"}, {"const": "fahrenheit"}]}},
+///     "required": ["unit"],
+///     "additionalProperties": false
+/// });
+/// assert_eq!(
+///     validate_strict_schema(&schema),
+///     vec![StrictSchemaError::UnsupportedKeyword {
+///         path: "#/properties/unit".to_owned(),
+///         keyword: "oneOf".to_owned()
+///     }]
+/// );
+/// let union = serde_json::json!({"anyOf": [{"type": "object"}, {"type": "string"}]});
+/// assert!(validate_strict_schema(&union).contains(&StrictSchemaError::UnsupportedKeyword {
+///     path: "#".to_owned(),
+///     keyword: "anyOf".to_owned()
+/// }));
+/// ```
+pub fn validate_strict_schema(schema: &Value) -> Vec<StrictSchemaError> {
+    let mut errors = vec![];
+    if schema.get("type").and_then(Value::as_str) != Some("object") {
+        errors.push(StrictSchemaError::RootNotObject);
+    }
+    if schema.get("anyOf").is_some() {
+        errors.push(StrictSchemaError::UnsupportedKeyword {
+            path: "#".to_owned(),
+            keyword: "anyOf".to_owned(),
+        });
+    }
+    let mut properties = 0;
+    validate_node(schema, "#".to_owned(), 0, &mut properties, &mut errors);
+    if properties > MAX_PROPERTIES {
+        errors.push(StrictSchemaError::TooManyProperties {
+            count: properties,
+            max: MAX_PROPERTIES,
+        });
+    }
+    errors
+}
+
+/// Composition and conditional keywords rejected in strict mode. Unions are written with `anyOf`.
+const UNSUPPORTED_KEYWORDS: [&str; 7] = ["oneOf", "allOf", "not", "patternProperties", "if", "then", "else"];
+
+fn validate_node(schema: &Value, path: String, depth: usize, properties: &mut usize, errors: &mut Vec<StrictSchemaError>) {
+    let Some(node) = schema.as_object() else {
+        return;
+    };
+    for keyword in UNSUPPORTED_KEYWORDS {
+        if node.contains_key(keyword) {
+            errors.push(StrictSchemaError::UnsupportedKeyword {
+                path: path.clone(),
+                keyword: keyword.to_owned(),
+            });
+        }
+    }
+    let is_object = match node.get("type") {
+        Some(Value::String(kind)) => kind == "object",
+        Some(Value::Array(kinds)) => kinds.iter().any(|kind| kind == "object"),
+        _ => node.contains_key("properties"),
+    };
+    if is_object {
+        if depth >= MAX_DEPTH {
+            errors.push(StrictSchemaError::TooDeep { path, max: MAX_DEPTH });
+            return;
+        }
+        if node.get("additionalProperties") != Some(&Value::Bool(false)) {
+            errors.push(StrictSchemaError::AdditionalPropertiesAllowed { path: path.clone() });
+        }
+        let required: Vec<&str> = node
+            .get("required")
+            .and_then(Value::as_array)
+            .map(|required| required.iter().filter_map(Value::as_str).collect())
+            .unwrap_or_default();
+        if let Some(props) = node.get("properties").and_then(Value::as_object) {
+            *properties += props.len();
+            for (name, prop) in props {
+                if !required.contains(&name.as_str()) {
+                    errors.push(StrictSchemaError::NotRequired {
+                        path: path.clone(),
+                        property: name.clone(),
+                    });
+                }
+                validate_node(prop, format!("{}/properties/{}", path, name), depth + 1, properties, errors);
+            }
+        }
+    }
+    if let Some(items) = node.get("items") {
+        validate_node(items, format!("{}/items", path), depth, properties, errors);
+    }
+    for keyword in ["anyOf", "$defs", "definitions"] {
+        let children: Vec<(String, &Value)> = match node.get(keyword) {
+            Some(Value::Array(items)) => items.iter().enumerate().map(|(i, v)| (i.to_string(), v)).collect(),
+            Some(Value::Object(items)) => items.iter().map(|(k, v)| (k.clone(), v)).collect(),
+            _ => continue,
+        };
+        for (key, child) in children {
+            validate_node(child, format!("{}/{}/{}", path, keyword, key), depth, properties, errors);
+        }
+    }
+}
+
+/// A part of a JSON schema that is not supported in strict mode.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StrictSchemaError {
+    /// The schema is not an object.
+    RootNotObject,
+    /// An object without `"additionalProperties": false`.
+    AdditionalPropertiesAllowed { path: String },
+    /// A property missing from `required`. Optional properties can be emulated with a union with `null`.
+    NotRequired { path: String, property: String },
+    /// Objects nested too deep.
+    TooDeep { path: String, max: usize },
+    /// Too many properties in total.
+    TooManyProperties { count: usize, max: usize },
+    /// A keyword like `oneOf` or `patternProperties` that strict mode does not support, or `anyOf` at the root.
+    UnsupportedKeyword { path: String, keyword: String },
+}
+
+impl std::fmt::Display for StrictSchemaError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StrictSchemaError::RootNotObject => write!(f, "the schema is not an object"),
+            StrictSchemaError::AdditionalPropertiesAllowed { path } => {
+                write!(f, "{} does not set additionalProperties to false", path)
+            }
+            StrictSchemaError::NotRequired { path, property } => {
+                write!(f, "property {:?} of {} is not required", property, path)
+            }
+            StrictSchemaError::TooDeep { path, max } => {
+                write!(f, "{} is nested more than {} levels deep", path, max)
+            }
+            StrictSchemaError::TooManyProperties { count, max } => {
+                write!(f, "the schema has {} properties, more than the maximum of {}", count, max)
+            }
+            StrictSchemaError::UnsupportedKeyword { path, keyword } => {
+                write!(f, "{} uses {}, which is not supported in strict mode", path, keyword)
+            }
+        }
+    }
+}
+
+impl std::error::Error for StrictSchemaError {}
+
+/// A function the model can call, implemented in Rust. Collected in a [ToolRegistry].
+pub trait Tool: Send + Sync + 'static {
+    /// The name, description and parameters of the function.
+    fn definition(&self) -> FunctionDefinition;
+
+    /// Call the function with the arguments generated by the model, returning its output.
+    fn call(&self, arguments: Value) -> impl Future<Output = anyhow::Result<Value>> + Send;
+}
+
+/// A [Tool] that can be stored as a trait object.
+trait DynTool: Send + Sync {
+    fn call(&self, arguments: Value) -> Pin<Box<dyn Future<Output = anyhow::Result<Value>> + Send + '_>>;
+}
+
+impl<T: Tool> DynTool for T {
+    fn call(&self, arguments: Value) -> Pin<Box<dyn Future<Output = anyhow::Result<Value>> + Send + '_>> {
+        Box::pin(Tool::call(self, arguments))
+    }
+}
+
+/// The tools the model can call, by name.
+///
+/// ```
+/// # tokio_test::block_on(async {
+/// use openai_rust::tools::{FunctionDefinition, Tool, ToolRegistry};
+/// use serde_json::{json, Value};
+///
+/// struct Weather;
+///
+/// impl Tool for Weather {
+///     fn definition(&self) -> FunctionDefinition {
+///         FunctionDefinition::new(
+///             "get_weather",
+///             "Get the current weather in a location",
+///             json!({
+///                 "type": "object",
+///                 "properties": { "location": { "type": "string" } },
+///                 "required": ["location"],
+///                 "additionalProperties": false
+///             }),
+///         )
+///         .strict(true)
+///     }
+///
+///     async fn call(&self, arguments: Value) -> anyhow::Result<Value> {
+///         Ok(json!({ "location": arguments["location"], "temperature": 18 }))
+///     }
+/// }
+///
+/// let registry = ToolRegistry::new().register(Weather);
+/// let mut args = openai_rust::assistants::AssistantArguments::new("gpt-4o");
+/// args.tools = Some(registry.tools());
+/// let output = registry.call("get_weather", r#"{"location": "Paris"}"#).await.unwrap();
+/// assert_eq!(output["temperature"], 18);
+/// assert!(registry.call("get_time", "{}").await.is_err());
+/// # })
+/// ```
+#[derive(Default)]
+pub struct ToolRegistry {
+    tools: Vec<(FunctionDefinition, Box<dyn DynTool>)>,
+}
+
+impl ToolRegistry {
+    pub fn new() -> ToolRegistry {
+        ToolRegistry::default()
+    }
+
+    /// Add a tool, replacing any tool with the same name.
+    pub fn register(mut self, tool: impl Tool) -> ToolRegistry {
+        let definition = tool.definition();
+        self.tools.retain(|(existing, _)| existing.name != definition.name);
+        self.tools.push((definition, Box::new(tool)));
+        self
+    }
+
+    /// The definitions of the tools, in the order they were registered.
+    pub fn definitions(&self) -> Vec<FunctionDefinition> {
+        self.tools.iter().map(|(definition, _)| definition.clone()).collect()
+    }
+
+    /// The tools as function tools, for the `tools` of a request.
+    #[cfg(feature = "assistants")]
+    pub fn tools(&self) -> Vec<crate::assistants::Tool> {
+        self.definitions().into_iter().map(crate::assistants::Tool::function).collect()
+    }
+
+    /// The tools as function tools, for the [tools](crate::chat::ChatArguments::tools) of a chat request.
+    #[cfg(feature = "chat")]
+    pub fn chat_tools(&self) -> Vec<crate::chat::Tool> {
+        self.definitions().into_iter().map(crate::chat::Tool::function).collect()
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.tools.iter().any(|(definition, _)| definition.name == name)
+    }
+
+    /// Call a tool with the arguments as generated by the model, a JSON string.
+    ///
+    /// Fails with [UnknownTool] if no tool has the name.
+    pub async fn call(&self, name: &str, arguments: &str) -> anyhow::Result<Value> {
+        let (_, tool) = self
+            .tools
+            .iter()
+            .find(|(definition, _)| definition.name == name)
+            .ok_or_else(|| UnknownTool { name: name.to_owned() })?;
+        let arguments = match arguments.trim() {
+            "" => Value::Object(Default::default()),
+            arguments => serde_json::from_str(arguments)?,
+        };
+        tool.call(arguments).await
+    }
+
+    /// Call the functions requested by a run concurrently, for [Client::submit_tool_outputs](crate::Client::submit_tool_outputs).
+    ///
+    /// Calls of other tools, like the code interpreter, are skipped.
+    /// Errors are given to the model as `{"error": "..."}`, so it can correct its arguments or tell the user.
+    #[cfg(feature = "assistants")]
+    pub async fn outputs(&self, calls: &[crate::threads::ToolCall]) -> Vec<crate::threads::ToolOutput> {
+        use crate::threads::{ToolCall, ToolOutput};
+        let outputs = calls.iter().filter_map(|call| match call {
+            ToolCall::Function { function, .. } => Some(async move {
+                let value = self.call_or_error(&function.name, &function.arguments).await;
+                ToolOutput::text(call, value.to_string())
+            }),
+            _ => None,
+        });
+        futures_util::future::join_all(outputs).await
+    }
+
+    /// Call the functions requested in a chat reply concurrently, returning a [tool message](crate::chat::Message::tool_result)
+    /// for each call, to add to the messages after the reply.
+    ///
+    /// Errors are given to the model as `{"error": "..."}`, so it can correct its arguments or tell the user.
+    #[cfg(feature = "chat")]
+    pub async fn chat_results(&self, calls: &[crate::chat::ToolCall]) -> Vec<crate::chat::Message> {
+        let results = calls.iter().map(|call| async move {
+            let value = self.call_or_error(&call.function.name, &call.function.arguments).await;
+            crate::chat::Message::tool_result(&call.id, value.to_string())
+        });
+        futures_util::future::join_all(results).await
+    }
+
+    /// Call a tool, turning an error into a JSON object for the model.
+    #[cfg(any(feature = "chat", feature = "assistants"))]
+    async fn call_or_error(&self, name: &str, arguments: &str) -> Value {
+        match self.call(name, arguments).await {
+            Ok(value) => value,
+            Err(e) => serde_json::json!({ "error": e.to_string() }),
+        }
+    }
+}
+
+impl std::fmt::Debug for ToolRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.tools.iter().map(|(definition, _)| &definition.name)).finish()
+    }
+}
+
+/// The model called a tool that is not in the [ToolRegistry].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownTool {
+    pub name: String,
+}
+
+impl std::fmt::Display for UnknownTool {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "there is no tool named {:?}", self.name)
+    }
+}
+
+impl std::error::Error for UnknownTool {}
@@ -0,0 +1,313 @@
+//! See <https://platform.openai.com/docs/api-reference/vector-stores>.
+//! Use with [Client::create_vector_store](crate::Client::create_vector_store),
+//! [Client::create_vector_store_file](crate::Client::create_vector_store_file) and
+//! [Client::create_vector_store_file_batch](crate::Client::create_vector_store_file_batch).
+//!
+//! A vector store holds the chunks of files searched by the `file_search` tool of assistants and responses.
+//! Files are chunked and embedded in the background, so wait for them before searching:
+//!
+//! ```no_run
+//! # tokio_test::block_on(async {
+//! # let api_key = "";
+//! use std::time::Duration;
+//! use openai_rust::vector_stores::{ExpirationPolicy, FileBatchArguments, VectorStoreArguments};
+//! let c = openai_rust::Client::new(api_key);
+//! let mut args = VectorStoreArguments::new();
+//! args.name = Some("Support FAQ".to_owned());
+//! args.expires_after = Some(ExpirationPolicy::last_active_days(7));
+//! let store = c.create_vector_store(args).await.unwrap();
+//! let args = FileBatchArguments::new(vec!["file-abc123".to_owned(), "file-abc456".to_owned()]);
+//! let batch = c.create_vector_store_file_batch(&store.id, args).await.unwrap();
+//! let batch = c.wait_for_vector_store_file_batch(&store.id, &batch.id, Duration::from_secs(1)).await.unwrap();
+//! println!("{} of {} files ready", batch.file_counts.completed, batch.file_counts.total);
+//! # })
+//! ```
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+/// Request arguments for creating a vector store.
+///
+/// See <https://platform.openai.com/docs/api-reference/vector-stores/create>.
+#[derive(Serialize, Debug, Clone, Default, PartialEq)]
+#[non_exhaustive]
+pub struct VectorStoreArguments {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    /// Files to add to the store, uploaded with the purpose `assistants`.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub file_ids: Vec<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_after: Option<ExpirationPolicy>,
+
+    /// How the files are chunked. Only used when [file_ids](VectorStoreArguments::file_ids) is not empty.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chunking_strategy: Option<ChunkingStrategy>,
+
+    /// Up to 16 key-value pairs.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<HashMap<String, String>>,
+}
+
+impl VectorStoreArguments {
+    pub fn new() -> VectorStoreArguments {
+        VectorStoreArguments::default()
+    }
+}
+
+/// Request arguments for modifying a vector store. Only the given fields are changed.
+///
+/// See <https://platform.openai.com/docs/api-reference/vector-stores/modify>.
+#[derive(Serialize, Debug, Clone, Default, PartialEq)]
+#[non_exhaustive]
+pub struct ModifyVectorStoreArguments {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_after: Option<ExpirationPolicy>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<HashMap<String, String>>,
+}
+
+impl ModifyVectorStoreArguments {
+    pub fn new() -> ModifyVectorStoreArguments {
+        ModifyVectorStoreArguments::default()
+    }
+}
+
+/// When a vector store expires, after which it can no longer be searched.
+///
+/// ```
+/// use openai_rust::vector_stores::ExpirationPolicy;
+/// let policy = ExpirationPolicy::last_active_days(7);
+/// assert_eq!(serde_json::to_string(&policy).unwrap(), r#"{"anchor":"last_active_at","days":7}"#);
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ExpirationPolicy {
+    pub anchor: ExpirationAnchor,
+    /// The number of days after the anchor, between 1 and 365.
+    pub days: u32,
+}
+
+impl ExpirationPolicy {
+    /// Expire a store when it wasn't used for the given amount of days.
+    pub fn last_active_days(days: u32) -> ExpirationPolicy {
+        ExpirationPolicy {
+            anchor: ExpirationAnchor::LastActiveAt,
+            days,
+        }
+    }
+}
+
+/// The timestamp an [ExpirationPolicy] counts from.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ExpirationAnchor {
+    /// The last time the store was searched or changed.
+    LastActiveAt,
+}
+
+/// How files are split into chunks.
+///
+/// ```
+/// use openai_rust::vector_stores::ChunkingStrategy;
+/// let strategy = ChunkingStrategy::fixed(800, 400);
+/// assert_eq!(
+///     serde_json::to_string(&strategy).unwrap(),
+///     r#"{"type":"static","static":{"max_chunk_size_tokens":800,"chunk_overlap_tokens":400}}"#
+/// );
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ChunkingStrategy {
+    /// Chunks of 800 tokens overlapping by 400 tokens.
+    Auto,
+    Static {
+        r#static: StaticChunking,
+    },
+    /// The strategy of files chunked before chunking strategies were introduced.
+    #[serde(other)]
+    Other,
+}
+
+impl ChunkingStrategy {
+    /// Chunks of a fixed size, overlapping by at most half of the size.
+    pub fn fixed(max_chunk_size_tokens: u32, chunk_overlap_tokens: u32) -> ChunkingStrategy {
+        ChunkingStrategy::Static {
+            r#static: StaticChunking {
+                max_chunk_size_tokens,
+                chunk_overlap_tokens,
+            },
+        }
+    }
+}
+
+/// The sizes of a [ChunkingStrategy::Static].
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct StaticChunking {
+    /// Between 100 and 4096.
+    pub max_chunk_size_tokens: u32,
+    pub chunk_overlap_tokens: u32,
+}
+
+/// A collection of processed files, searched by the `file_search` tool.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct VectorStore {
+    pub id: String,
+    pub created_at: u64,
+    pub name: Option<String>,
+    #[serde(default)]
+    pub usage_bytes: u64,
+    pub file_counts: FileCounts,
+    /// One of `expired`, `in_progress` or `completed`. A store is `completed` once all of its files were processed.
+    pub status: String,
+    #[serde(default)]
+    pub expires_after: Option<ExpirationPolicy>,
+    #[serde(default)]
+    pub expires_at: Option<u64>,
+    #[serde(default)]
+    pub last_active_at: Option<u64>,
+    #[serde(default)]
+    pub metadata: HashMap<String, String>,
+}
+
+impl VectorStore {
+    /// Whether files are still being processed.
+    pub fn is_in_progress(&self) -> bool {
+        self.status == "in_progress"
+    }
+}
+
+/// The number of files of a [VectorStore] or [VectorStoreFileBatch] by status.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[non_exhaustive]
+#[serde(default)]
+pub struct FileCounts {
+    pub in_progress: u32,
+    pub completed: u32,
+    pub failed: u32,
+    pub cancelled: u32,
+    pub total: u32,
+}
+
+/// The response of [Client::delete_vector_store](crate::Client::delete_vector_store).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct DeletedVectorStore {
+    pub id: String,
+    pub deleted: bool,
+}
+
+/// Request arguments for adding a file to a vector store.
+///
+/// See <https://platform.openai.com/docs/api-reference/vector-stores-files/createFile>.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct VectorStoreFileArguments {
+    /// A file uploaded with the purpose `assistants`.
+    pub file_id: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chunking_strategy: Option<ChunkingStrategy>,
+}
+
+impl VectorStoreFileArguments {
+    pub fn new(file_id: impl AsRef<str>) -> VectorStoreFileArguments {
+        VectorStoreFileArguments {
+            file_id: file_id.as_ref().to_owned(),
+            chunking_strategy: None,
+        }
+    }
+}
+
+/// A file in a vector store. Its ID is the ID of the file in the Files API.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct VectorStoreFile {
+    pub id: String,
+    pub created_at: u64,
+    pub vector_store_id: String,
+    /// One of `in_progress`, `completed`, `cancelled` or `failed`.
+    pub status: String,
+    #[serde(default)]
+    pub usage_bytes: u64,
+    /// Why the file could not be processed, when the status is `failed`.
+    #[serde(default)]
+    pub last_error: Option<VectorStoreFileError>,
+    #[serde(default)]
+    pub chunking_strategy: Option<ChunkingStrategy>,
+}
+
+impl VectorStoreFile {
+    /// Whether the file is still being processed.
+    pub fn is_in_progress(&self) -> bool {
+        self.status == "in_progress"
+    }
+}
+
+/// The error of a [VectorStoreFile] that could not be processed.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct VectorStoreFileError {
+    /// One of `server_error`, `unsupported_file` or `invalid_file`.
+    pub code: String,
+    pub message: String,
+}
+
+/// The response of [Client::delete_vector_store_file](crate::Client::delete_vector_store_file).
+/// The file itself is not deleted from the Files API.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct DeletedVectorStoreFile {
+    pub id: String,
+    pub deleted: bool,
+}
+
+/// Request arguments for adding several files to a vector store at once.
+///
+/// See <https://platform.openai.com/docs/api-reference/vector-stores-file-batches/createBatch>.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct FileBatchArguments {
+    /// Files uploaded with the purpose `assistants`.
+    pub file_ids: Vec<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chunking_strategy: Option<ChunkingStrategy>,
+}
+
+impl FileBatchArguments {
+    pub fn new(file_ids: Vec<String>) -> FileBatchArguments {
+        FileBatchArguments {
+            file_ids,
+            chunking_strategy: None,
+        }
+    }
+}
+
+/// A batch of files added to a vector store.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct VectorStoreFileBatch {
+    pub id: String,
+    pub created_at: u64,
+    pub vector_store_id: String,
+    /// One of `in_progress`, `completed`, `cancelled` or `failed`.
+    pub status: String,
+    pub file_counts: FileCounts,
+}
+
+impl VectorStoreFileBatch {
+    /// Whether files of the batch are still being processed.
+    pub fn is_in_progress(&self) -> bool {
+        self.status == "in_progress"
+    }
+}
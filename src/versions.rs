@@ -0,0 +1,69 @@
+//! Headers pinning the version of groups of endpoints, like `OpenAI-Beta: assistants=v2` for the Assistants API.
+//!
+//! Beta endpoints reject requests without these headers. The client adds the [DEFAULT_VERSIONS] to every
+//! request of which the path starts with the endpoint, after the base URL. They can be changed with
+//! [Client::with_api_version](crate::Client::with_api_version), like when a new version of a beta is released.
+//!
+//! ```
+//! let client = openai_rust::Client::new("")
+//!     .with_api_version("/assistants", "OpenAI-Beta", "assistants=v3")
+//!     .unwrap()
+//!     .with_api_version("/threads", "OpenAI-Beta", "assistants=v3")
+//!     .unwrap();
+//! ```
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+
+/// The endpoint, header and value pinned by default.
+pub const DEFAULT_VERSIONS: &[(&str, &str, &str)] = &[
+    ("/assistants", "OpenAI-Beta", "assistants=v2"),
+    ("/threads", "OpenAI-Beta", "assistants=v2"),
+    ("/vector_stores", "OpenAI-Beta", "assistants=v2"),
+];
+
+/// The version headers of each endpoint.
+#[derive(Debug, Clone)]
+pub(crate) struct Versions {
+    endpoints: Vec<(String, HeaderMap)>,
+}
+
+impl Default for Versions {
+    fn default() -> Self {
+        let mut versions = Versions { endpoints: vec![] };
+        for (endpoint, name, value) in DEFAULT_VERSIONS {
+            versions.set(endpoint, HeaderName::from_bytes(name.as_bytes()).unwrap(), HeaderValue::from_static(value));
+        }
+        versions
+    }
+}
+
+impl Versions {
+    /// Replace the value of a header for an endpoint.
+    pub(crate) fn set(&mut self, endpoint: &str, name: HeaderName, value: HeaderValue) {
+        match self.endpoints.iter_mut().find(|(e, _)| e == endpoint) {
+            Some((_, headers)) => {
+                headers.insert(name, value);
+            }
+            None => {
+                let mut headers = HeaderMap::new();
+                headers.insert(name, value);
+                self.endpoints.push((endpoint.to_owned(), headers));
+            }
+        }
+    }
+
+    /// Add the headers of the endpoints matching `path` to a request,
+    /// unless the request already has a value for them.
+    pub(crate) fn apply(&self, path: &str, request: &mut reqwest::Request) {
+        let matching = self.endpoints.iter().filter(|(endpoint, _)| {
+            path.strip_prefix(endpoint.as_str())
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+        });
+        for (_, headers) in matching {
+            for (name, value) in headers {
+                if !request.headers().contains_key(name) {
+                    request.headers_mut().insert(name, value.clone());
+                }
+            }
+        }
+    }
+}
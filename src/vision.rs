@@ -0,0 +1,195 @@
+//! Images for vision models, downloaded and embedded in the request.
+//! Send an [ImageUrl] in a message with [ContentPart::image_url](crate::chat::ContentPart::image_url).
+//!
+//! The API can fetch image URLs itself, but not URLs that are private or only reachable from your network.
+//! [fetch_image] downloads an image and turns it into a data URL instead.
+//!
+//! The API scales images down before the model sees them, see [target_size].
+//! Large images can be scaled down before they are sent with a [Resize] implementation,
+//! like `ScaleDown` of the `image` feature, which uses the [image](https://crates.io/crates/image) crate.
+//! This saves bandwidth without changing what the model sees or the [tokens](image_tokens) it costs.
+//!
+//! ```
+//! use openai_rust::vision::{image_tokens, target_size, ImageDetail};
+//! assert_eq!(target_size(4096, 2048, ImageDetail::High), (1536, 768));
+//! assert_eq!(image_tokens(4096, 2048, ImageDetail::High), 1105);
+//! assert_eq!(image_tokens(4096, 2048, ImageDetail::Low), 85);
+//! ```
+use anyhow::{anyhow, Result};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+
+/// The resolution at which the model sees an image.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum ImageDetail {
+    /// Chosen by the API based on the size of the image.
+    #[default]
+    Auto,
+    /// A 512x512 version of the image, for a fixed 85 tokens.
+    Low,
+    /// The image in tiles of 512x512, for 170 tokens per tile and a base of 85.
+    High,
+}
+
+/// An image given to a vision model, by URL or embedded as a data URL.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ImageUrl {
+    pub url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<ImageDetail>,
+}
+
+impl ImageUrl {
+    /// An image the API fetches itself.
+    pub fn new(url: impl AsRef<str>) -> ImageUrl {
+        ImageUrl {
+            url: url.as_ref().to_owned(),
+            detail: None,
+        }
+    }
+
+    /// An image embedded as a data URL, with a MIME type like `image/png`.
+    pub fn from_bytes(image: &[u8], mime: &str) -> ImageUrl {
+        ImageUrl::new(format!("data:{};base64,{}", mime, BASE64.encode(image)))
+    }
+
+    pub fn detail(mut self, detail: ImageDetail) -> ImageUrl {
+        self.detail = Some(detail);
+        self
+    }
+}
+
+/// Scales an image down before it is sent, see [target_size].
+pub trait Resize {
+    /// Scale down an image with a MIME type like `image/png`, returning the new image and its MIME type.
+    fn resize(&self, image: Vec<u8>, mime: &str, detail: ImageDetail) -> Result<(Vec<u8>, String)>;
+}
+
+impl<F: Fn(Vec<u8>, &str, ImageDetail) -> Result<(Vec<u8>, String)>> Resize for F {
+    fn resize(&self, image: Vec<u8>, mime: &str, detail: ImageDetail) -> Result<(Vec<u8>, String)> {
+        self(image, mime, detail)
+    }
+}
+
+/// Scales images to their [target_size] with the [image](https://crates.io/crates/image) crate.
+/// JPEG images stay JPEG, other images are sent as PNG. Images that are small enough are sent unchanged.
+///
+/// ```no_run
+/// # tokio_test::block_on(async {
+/// use openai_rust::vision::{fetch_image, ImageDetail, ScaleDown};
+/// let http = reqwest::Client::new();
+/// let image = fetch_image(&http, "https://intranet.example.com/scan.jpg", ImageDetail::High, Some(&ScaleDown))
+///     .await
+///     .unwrap();
+/// # })
+/// ```
+#[cfg(feature = "image")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ScaleDown;
+
+#[cfg(feature = "image")]
+impl Resize for ScaleDown {
+    fn resize(&self, image: Vec<u8>, mime: &str, detail: ImageDetail) -> Result<(Vec<u8>, String)> {
+        let decoded = image::load_from_memory(&image)?;
+        let (width, height) = target_size(decoded.width(), decoded.height(), detail);
+        if (width, height) == (decoded.width(), decoded.height()) {
+            return Ok((image, mime.to_owned()));
+        }
+        let resized = decoded.resize_exact(width, height, image::imageops::FilterType::Triangle);
+        let mut encoded = std::io::Cursor::new(Vec::new());
+        if mime == "image/jpeg" {
+            // The JPEG encoder has no alpha channel
+            image::DynamicImage::ImageRgb8(resized.to_rgb8()).write_to(&mut encoded, image::ImageFormat::Jpeg)?;
+            Ok((encoded.into_inner(), "image/jpeg".to_owned()))
+        } else {
+            resized.write_to(&mut encoded, image::ImageFormat::Png)?;
+            Ok((encoded.into_inner(), "image/png".to_owned()))
+        }
+    }
+}
+
+/// The largest image [fetch_image] downloads, the limit of the API for images.
+pub const MAX_IMAGE_BYTES: usize = 20 * 1024 * 1024;
+
+/// Download an image, optionally scale it down, and embed it as a data URL.
+/// Fails for images larger than [MAX_IMAGE_BYTES].
+///
+/// ```no_run
+/// # tokio_test::block_on(async {
+/// use openai_rust::vision::{fetch_image, ImageDetail};
+/// let http = reqwest::Client::new();
+/// let image = fetch_image(&http, "https://intranet.example.com/chart.png", ImageDetail::High, None)
+///     .await
+///     .unwrap();
+/// assert!(image.url.starts_with("data:image/png;base64,"));
+/// # })
+/// ```
+pub async fn fetch_image(
+    http: &reqwest::Client,
+    url: &str,
+    detail: ImageDetail,
+    resize: Option<&dyn Resize>,
+) -> Result<ImageUrl> {
+    let mut res = http.get(url).send().await?;
+    if !res.status().is_success() {
+        return Err(anyhow!("fetching {} failed with {}", url, res.status()));
+    }
+    let mime = res
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.split(';').next().unwrap_or_default().trim().to_owned())
+        .filter(|mime| mime.starts_with("image/"))
+        .ok_or_else(|| anyhow!("{} is not an image", url))?;
+    let too_large = || anyhow!("{} is larger than {} bytes", url, MAX_IMAGE_BYTES);
+    if res.content_length().is_some_and(|length| length > MAX_IMAGE_BYTES as u64) {
+        return Err(too_large());
+    }
+    let mut image = Vec::new();
+    while let Some(chunk) = res.chunk().await? {
+        if image.len() + chunk.len() > MAX_IMAGE_BYTES {
+            return Err(too_large());
+        }
+        image.extend_from_slice(&chunk);
+    }
+    let (image, mime) = match resize {
+        Some(resize) => resize.resize(image, &mime, detail)?,
+        None => (image, mime),
+    };
+    Ok(ImageUrl::from_bytes(&image, &mime).detail(detail))
+}
+
+/// The size an image is scaled down to by the API.
+///
+/// With [ImageDetail::High] it is scaled to fit in 2048x2048, and then so the shortest side is at most 768 pixels.
+/// With [ImageDetail::Low] it is scaled to fit in 512x512.
+/// [ImageDetail::Auto] is treated like [ImageDetail::High]. Images are never scaled up.
+pub fn target_size(width: u32, height: u32, detail: ImageDetail) -> (u32, u32) {
+    let scale = |(width, height): (u32, u32), factor: f64| {
+        if factor >= 1.0 {
+            (width, height)
+        } else {
+            ((width as f64 * factor).round() as u32, (height as f64 * factor).round() as u32)
+        }
+    };
+    match detail {
+        ImageDetail::Low => scale((width, height), 512.0 / width.max(height) as f64),
+        ImageDetail::High | ImageDetail::Auto => {
+            let size = scale((width, height), 2048.0 / width.max(height) as f64);
+            scale(size, 768.0 / size.0.min(size.1) as f64)
+        }
+    }
+}
+
+/// The amount of prompt tokens an image costs with GPT-4o.
+pub fn image_tokens(width: u32, height: u32, detail: ImageDetail) -> usize {
+    match detail {
+        ImageDetail::Low => 85,
+        ImageDetail::High | ImageDetail::Auto => {
+            let (width, height) = target_size(width, height, detail);
+            85 + 170 * width.div_ceil(512) as usize * height.div_ceil(512) as usize
+        }
+    }
+}
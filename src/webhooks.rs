@@ -0,0 +1,216 @@
+//! Verify and parse the webhook events OpenAI sends to your server.
+//!
+//! See <https://platform.openai.com/docs/guides/webhooks>.
+//!
+//! Webhooks are signed following the [Standard Webhooks](https://www.standardwebhooks.com/) specification,
+//! with the `webhook-id`, `webhook-timestamp` and `webhook-signature` headers.
+//! [Webhook::unwrap] checks the signature and the timestamp before parsing the body,
+//! so forged and replayed requests are rejected.
+//!
+//! ```
+//! use openai_rust::webhooks::{EventType, Webhook};
+//! # let secret = "whsec_c2VjcmV0";
+//! # let body = r#"{"id": "evt_123", "object": "event", "created_at": 1750287078, "type": "batch.completed", "data": {"id": "batch_123"}}"#;
+//! # let timestamp = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_secs().to_string();
+//! # let signature = openai_rust::webhooks::sign(secret, "wh_123", &timestamp, body.as_bytes()).unwrap();
+//! # let mut headers = reqwest::header::HeaderMap::new();
+//! # headers.insert("webhook-id", "wh_123".parse().unwrap());
+//! # headers.insert("webhook-timestamp", timestamp.parse().unwrap());
+//! # headers.insert("webhook-signature", signature.parse().unwrap());
+//! let webhook = Webhook::new(secret).unwrap();
+//! let event = webhook.unwrap(&headers, body.as_bytes()).unwrap();
+//! if event.kind == EventType::BatchCompleted {
+//!     println!("batch {} is done", event.data.id);
+//! }
+//! # assert_eq!(event.data.id, "batch_123");
+//! # assert!(webhook.unwrap(&headers, b"{}").is_err());
+//! ```
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use hmac::{Hmac, Mac};
+use reqwest::header::HeaderMap;
+use sha2::Sha256;
+use serde::{Deserialize, Serialize};
+
+/// How far the timestamp of a webhook may be from the current time by default.
+pub const DEFAULT_TOLERANCE: Duration = Duration::from_secs(5 * 60);
+
+/// Verifies webhooks with the signing secret of an endpoint.
+#[derive(Clone)]
+pub struct Webhook {
+    secret: Vec<u8>,
+    tolerance: Duration,
+}
+
+impl std::fmt::Debug for Webhook {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Webhook").field("tolerance", &self.tolerance).finish_non_exhaustive()
+    }
+}
+
+impl Webhook {
+    /// Use the signing secret of the endpoint, which starts with `whsec_`.
+    pub fn new(secret: &str) -> Result<Webhook, WebhookError> {
+        Ok(Webhook {
+            secret: decode_secret(secret)?,
+            tolerance: DEFAULT_TOLERANCE,
+        })
+    }
+
+    /// How far the timestamp of a webhook may be from the current time. Defaults to [DEFAULT_TOLERANCE].
+    pub fn tolerance(mut self, tolerance: Duration) -> Webhook {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Check the signature and timestamp of a webhook.
+    ///
+    /// `body` has to be the raw body of the request, as JSON parsed and serialized again may be formatted differently.
+    pub fn verify(&self, headers: &HeaderMap, body: &[u8]) -> Result<(), WebhookError> {
+        let header = |name: &'static str| {
+            headers
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .ok_or(WebhookError::MissingHeader(name))
+        };
+        let id = header("webhook-id")?;
+        let timestamp = header("webhook-timestamp")?;
+        let signatures = header("webhook-signature")?;
+
+        let sent_at = timestamp.parse::<u64>().map_err(|_| WebhookError::InvalidTimestamp)?;
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        if now.abs_diff(sent_at) > self.tolerance.as_secs() {
+            return Err(WebhookError::InvalidTimestamp);
+        }
+
+        let expected = mac(&self.secret, id, timestamp, body)?;
+        // There can be several signatures while the secret is rotated
+        let valid = signatures
+            .split(' ')
+            .filter_map(|signature| signature.strip_prefix("v1,"))
+            .filter_map(|signature| BASE64.decode(signature).ok())
+            .any(|signature| expected.clone().verify_slice(&signature).is_ok());
+        if valid {
+            Ok(())
+        } else {
+            Err(WebhookError::InvalidSignature)
+        }
+    }
+
+    /// Verify a webhook, see [Webhook::verify], and parse its body.
+    pub fn unwrap(&self, headers: &HeaderMap, body: &[u8]) -> Result<Event, WebhookError> {
+        self.verify(headers, body)?;
+        serde_json::from_slice(body).map_err(WebhookError::Json)
+    }
+}
+
+/// The `webhook-signature` header for a body, to test a webhook handler.
+///
+/// ```
+/// let signature = openai_rust::webhooks::sign(
+///     "whsec_MfKQ9r8GKYqrTwjUPD8ILPZIo2LaLaSw",
+///     "msg_p5jXN8AQM9LWM0D4loKWxJek",
+///     "1614265330",
+///     br#"{"test": 2432232314}"#,
+/// );
+/// assert_eq!(signature.unwrap(), "v1,g0hM9SsE+OTPJTGt/tmIKtSyZlE3uFJELVlNIOLJ1OE=");
+/// ```
+pub fn sign(secret: &str, id: &str, timestamp: &str, body: &[u8]) -> Result<String, WebhookError> {
+    let secret = decode_secret(secret)?;
+    let signature = mac(&secret, id, timestamp, body)?.finalize().into_bytes();
+    Ok(format!("v1,{}", BASE64.encode(signature)))
+}
+
+fn decode_secret(secret: &str) -> Result<Vec<u8>, WebhookError> {
+    BASE64
+        .decode(secret.strip_prefix("whsec_").unwrap_or(secret))
+        .map_err(|_| WebhookError::InvalidSecret)
+}
+
+/// The HMAC-SHA256 of `{id}.{timestamp}.{body}`, which is signed by OpenAI.
+fn mac(secret: &[u8], id: &str, timestamp: &str, body: &[u8]) -> Result<Hmac<Sha256>, WebhookError> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret).map_err(|_| WebhookError::InvalidSecret)?;
+    mac.update(format!("{}.{}.", id, timestamp).as_bytes());
+    mac.update(body);
+    Ok(mac)
+}
+
+/// An event sent to a webhook.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct Event {
+    pub id: String,
+    pub created_at: u64,
+    #[serde(rename = "type")]
+    pub kind: EventType,
+    /// The object the event is about, like the batch that completed.
+    pub data: EventData,
+}
+
+/// What happened to the object of an [Event].
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EventType {
+    #[serde(rename = "batch.completed")]
+    BatchCompleted,
+    #[serde(rename = "batch.failed")]
+    BatchFailed,
+    #[serde(rename = "batch.cancelled")]
+    BatchCancelled,
+    #[serde(rename = "batch.expired")]
+    BatchExpired,
+    #[serde(rename = "fine_tuning.job.succeeded")]
+    FineTuningJobSucceeded,
+    #[serde(rename = "fine_tuning.job.failed")]
+    FineTuningJobFailed,
+    #[serde(rename = "fine_tuning.job.cancelled")]
+    FineTuningJobCancelled,
+    #[serde(rename = "response.completed")]
+    ResponseCompleted,
+    #[serde(rename = "response.failed")]
+    ResponseFailed,
+    #[serde(rename = "response.cancelled")]
+    ResponseCancelled,
+    #[serde(rename = "response.incomplete")]
+    ResponseIncomplete,
+    /// An event not yet supported by this crate.
+    #[serde(other)]
+    Other,
+}
+
+/// The object of an [Event], which can be fetched with its ID.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct EventData {
+    pub id: String,
+}
+
+/// Why a webhook was rejected.
+#[derive(Debug)]
+pub enum WebhookError {
+    /// The signing secret is not base64 encoded.
+    InvalidSecret,
+    /// A header of the webhook is missing.
+    MissingHeader(&'static str),
+    /// The timestamp is too far from the current time, or not a timestamp at all.
+    InvalidTimestamp,
+    /// None of the signatures match the body.
+    InvalidSignature,
+    /// The body is not a valid event.
+    Json(serde_json::Error),
+}
+
+impl std::fmt::Display for WebhookError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WebhookError::InvalidSecret => write!(f, "the webhook secret is not base64 encoded"),
+            WebhookError::MissingHeader(name) => write!(f, "missing {} header", name),
+            WebhookError::InvalidTimestamp => write!(f, "the webhook timestamp is out of range"),
+            WebhookError::InvalidSignature => write!(f, "the webhook signature is invalid"),
+            WebhookError::Json(e) => write!(f, "invalid webhook event: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for WebhookError {}
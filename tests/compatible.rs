@@ -0,0 +1,77 @@
+// Responses recorded from OpenAI compatible servers (Ollama, llama.cpp, vLLM and LocalAI).
+// These run without an API key.
+use openai_rust::chat::stream::ChatCompletionChunk;
+use openai_rust::chat::ChatCompletion;
+use openai_rust::completions::CompletionResponse;
+use openai_rust::embeddings::EmbeddingsResponse;
+use openai_rust::models::Model;
+use serde::de::DeserializeOwned;
+
+fn fixture<T: DeserializeOwned>(json: &str) -> T {
+    serde_json::from_str(json).unwrap()
+}
+
+#[test]
+pub fn ollama_chat_without_usage() {
+    let res: ChatCompletion = fixture(include_str!("fixtures/compatible/ollama_chat.json"));
+    assert_eq!(res.to_string(), "Hello! How can I help you today?");
+    assert!(res.usage.is_none());
+}
+
+#[test]
+pub fn llamacpp_chat_with_null_id() {
+    let res: ChatCompletion = fixture(include_str!("fixtures/compatible/llamacpp_chat.json"));
+    assert_eq!(res.id, "");
+    assert_eq!(res.usage.unwrap().total_tokens, 23);
+}
+
+#[test]
+pub fn vllm_chat_with_extra_fields() {
+    let res: ChatCompletion = fixture(include_str!("fixtures/compatible/vllm_chat.json"));
+    assert_eq!(res.choices[0].finish_reason, "stop");
+}
+
+#[test]
+pub fn ollama_chunk() {
+    let res: ChatCompletionChunk = fixture(include_str!("fixtures/compatible/ollama_chunk.json"));
+    assert_eq!(res.to_string(), "Hello");
+}
+
+#[test]
+pub fn llamacpp_chunk_without_model() {
+    let res: ChatCompletionChunk = fixture(include_str!("fixtures/compatible/llamacpp_chunk.json"));
+    assert_eq!(res.model, "");
+    assert_eq!(res.to_string(), "Hello");
+}
+
+#[test]
+pub fn vllm_completion() {
+    let res: CompletionResponse = fixture(include_str!("fixtures/compatible/vllm_completion.json"));
+    assert_eq!(res.to_string(), " jumps over the lazy dog");
+}
+
+#[test]
+pub fn ollama_embeddings_without_usage() {
+    let res: EmbeddingsResponse = fixture(include_str!("fixtures/compatible/ollama_embeddings.json"));
+    assert_eq!(res.data[0].embedding.len(), 4);
+    assert!(res.usage.is_none());
+}
+
+#[test]
+pub fn llamacpp_embeddings() {
+    let res: EmbeddingsResponse = fixture(include_str!("fixtures/compatible/llamacpp_embeddings.json"));
+    assert_eq!(res.usage.unwrap().prompt_tokens, 9);
+}
+
+#[test]
+pub fn llamacpp_model() {
+    let res: Model = fixture(include_str!("fixtures/compatible/llamacpp_model.json"));
+    assert_eq!(res.owned_by, "llamacpp");
+}
+
+#[test]
+pub fn localai_model_without_owner() {
+    let res: Model = fixture(include_str!("fixtures/compatible/localai_model.json"));
+    assert_eq!(res.id, "gpt-4");
+    assert_eq!(res.created, 0);
+}
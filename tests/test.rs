@@ -1,6 +1,5 @@
-use futures_util::{StreamExt, FutureExt};
+use futures_util::StreamExt;
 use lazy_static::lazy_static;
-use openai_rust;
 use std::env::var;
 
 lazy_static! {
@@ -18,10 +17,7 @@ pub async fn create_chat() {
     let c = openai_rust::Client::new(&KEY);
     let args = openai_rust::chat::ChatArguments::new(
         "gpt-3.5-turbo",
-        vec![openai_rust::chat::Message {
-            role: "user".to_owned(),
-            content: "Hello GPT!".to_owned(),
-        }],
+        vec![openai_rust::chat::Message::user("Hello GPT!")],
     );
     c.create_chat(args).await.unwrap();
 }
@@ -31,10 +27,7 @@ pub async fn create_chat_stream() {
     let c = openai_rust::Client::new(&KEY);
     let args = openai_rust::chat::ChatArguments::new(
         "gpt-3.5-turbo",
-        vec![openai_rust::chat::Message {
-            role: "user".to_owned(),
-            content: "Hello GPT!".to_owned(),
-        }],
+        vec![openai_rust::chat::Message::user("Hello GPT!")],
     );
 
     c.create_chat_stream(args)
@@ -66,6 +59,7 @@ pub async fn create_completion_logprobs() {
 }
 
 #[tokio::test]
+#[allow(deprecated)]
 pub async fn create_edit() {
     let c = openai_rust::Client::new(&KEY);
     let args = openai_rust::edits::EditArguments::new(
@@ -103,3 +97,77 @@ pub async fn create_image() {
     let args = openai_rust::images::ImageArguments::new("Lovecraftian Dagon");
     c.create_image(args).await.unwrap();
 }
+
+#[tokio::test]
+pub async fn create_response() {
+    let c = openai_rust::Client::new(&KEY);
+    let mut args = openai_rust::responses::ResponseArguments::new("o4-mini", "Hello GPT!");
+    args.reasoning = Some(openai_rust::responses::Reasoning {
+        effort: Some(openai_rust::responses::ReasoningEffort::Low),
+        summary: Some(openai_rust::responses::ReasoningSummary::Auto),
+    });
+    c.create_response(args).await.unwrap();
+}
+
+#[tokio::test]
+pub async fn list_response_input_items() {
+    let c = openai_rust::Client::new(&KEY);
+    let args = openai_rust::responses::ResponseArguments::new("gpt-4o-mini", "Hello GPT!");
+    let res = c.create_response(args).await.unwrap();
+    let items = c
+        .list_response_input_items(&res.id, Default::default())
+        .collect::<Vec<_>>()
+        .await;
+    assert!(items.into_iter().all(|i| i.is_ok()));
+}
+
+#[tokio::test]
+pub async fn get_and_delete_response() {
+    let c = openai_rust::Client::new(&KEY);
+    let args = openai_rust::responses::ResponseArguments::new("gpt-4o-mini", "Hello GPT!");
+    let res = c.create_response(args).await.unwrap();
+    c.get_response(&res.id).await.unwrap();
+    assert!(c.delete_response(&res.id).await.unwrap().deleted);
+}
+
+#[tokio::test]
+pub async fn create_assistant() {
+    use openai_rust::assistants::*;
+    let c = openai_rust::Client::new(&KEY);
+    let mut args = AssistantArguments::new("gpt-4o-mini");
+    args.tools = Some(vec![Tool::file_search(FileSearch {
+        max_num_results: Some(5),
+        ranking_options: Some(RankingOptions {
+            ranker: Some("auto".to_owned()),
+            score_threshold: 0.5,
+        }),
+    })]);
+    c.create_assistant(args).await.unwrap();
+}
+
+#[tokio::test]
+pub async fn create_speech_stream() {
+    use openai_rust::audio::*;
+    let c = openai_rust::Client::new(&KEY);
+    let mut args = SpeechArguments::new("tts-1", "Hello GPT!", Voice::Alloy);
+    args.response_format = Some(AudioFormat::Wav);
+    let mut audio = vec![];
+    let written = c.create_speech_stream(args, &mut audio).await.unwrap();
+    assert_eq!(written, audio.len() as u64);
+    assert!(audio.starts_with(b"RIFF"));
+}
+
+#[tokio::test]
+pub async fn create_transcription() {
+    use openai_rust::audio::*;
+    let c = openai_rust::Client::new(&KEY);
+    let args = SpeechArguments::new("tts-1", "Hello GPT!", Voice::Alloy);
+    let mut audio = vec![];
+    c.create_speech_stream(args, &mut audio).await.unwrap();
+    let mut args = TranscriptionArguments::new("whisper-1", openai_rust::files::Upload::bytes(audio, "hello.mp3"));
+    args.language = Some("en".to_owned());
+    args.response_format = Some(TranscriptionFormat::VerboseJson);
+    args.timestamp_granularities = Some(vec![TimestampGranularity::Word]);
+    let transcript = c.create_transcription(args).await.unwrap();
+    assert!(!transcript.words.is_empty());
+}
@@ -0,0 +1,57 @@
+use openai_rust::webhooks::{sign, Webhook, WebhookError};
+use reqwest::header::HeaderMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const SECRET: &str = "whsec_MfKQ9r8GKYqrTwjUPD8ILPZIo2LaLaSw";
+const BODY: &[u8] = br#"{"id": "evt_123", "object": "event", "created_at": 1750287078, "type": "batch.completed", "data": {"id": "batch_123"}}"#;
+
+fn now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs()
+}
+
+fn headers(timestamp: u64, signature: &str) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    headers.insert("webhook-id", "wh_123".parse().unwrap());
+    headers.insert("webhook-timestamp", timestamp.to_string().parse().unwrap());
+    headers.insert("webhook-signature", signature.parse().unwrap());
+    headers
+}
+
+#[test]
+fn signed_webhooks_are_accepted() {
+    let signature = sign(SECRET, "wh_123", &now().to_string(), BODY).unwrap();
+    let event = Webhook::new(SECRET).unwrap().unwrap(&headers(now(), &signature), BODY).unwrap();
+    assert_eq!(event.data.id, "batch_123");
+}
+
+#[test]
+fn tampered_bodies_are_rejected() {
+    let signature = sign(SECRET, "wh_123", &now().to_string(), BODY).unwrap();
+    let tampered = String::from_utf8_lossy(BODY).replace("batch_123", "batch_456");
+    let res = Webhook::new(SECRET).unwrap().verify(&headers(now(), &signature), tampered.as_bytes());
+    assert!(matches!(res, Err(WebhookError::InvalidSignature)));
+}
+
+#[test]
+fn signatures_of_other_secrets_are_rejected() {
+    let signature = sign("whsec_c2VjcmV0", "wh_123", &now().to_string(), BODY).unwrap();
+    let res = Webhook::new(SECRET).unwrap().verify(&headers(now(), &signature), BODY);
+    assert!(matches!(res, Err(WebhookError::InvalidSignature)));
+}
+
+#[test]
+fn expired_timestamps_are_rejected() {
+    let sent_at = now() - 10 * 60;
+    let signature = sign(SECRET, "wh_123", &sent_at.to_string(), BODY).unwrap();
+    let res = Webhook::new(SECRET).unwrap().verify(&headers(sent_at, &signature), BODY);
+    assert!(matches!(res, Err(WebhookError::InvalidTimestamp)));
+}
+
+#[test]
+fn any_of_several_signatures_may_match() {
+    let old = sign("whsec_c2VjcmV0", "wh_123", &now().to_string(), BODY).unwrap();
+    let new = sign(SECRET, "wh_123", &now().to_string(), BODY).unwrap();
+    let webhook = Webhook::new(SECRET).unwrap();
+    assert!(webhook.verify(&headers(now(), &format!("{} {}", old, new)), BODY).is_ok());
+    assert!(webhook.verify(&headers(now(), &format!("v2,abc {}", old)), BODY).is_err());
+}